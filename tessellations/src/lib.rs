@@ -6,6 +6,14 @@ A library for creating and editing escher tiles
 */
 
 pub mod render;
+pub mod tessellationanalysis;
 pub mod tessellationfigure;
+pub mod tessellationfrieze;
+pub mod tessellationhyperbolic;
 pub mod tessellationline;
+pub mod tessellationparametric;
+pub mod tessellationpolygon;
 pub mod tessellationshape;
+pub mod tessellationstyle;
+pub mod tessellationsubstitution;
+pub mod tessellationwarp;
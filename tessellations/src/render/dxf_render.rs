@@ -0,0 +1,419 @@
+use crate::render::{RenderOptions, Scene, SceneTile};
+use crate::tessellationfigure::{DecorationSegment, TessellationFigure, TessellationPlane};
+use crate::tessellationline::{Point, Transform};
+use crate::tessellationpolygon::{offset_loops, order_strokes, strokes, ClipShape};
+use crate::tessellationstyle::{LineJoin, Style};
+use std::fmt::Write;
+
+/// Layer of the outlines when the drawing has layers
+pub const CUT_LAYER: &str = "CUT";
+/// Layer of the decorations when the drawing has layers
+pub const ENGRAVE_LAYER: &str = "ENGRAVE";
+/// Layer of everything when the drawing has no layers
+const DEFAULT_LAYER: &str = "0";
+
+/// Points of the outlines closer than this in millimetres are the same
+const MERGE_TOLERANCE: f32 = 1e-3;
+
+/// Units, layers and edges of a dxf drawing
+#[derive(Debug, Clone, PartialEq)]
+pub struct DXFOptions {
+    /// millimetres per tile unit
+    pub scale: f32,
+    /// outlines on the cut layer and decorations on the engrave layer,
+    /// otherwise everything is on layer 0
+    pub layers: bool,
+    /// write an edge shared by two tiles once, the outlines become open
+    /// polylines along the edges
+    pub merge_edges: bool,
+    /// write the curves of the decorations as splines, otherwise as
+    /// polylines within `tolerance`
+    pub splines: bool,
+    /// largest distance in millimetres of a polyline from the curve
+    pub tolerance: f32,
+    /// width in millimetres of the cut of the blade or beam, the outlines
+    /// are moved out by half of it so the pieces keep their size. Every
+    /// piece is then cut on its own and the edges are not merged.
+    pub kerf: f32,
+    /// corners of the outlines moved by the kerf
+    pub join: LineJoin,
+}
+
+impl DXFOptions {
+    /// Options for a drawing with `scale` millimetres per tile unit
+    pub fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            layers: true,
+            merge_edges: true,
+            splines: true,
+            tolerance: 0.05,
+            kerf: 0.0,
+            join: LineJoin::Miter,
+        }
+    }
+}
+
+impl Default for DXFOptions {
+    fn default() -> Self {
+        Self::new(50.0)
+    }
+}
+
+/// Drawing exchange format document in millimetres
+#[derive(Debug, Clone, PartialEq)]
+pub struct DXF {
+    data: String,
+}
+
+impl DXF {
+    pub fn save_dxf(&self, path: &std::path::Path) -> bool {
+        std::fs::write(path, &self.data).is_ok()
+    }
+
+    pub fn get_data(&self) -> String {
+        self.data.clone()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct DXFBackend;
+
+pub trait Draft {
+    /// Draw the tiles of a scene in world coordinates, the view of the scene
+    /// is not used
+    fn draft_scene(&self, scene: &Scene, options: &DXFOptions) -> DXF;
+
+    /// Draw every prototile of the figure once
+    fn draft_figure(&self, figure: &TessellationFigure, options: &DXFOptions) -> DXF {
+        let mut scene = Scene::new(&Style::default());
+        let count = scene.add_figure(figure);
+        for k in 0..count {
+            scene.tiles.push(SceneTile {
+                outline: k,
+                transform: Transform::identity(),
+                color: k,
+                rotation: 0,
+                decorated: k == 0,
+            });
+        }
+        self.draft_scene(&scene, options)
+    }
+
+    /// Draw the tiles of the plane inside `shape` in tile units, the tiles on
+    /// the border of the shape are cut. A shape without area gives a drawing
+    /// without entities
+    fn draft_clipped_plane(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        shape: &ClipShape,
+        options: &DXFOptions,
+    ) -> DXF {
+        let tolerance = options.tolerance / options.scale;
+        let points = shape.outline(tolerance);
+        if points.len() < 3 {
+            return self.draft_scene(&Scene::default(), options);
+        }
+        let min = points.iter().fold(points[0], |a, p| a.min(*p));
+        let max = points.iter().fold(points[0], |a, p| a.max(*p));
+        let canvas = RenderOptions::new(
+            max.x - min.x,
+            max.y - min.y,
+            Transform::translation(-min.x, -min.y),
+        );
+        let mut scene = Scene::canvas(&canvas);
+        scene.add_visible_plane(plane, figure);
+        self.draft_scene(&scene.clip(shape, tolerance), options)
+    }
+}
+
+impl Draft for DXFBackend {
+    fn draft_scene(&self, scene: &Scene, options: &DXFOptions) -> DXF {
+        // millimetres with the y axis up
+        let world = Transform::scale(options.scale, -options.scale);
+        let (cut, engrave) = if options.layers {
+            (CUT_LAYER, ENGRAVE_LAYER)
+        } else {
+            (DEFAULT_LAYER, DEFAULT_LAYER)
+        };
+        let mut entities = Entities::default();
+        let polygons: Vec<Vec<Point>> = scene
+            .tiles
+            .iter()
+            .map(|tile| {
+                let m = tile.transform.then(&world);
+                scene.outlines[tile.outline]
+                    .iter()
+                    .map(|&p| m.transform_point(p))
+                    .collect()
+            })
+            .collect();
+        if options.kerf > 0.0 {
+            // every part and hole of a tile that the kerf splits
+            for points in &polygons {
+                for points in offset_loops(points, options.kerf / 2.0, options.join) {
+                    entities.polyline(cut, &points, true);
+                }
+            }
+        } else if options.merge_edges {
+            let strokes = strokes(&polygons, MERGE_TOLERANCE);
+            for mut points in order_strokes(strokes, Point::origin()) {
+                let closed = points.len() > 3 && points[0] == points[points.len() - 1];
+                if closed {
+                    points.pop();
+                }
+                entities.polyline(cut, &points, closed);
+            }
+        } else {
+            for points in &polygons {
+                entities.polyline(cut, points, true);
+            }
+        }
+        for tile in scene.tiles.iter().filter(|t| t.decorated) {
+            let m = tile.transform.then(&world);
+            for decoration in &scene.decorations {
+                for path in subpaths(&decoration.segments(), &m) {
+                    if options.splines && path.curved {
+                        entities.spline(engrave, &path.quadratic());
+                    } else {
+                        let points = path.flatten(options.tolerance);
+                        entities.polyline(engrave, &points, path.closed);
+                    }
+                }
+            }
+        }
+        let mut layers = vec![DEFAULT_LAYER];
+        if options.layers {
+            layers.extend([CUT_LAYER, ENGRAVE_LAYER]);
+        }
+        DXF {
+            data: document(&layers, &entities.data, entities.handle),
+        }
+    }
+}
+
+/// Entities section of a document with a handle for every entity
+struct Entities {
+    data: String,
+    handle: usize,
+}
+
+impl Default for Entities {
+    fn default() -> Self {
+        // the handles below are used by the tables
+        Self {
+            data: String::new(),
+            handle: 0x100,
+        }
+    }
+}
+
+impl Entities {
+    fn header(&mut self, kind: &str, layer: &str, subclass: &str) {
+        self.handle += 1;
+        let _ = write!(
+            self.data,
+            "0\n{}\n5\n{:X}\n100\nAcDbEntity\n8\n{}\n100\n{}\n",
+            kind, self.handle, layer, subclass
+        );
+    }
+
+    fn polyline(&mut self, layer: &str, points: &[Point], closed: bool) {
+        if points.len() < 2 {
+            return;
+        }
+        self.header("LWPOLYLINE", layer, "AcDbPolyline");
+        let _ = write!(
+            self.data,
+            "90\n{}\n70\n{}\n",
+            points.len(),
+            if closed { 1 } else { 0 }
+        );
+        for p in points {
+            let _ = write!(self.data, "10\n{}\n20\n{}\n", p.x, p.y);
+        }
+    }
+
+    /// Quadratic b-spline through every second control point, the knots are
+    /// doubled so every piece is a quadratic bezier curve
+    fn spline(&mut self, layer: &str, controls: &[Point]) {
+        let pieces = controls.len() / 2;
+        if pieces == 0 {
+            return;
+        }
+        self.header("SPLINE", layer, "AcDbSpline");
+        let _ = write!(
+            self.data,
+            "210\n0\n220\n0\n230\n1\n70\n8\n71\n2\n72\n{}\n73\n{}\n74\n0\n",
+            2 * pieces + 4,
+            2 * pieces + 1
+        );
+        let mut knots = vec![0; 3];
+        for k in 1..pieces {
+            knots.extend([k, k]);
+        }
+        knots.extend([pieces; 3]);
+        for knot in knots {
+            let _ = write!(self.data, "40\n{}\n", knot);
+        }
+        for p in controls {
+            let _ = write!(self.data, "10\n{}\n20\n{}\n30\n0\n", p.x, p.y);
+        }
+    }
+}
+
+/// Document with the header, the layer table and the entities
+fn document(layers: &[&str], entities: &str, handseed: usize) -> String {
+    let mut data = String::new();
+    let _ = write!(
+        data,
+        "0\nSECTION\n2\nHEADER\n9\n$ACADVER\n1\nAC1015\n9\n$HANDSEED\n5\n{:X}\n9\n$INSUNITS\n70\n4\n9\n$MEASUREMENT\n70\n1\n0\nENDSEC\n",
+        handseed + 1
+    );
+    let _ = write!(
+        data,
+        "0\nSECTION\n2\nTABLES\n0\nTABLE\n2\nLAYER\n5\n2\n100\nAcDbSymbolTable\n70\n{}\n",
+        layers.len()
+    );
+    for (k, layer) in layers.iter().enumerate() {
+        // red for cutting and blue for engraving, the colors laser software
+        // assigns to its operations
+        let color = match *layer {
+            CUT_LAYER => 1,
+            ENGRAVE_LAYER => 5,
+            _ => 7,
+        };
+        let _ = write!(
+            data,
+            "0\nLAYER\n5\n{:X}\n100\nAcDbSymbolTableRecord\n100\nAcDbLayerTableRecord\n2\n{}\n70\n0\n62\n{}\n6\nCONTINUOUS\n",
+            0x10 + k,
+            layer,
+            color
+        );
+    }
+    data.push_str("0\nENDTAB\n0\nENDSEC\n0\nSECTION\n2\nENTITIES\n");
+    data.push_str(entities);
+    data.push_str("0\nENDSEC\n0\nEOF\n");
+    data
+}
+
+/// Part of a decoration from a move to the next
+struct Subpath {
+    start: Point,
+    /// end points with the control point of a curve
+    segments: Vec<(Option<Point>, Point)>,
+    closed: bool,
+    curved: bool,
+}
+
+impl Subpath {
+    /// Control points of the quadratic pieces, a line is a curve with its
+    /// control point in the middle
+    fn quadratic(&self) -> Vec<Point> {
+        let mut controls = vec![self.start];
+        let mut last = self.start;
+        let closing = if self.closed && last != self.end() {
+            Some((None, self.start))
+        } else {
+            None
+        };
+        for &(c, p) in self.segments.iter().chain(closing.iter()) {
+            controls.push(c.unwrap_or_else(|| last.lerp(p, 0.5)));
+            controls.push(p);
+            last = p;
+        }
+        controls
+    }
+
+    /// Points of the subpath with the curves split into lines
+    fn flatten(&self, tolerance: f32) -> Vec<Point> {
+        let mut points = vec![self.start];
+        let mut last = self.start;
+        for &(c, p) in &self.segments {
+            if let Some(c) = c {
+                // the distance of a quadratic curve from its chord
+                let deviation = (last.to_vector() - c.to_vector() * 2.0 + p.to_vector()).length();
+                let n = ((deviation / (8.0 * tolerance)).sqrt().ceil() as usize).max(1);
+                for k in 1..n {
+                    let t = k as f32 / n as f32;
+                    points.push(last.lerp(c, t).lerp(c.lerp(p, t), t));
+                }
+            }
+            points.push(p);
+            last = p;
+        }
+        if self.closed && points.len() > 2 && points[0] == last {
+            points.pop();
+        }
+        points
+    }
+
+    fn end(&self) -> Point {
+        self.segments.last().map_or(self.start, |&(_, p)| p)
+    }
+}
+
+/// Split the segments of a decoration at every move and place them with `m`
+fn subpaths(segments: &[DecorationSegment], m: &Transform) -> Vec<Subpath> {
+    let mut res = Vec::<Subpath>::new();
+    for segment in segments {
+        match *segment {
+            DecorationSegment::MoveTo(p) => res.push(Subpath {
+                start: m.transform_point(p),
+                segments: Vec::new(),
+                closed: false,
+                curved: false,
+            }),
+            DecorationSegment::LineTo(p) => {
+                if let Some(path) = res.last_mut() {
+                    path.segments.push((None, m.transform_point(p)));
+                }
+            }
+            DecorationSegment::QuadTo(c, p) => {
+                if let Some(path) = res.last_mut() {
+                    path.segments
+                        .push((Some(m.transform_point(c)), m.transform_point(p)));
+                    path.curved = true;
+                }
+            }
+            DecorationSegment::Close => {
+                if let Some(path) = res.last_mut() {
+                    path.closed = true;
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_quadratic_controls() {
+        let path = subpaths(
+            &[
+                DecorationSegment::MoveTo(Point::new(0.0, 0.0)),
+                DecorationSegment::QuadTo(Point::new(1.0, 1.0), Point::new(2.0, 0.0)),
+                DecorationSegment::Close,
+            ],
+            &Transform::identity(),
+        );
+        assert_eq!(
+            path[0].quadratic(),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 1.0),
+                Point::new(2.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(0.0, 0.0),
+            ]
+        );
+        let points = path[0].flatten(0.01);
+        assert!(points.len() > 4);
+        assert!(points.iter().all(|p| p.y >= 0.0 && p.y <= 0.5));
+    }
+}
@@ -1,84 +1,238 @@
-use crate::tessellationfigure::{TessellationFigure, TessellationPlane};
-use euclid::Angle;
-use lyon::algorithms::rounded_polygon;
-use lyon::math::{point, Transform};
-use lyon::path::{Path, Polygon, NO_ATTRIBUTES};
+use crate::render::scene::{WARPED_MAX, WARPED_MIN};
+use crate::render::{outline, Scene};
+use crate::tessellationfigure::{
+    DecorationSegment, TessellationFigure, TessellationPlane, TessellationStrip,
+};
+use crate::tessellationline::Transform;
+use crate::tessellationstyle::Style;
+use crate::tessellationsubstitution::SubstitutionTiling;
+use crate::tessellationwarp::Warp;
+use lyon::math::point;
+use lyon::path::path::Builder as PathBuilder;
+use lyon::path::{Path, Polygon};
 use palette::Srgb;
 
+/// Corners in tile units of the part of the plane that is built
+const PLANE_MIN: (f32, f32) = (-8.0, -8.0);
+const PLANE_MAX: (f32, f32) = (8.0, 8.0);
+
 #[derive(Clone, Copy)]
 pub struct LyonBackend;
 
 pub trait Builder {
     /// Compose a figure to a document
     fn build(&self, figure: &TessellationFigure) -> Option<Path>;
+    /// Build a path for the outline of every prototile of the figure
+    fn build_prototiles(&self, figure: &TessellationFigure) -> Vec<Path>;
+    /// Build a path for every decoration of the figure in tile coordinates
+    fn build_decorations(&self, figure: &TessellationFigure) -> Vec<Path>;
+    /// Build a path in world coordinates for every tile of the scene, shrunk
+    /// by the grout of the style
+    fn build_scene(&self, scene: &Scene) -> Vec<TilePath>;
+    /// Build a placement of every tile of the plane with its color from the
+    /// palette of the style, the placements of a prototile are contiguous
     fn build_plane(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
-        colors: &[Srgb],
+        style: &Style,
     ) -> Vec<OutputPrimitive>;
+    /// Build the tiles of the plane placed with `m` and mapped through `warp`,
+    /// the edges are subdivided until they are within `tolerance` of the
+    /// curve in warped units
+    fn build_warped(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        warp: &Warp,
+        m: &Transform,
+        tolerance: f32,
+    ) -> Vec<TilePath>;
+    /// Build the outlines of the copies of every prototile on a frieze
+    /// covering `0..length` in tile units
+    fn build_strip(
+        &self,
+        strip: &TessellationStrip,
+        figure: &TessellationFigure,
+        length: f32,
+    ) -> Vec<Path>;
+    /// Build a path for every tile of a substitution tiling after `depth`
+    /// substitutions, in the order of [`SubstitutionTiling::tiles`]
+    fn build_substitution(&self, tiling: &SubstitutionTiling, depth: usize) -> Vec<Path>;
 }
 
 impl Builder for LyonBackend {
     fn build(&self, figure: &TessellationFigure) -> Option<Path> {
-        let points: Vec<lyon::math::Point> = figure
-            .points()
-            .windows(2)
-            .filter_map(|l| if l[0] != l[1] { Some(l[0]) } else { None })
-            .map(|p| point(p.x, p.y))
-            .collect();
         let mut builder = Path::builder();
-        builder.add_polygon(Polygon {
-            points: &points,
-            closed: true,
-        });
+        for points in figure.prototile_points() {
+            add_outline(&mut builder, &points);
+        }
         //rounded_polygon::add_rounded_polygon(&mut builder, figure_polygon, 0.002, NO_ATTRIBUTES);
         let figure_path = builder.build();
         Some(figure_path)
     }
 
+    fn build_prototiles(&self, figure: &TessellationFigure) -> Vec<Path> {
+        figure
+            .prototile_points()
+            .iter()
+            .map(|points| {
+                let mut builder = Path::builder();
+                add_outline(&mut builder, points);
+                builder.build()
+            })
+            .collect()
+    }
+
+    fn build_decorations(&self, figure: &TessellationFigure) -> Vec<Path> {
+        figure
+            .decorations
+            .iter()
+            .map(|decoration| {
+                let mut builder = Path::builder();
+                let mut open = false;
+                for segment in decoration.segments() {
+                    match segment {
+                        DecorationSegment::MoveTo(p) => {
+                            if open {
+                                builder.end(false);
+                            }
+                            builder.begin(point(p.x, p.y));
+                            open = true;
+                        }
+                        DecorationSegment::LineTo(p) => {
+                            builder.line_to(point(p.x, p.y));
+                        }
+                        DecorationSegment::QuadTo(c, p) => {
+                            builder.quadratic_bezier_to(point(c.x, c.y), point(p.x, p.y));
+                        }
+                        DecorationSegment::Close => {
+                            builder.end(true);
+                            open = false;
+                        }
+                    }
+                }
+                if open {
+                    builder.end(false);
+                }
+                builder.build()
+            })
+            .collect()
+    }
+
+    fn build_scene(&self, scene: &Scene) -> Vec<TilePath> {
+        let outlines = scene.tile_outlines();
+        scene
+            .tiles
+            .iter()
+            .filter_map(|tile| {
+                let polygons = &outlines[tile.outline];
+                if polygons.iter().all(|points| points.is_empty()) {
+                    return None;
+                }
+                let mut builder = Path::builder();
+                for points in polygons.iter().filter(|points| !points.is_empty()) {
+                    let points = points
+                        .iter()
+                        .map(|&p| {
+                            let p = tile.transform.transform_point(p);
+                            point(p.x, p.y)
+                        })
+                        .collect::<Vec<lyon::math::Point>>();
+                    builder.add_polygon(Polygon {
+                        points: &points,
+                        closed: true,
+                    });
+                }
+                Some(TilePath {
+                    path: builder.build(),
+                    color: tile.color,
+                })
+            })
+            .collect()
+    }
+
     fn build_plane(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
-        palette: &[Srgb],
+        style: &Style,
     ) -> Vec<OutputPrimitive> {
+        let scene = Scene::region(plane, figure, PLANE_MIN.into(), PLANE_MAX.into(), style);
         let mut res: Vec<OutputPrimitive> = Vec::new();
-        let mut row = 0;
-        let g = plane.grid(figure, 16.0, 16.0);
-        let mut c = 0;
-        for rotdiv in 1..=figure.rotdiv {
-            let angle = Angle::degrees(360.0 * (rotdiv as f32) / (figure.rotdiv as f32));
-
-            for gridrow in &g {
-                if !figure.is_reversed {
-                    c = row % 2; // use for brick
-                }
-                for gridpoint in gridrow {
-                    if figure.is_reversed {
-                        c = rotdiv - 1; // for diamond
-                    }
-                    if !figure.is_reversed && figure.gridincy < figure.gridincx {
-                        c = row % 3; // used for hexagon
-                    }
-
-                    let cc = palette[(c % 4) as usize];
-                    res.push(OutputPrimitive {
-                        x: gridpoint.x-4.0,
-                        y: gridpoint.y,
-                        r: cc.red,
-                        g: cc.green,
-                        b: cc.blue,
-                        angle: angle.radians,
-                    });
-
-                    c += 1;
-                }
-                row += 1;
+        // the placements of a prototile follow each other so every prototile
+        // is a contiguous range
+        for tile in 0..scene.outlines.len() {
+            for placed in scene.tiles.iter().filter(|t| t.outline == tile) {
+                let t = &placed.transform;
+                let cc: Srgb = scene.color(placed).into();
+                res.push(OutputPrimitive {
+                    x: t.m31 - 4.0,
+                    y: t.m32,
+                    r: cc.red,
+                    g: cc.green,
+                    b: cc.blue,
+                    angle: t.m12.atan2(t.m11),
+                    tile,
+                });
             }
         }
         res
     }
+
+    fn build_warped(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        warp: &Warp,
+        m: &Transform,
+        tolerance: f32,
+    ) -> Vec<TilePath> {
+        let scene = Scene::region(
+            plane,
+            figure,
+            WARPED_MIN.into(),
+            WARPED_MAX.into(),
+            &Style::default(),
+        )
+        .warp(warp, m, &Transform::identity(), tolerance);
+        self.build_scene(&scene)
+    }
+
+    fn build_strip(
+        &self,
+        strip: &TessellationStrip,
+        figure: &TessellationFigure,
+        length: f32,
+    ) -> Vec<Path> {
+        // one pixel per tile unit
+        let (top, bottom) = strip.bounds(figure);
+        let scene = Scene::strip(strip, figure, length, bottom - top, &Style::default());
+        self.build_scene(&scene)
+            .into_iter()
+            .map(|tile| tile.path)
+            .collect()
+    }
+
+    fn build_substitution(&self, tiling: &SubstitutionTiling, depth: usize) -> Vec<Path> {
+        let scene = Scene::substitution(tiling, depth, &Transform::identity(), &Style::default());
+        self.build_scene(&scene)
+            .into_iter()
+            .map(|tile| tile.path)
+            .collect()
+    }
+}
+
+/// Add the closed outline `points` without repeated points
+fn add_outline(builder: &mut PathBuilder, points: &[crate::tessellationline::Point]) {
+    let points: Vec<lyon::math::Point> = outline(points).iter().map(|p| point(p.x, p.y)).collect();
+    if points.is_empty() {
+        return;
+    }
+    builder.add_polygon(Polygon {
+        points: &points,
+        closed: true,
+    });
 }
 
 pub struct OutputPrimitive {
@@ -88,22 +242,61 @@ pub struct OutputPrimitive {
     pub g: f32,
     pub b: f32,
     pub angle: f32,
+    /// index of the prototile
+    pub tile: usize,
+}
+
+/// Outline of a placed tile
+pub struct TilePath {
+    pub path: Path,
+    /// index of the color of the tile
+    pub color: usize,
 }
 
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
-    use crate::tessellationfigure::TessellationFigure;
+    use crate::tessellationfigure::{Decoration, TessellationFigure};
+    use crate::tessellationline::Point;
 
     #[test]
     fn test_square_shader() {
         let f = TessellationFigure::square();
-        let m: Transform = Transform::identity();
         let shaders = LyonBackend.build(&f).unwrap();
         assert_eq!(
             format!("{:?}", shaders),
             "\" M 0.0 0.0 L 0.0 1.0 L 1.0 1.0 L 1.0 0.0 Z\""
         );
     }
+
+    #[test]
+    fn test_decoration_paths() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::polyline(vec![
+            Point::new(0.25, 0.5),
+            Point::new(0.75, 0.5),
+        ]));
+        let paths = LyonBackend.build_decorations(&f);
+        assert_eq!(format!("{:?}", paths[0]), "\" M 0.25 0.5 L 0.75 0.5\"");
+    }
+
+    #[test]
+    fn test_prototile_paths() {
+        let f = TessellationFigure::two_triangles();
+        let paths = LyonBackend.build_prototiles(&f);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(
+            format!("{:?}", paths[1]),
+            "\" M 0.0 0.0 L 1.0 1.0 L 0.0 1.0 Z\""
+        );
+    }
+
+    #[test]
+    fn test_strip_paths() {
+        let f = TessellationFigure::square();
+        let strip = TessellationStrip::new(crate::tessellationfrieze::FriezeGroup::Jump);
+        // two copies per period for the periods -1..=2
+        assert_eq!(LyonBackend.build_strip(&strip, &f, 1.0).len(), 8);
+    }
 }
@@ -0,0 +1,182 @@
+use crate::render::{Builder, LyonBackend};
+use crate::tessellationfigure::{TessellationFigure, TessellationPlane};
+use crate::tessellationline::Point;
+use crate::tessellationstyle::{LineCap, LineJoin, Style};
+use lyon::path::Path;
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, StrokeOptions,
+    StrokeTessellator, StrokeVertex, VertexBuffers,
+};
+use std::ops::Range;
+
+/// Precision and line ends of the triangles of a mesh
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshOptions {
+    /// largest distance in tile units between a curve and the triangles
+    pub tolerance: f32,
+    /// corners of the strokes
+    pub join: LineJoin,
+    /// ends of the open strokes
+    pub cap: LineCap,
+}
+
+impl MeshOptions {
+    /// Options for curves within `tolerance` tile units of the triangles
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+        }
+    }
+}
+
+impl Default for MeshOptions {
+    fn default() -> Self {
+        Self::new(0.02)
+    }
+}
+
+/// Vertex of a mesh in tile units
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeshVertex {
+    pub position: [f32; 2],
+    /// zero for a fill, a stroke of width `w` moves the vertex to
+    /// `position + normal * w / 2`
+    pub normal: [f32; 2],
+}
+
+/// Triangle list of vertex and index buffers
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Mesh {
+    pub vertices: Vec<MeshVertex>,
+    /// three indices into the vertices for every triangle
+    pub indices: Vec<u32>,
+}
+
+/// Placement of a copy of a mesh
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MeshInstance {
+    /// affine map from tile to world coordinates, `[m11, m12, m21, m22, m31,
+    /// m32]` so a point goes to `x * [m11, m12] + y * [m21, m22] + [m31, m32]`
+    pub transform: [f32; 6],
+    /// srgb red, green, blue and alpha from 0 to 1
+    pub color: [f32; 4],
+}
+
+/// Meshes of a prototile and its instances
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TileMesh {
+    pub fill: Mesh,
+    /// zero width stroke along the outline
+    pub stroke: Mesh,
+    /// range of the instances of the prototile in [`PlaneMesh::instances`]
+    pub instances: Range<u32>,
+}
+
+/// Meshes of the prototiles and an instance for every tile of a plane
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlaneMesh {
+    pub tiles: Vec<TileMesh>,
+    pub instances: Vec<MeshInstance>,
+}
+
+pub trait Tessellate: Builder {
+    /// Triangles covering the inside of the path by the nonzero rule
+    fn fill_mesh(&self, path: &Path, options: &MeshOptions) -> Mesh;
+
+    /// Triangles of a stroke of zero width along the path, the width is set
+    /// when drawing with the normals of the vertices
+    fn stroke_mesh(&self, path: &Path, options: &MeshOptions) -> Mesh;
+
+    /// Meshes of every prototile of the figure with an instance for every
+    /// tile of the plane in the rectangle `min` `max` in tile units, colored
+    /// from the palette of the style
+    fn tessellate_plane(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        style: &Style,
+        options: &MeshOptions,
+    ) -> PlaneMesh {
+        let placements = plane.placements(figure, min, max, style.palette.len());
+        let mut res = PlaneMesh::default();
+        for (k, path) in self.build_prototiles(figure).iter().enumerate() {
+            let start = res.instances.len() as u32;
+            for placement in placements.iter().filter(|p| p.tile == k) {
+                let t = &placement.transform;
+                let color = style.color(placement.color);
+                res.instances.push(MeshInstance {
+                    transform: [t.m11, t.m12, t.m21, t.m22, t.m31, t.m32],
+                    color: [
+                        color.r as f32 / 255.0,
+                        color.g as f32 / 255.0,
+                        color.b as f32 / 255.0,
+                        color.opacity(),
+                    ],
+                });
+            }
+            res.tiles.push(TileMesh {
+                fill: self.fill_mesh(path, options),
+                stroke: self.stroke_mesh(path, options),
+                instances: start..res.instances.len() as u32,
+            });
+        }
+        res
+    }
+}
+
+impl Tessellate for LyonBackend {
+    fn fill_mesh(&self, path: &Path, options: &MeshOptions) -> Mesh {
+        let mut buffers: VertexBuffers<MeshVertex, u32> = VertexBuffers::new();
+        let res = FillTessellator::new().tessellate_path(
+            path,
+            &FillOptions::tolerance(options.tolerance).with_fill_rule(FillRule::NonZero),
+            &mut BuffersBuilder::new(&mut buffers, |v: FillVertex| MeshVertex {
+                position: v.position().to_array(),
+                normal: [0.0, 0.0],
+            }),
+        );
+        mesh(buffers, res.is_ok())
+    }
+
+    fn stroke_mesh(&self, path: &Path, options: &MeshOptions) -> Mesh {
+        let join = match options.join {
+            LineJoin::Miter => lyon::path::LineJoin::Miter,
+            LineJoin::Round => lyon::path::LineJoin::Round,
+            LineJoin::Bevel => lyon::path::LineJoin::Bevel,
+        };
+        let cap = match options.cap {
+            LineCap::Butt => lyon::path::LineCap::Butt,
+            LineCap::Round => lyon::path::LineCap::Round,
+            LineCap::Square => lyon::path::LineCap::Square,
+        };
+        let mut buffers: VertexBuffers<MeshVertex, u32> = VertexBuffers::new();
+        let res = StrokeTessellator::new().tessellate_path(
+            path,
+            &StrokeOptions::tolerance(options.tolerance)
+                .with_line_join(join)
+                .with_line_cap(cap),
+            &mut BuffersBuilder::new(&mut buffers, |v: StrokeVertex| MeshVertex {
+                position: v.position_on_path().to_array(),
+                normal: v.normal().to_array(),
+            }),
+        );
+        mesh(buffers, res.is_ok())
+    }
+}
+
+/// Mesh of the buffers, empty when the tessellation failed
+fn mesh(buffers: VertexBuffers<MeshVertex, u32>, ok: bool) -> Mesh {
+    if !ok {
+        return Mesh::default();
+    }
+    Mesh {
+        vertices: buffers.vertices,
+        indices: buffers.indices,
+    }
+}
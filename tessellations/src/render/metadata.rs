@@ -0,0 +1,106 @@
+use crate::tessellationfigure::TessellationFigure;
+use crate::tessellationstyle::Style;
+use serde::{Deserialize, Serialize};
+
+/// Keyword of the png text chunk with the figure
+pub const METADATA_KEYWORD: &str = "tessellation";
+
+/// Figure and style that made an exported image.
+///
+/// Svg documents carry them as json in a `<metadata>` element and png
+/// images in a text chunk, so any exported image can be opened again.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    pub figure: TessellationFigure,
+    #[serde(default)]
+    pub style: Style,
+}
+
+#[derive(Serialize)]
+struct MetadataRef<'a> {
+    figure: &'a TessellationFigure,
+    style: &'a Style,
+}
+
+impl ImageMetadata {
+    /// The json embedded in the images for `figure` drawn with `style`
+    pub fn json(figure: &TessellationFigure, style: &Style) -> String {
+        serde_json::to_string(&MetadataRef { figure, style }).expect("json error")
+    }
+
+    pub fn from_json(text: &str) -> Option<Self> {
+        serde_json::from_str(text).ok()
+    }
+
+    /// Read the `<metadata>` element of an svg document
+    pub fn from_svg(text: &str) -> Option<Self> {
+        let start = text.find("<metadata>")? + "<metadata>".len();
+        let end = start + text[start..].find("</metadata>")?;
+        Self::from_json(&unescape_xml(&text[start..end]))
+    }
+
+    /// Read the text chunk of a png image
+    pub fn from_png(data: &[u8]) -> Option<Self> {
+        let reader = png::Decoder::new(data).read_info().ok()?;
+        let info = reader.info();
+        let text = info
+            .utf8_text
+            .iter()
+            .find(|chunk| chunk.keyword == METADATA_KEYWORD)
+            .and_then(|chunk| chunk.get_text().ok())
+            .or_else(|| {
+                info.uncompressed_latin1_text
+                    .iter()
+                    .find(|chunk| chunk.keyword == METADATA_KEYWORD)
+                    .map(|chunk| chunk.text.clone())
+            })?;
+        Self::from_json(&text)
+    }
+
+    /// Read an exported svg or png file, `None` when it has no figure
+    pub fn load(path: &std::path::Path) -> Option<Self> {
+        let data = std::fs::read(path).ok()?;
+        if data.starts_with(b"\x89PNG") {
+            Self::from_png(&data)
+        } else {
+            Self::from_svg(std::str::from_utf8(&data).ok()?)
+        }
+    }
+}
+
+/// Text that can be written inside an xml element
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        let text = "{\"a\":\"<b> & c\"}";
+        assert_eq!(escape_xml(text), "{\"a\":\"&lt;b&gt; &amp; c\"}");
+        assert_eq!(unescape_xml(&escape_xml(text)), text);
+    }
+
+    #[test]
+    fn test_json() {
+        let f = TessellationFigure::hexagon();
+        let json = ImageMetadata::json(&f, &Style::default());
+        let metadata = ImageMetadata::from_json(&json).unwrap();
+        assert_eq!(metadata.figure.gridincx, f.gridincx);
+        assert_eq!(metadata.style, Style::default());
+    }
+}
@@ -1,12 +1,31 @@
+mod dxf_render;
 mod lyon_render;
+mod mesh_render;
+mod metadata;
+mod options;
+mod pdf_render;
+mod plot_render;
 mod raqote_render;
+mod scene;
+mod solid_render;
 mod svg_render;
+pub use dxf_render::{DXFBackend, DXFOptions, Draft, CUT_LAYER, DXF, ENGRAVE_LAYER};
 pub use lyon_render::Builder;
 pub use lyon_render::LyonBackend;
 pub use lyon_render::OutputPrimitive;
+pub use lyon_render::TilePath;
+pub use mesh_render::{
+    Mesh, MeshInstance, MeshOptions, MeshVertex, PlaneMesh, Tessellate, TileMesh,
+};
+pub use metadata::{ImageMetadata, METADATA_KEYWORD};
+pub use options::RenderOptions;
+pub use pdf_render::{PDFBackend, PDFOptions, Print, PDF};
+pub use plot_render::{Plot, PlotOptions, Plotter, PlotterBackend};
 pub use raqote_render::Backend;
 pub use raqote_render::OutputImage;
 pub use raqote_render::Render;
+pub use scene::{outline, Scene, SceneOverlay, SceneTile};
+pub use solid_render::{Extrude, ExtrudeOptions, Solid};
 pub use svg_render::Compose;
 pub use svg_render::SVGBackend;
 pub use svg_render::SVGImage;
@@ -0,0 +1,64 @@
+use crate::tessellationline::Transform;
+use crate::tessellationstyle::Style;
+
+/// Size, resolution, view and style of a rendered image
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// size of the image in logical pixels
+    pub width: f32,
+    pub height: f32,
+    /// device pixels per logical pixel
+    pub dpr: f32,
+    /// the widths of the lines are in logical pixels
+    pub style: Style,
+    /// maps tile coordinates to logical pixels, used for the plane and the
+    /// overlay of the figure
+    pub view: Transform,
+}
+
+impl RenderOptions {
+    /// Options for an image of `width` by `height` logical pixels showing
+    /// the tiles with `view`
+    pub fn new(width: f32, height: f32, view: Transform) -> Self {
+        Self {
+            width,
+            height,
+            dpr: 1.0,
+            style: Style::default(),
+            view,
+        }
+    }
+
+    /// Size of the image in device pixels
+    pub fn pixel_size(&self) -> (f32, f32) {
+        (
+            (self.width * self.dpr).round(),
+            (self.height * self.dpr).round(),
+        )
+    }
+
+    /// Maps tile coordinates to device pixels
+    pub fn device_view(&self) -> Transform {
+        self.view.then_scale(self.dpr, self.dpr)
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self::new(400.0, 400.0, Transform::scale(70.0, 70.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_device_pixels() {
+        let mut options = RenderOptions::new(1920.0, 1080.0, Transform::scale(100.0, 100.0));
+        options.dpr = 2.0;
+        assert_eq!(options.pixel_size(), (3840.0, 2160.0));
+        assert_eq!(options.device_view().m11, 200.0);
+    }
+}
@@ -0,0 +1,631 @@
+use crate::render::{RenderOptions, Scene};
+use crate::tessellationfigure::{
+    Decoration, DecorationSegment, TessellationFigure, TessellationPlane,
+};
+use crate::tessellationline::{Point, Transform, Vector};
+use crate::tessellationstyle::{Color, LineCap, LineJoin, Stroke};
+use std::fmt::Write;
+
+/// Points of a pdf page per millimetre
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+
+/// Length in millimetres of a crop mark
+const CROP_MARK: f32 = 5.0;
+
+/// Gap in millimetres between a crop mark and the corner it marks
+const CROP_GAP: f32 = 1.0;
+
+/// Size of the page labels in points
+const LABEL_SIZE: f32 = 8.0;
+
+/// Paper, scale and poster pages of a pdf document
+#[derive(Debug, Clone, PartialEq)]
+pub struct PDFOptions {
+    /// size of a sheet of paper in millimetres
+    pub paper_width: f32,
+    pub paper_height: f32,
+    /// border of every sheet in millimetres that is not drawn on, the crop
+    /// marks and labels are in it
+    pub margin: f32,
+    /// millimetres per pixel of the scene
+    pub scale: f32,
+    /// split the scene over as many pages as it needs at `scale`, otherwise
+    /// the scene is scaled down to fit on one page
+    pub poster: bool,
+    /// millimetres of the drawing that are on two neighbouring pages
+    pub overlap: f32,
+    pub crop_marks: bool,
+    pub labels: bool,
+}
+
+impl PDFOptions {
+    /// Options for sheets of `paper_width` by `paper_height` millimetres with
+    /// `scale` millimetres per pixel
+    pub fn new(paper_width: f32, paper_height: f32, scale: f32) -> Self {
+        Self {
+            paper_width,
+            paper_height,
+            margin: 10.0,
+            scale,
+            poster: true,
+            overlap: 10.0,
+            crop_marks: true,
+            labels: true,
+        }
+    }
+
+    /// Options for portrait A4 sheets
+    pub fn a4(scale: f32) -> Self {
+        Self::new(210.0, 297.0, scale)
+    }
+
+    /// Size in millimetres of the part of a sheet that is drawn on
+    fn printable(&self) -> (f32, f32) {
+        (
+            self.paper_width - 2.0 * self.margin,
+            self.paper_height - 2.0 * self.margin,
+        )
+    }
+}
+
+impl Default for PDFOptions {
+    fn default() -> Self {
+        Self::a4(0.5)
+    }
+}
+
+/// Portable document with a page for every sheet of a poster
+#[derive(Debug, Clone, PartialEq)]
+pub struct PDF {
+    data: Vec<u8>,
+    /// rows and columns of pages
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl PDF {
+    pub fn save_pdf(&self, path: &std::path::Path) -> bool {
+        std::fs::write(path, &self.data).is_ok()
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Number of pages
+    pub fn pages(&self) -> usize {
+        self.rows * self.columns
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct PDFBackend;
+
+pub trait Print {
+    /// Print a scene, a poster page shows the part of the image in its
+    /// printable area. Textures are not printed.
+    fn print_scene(&self, scene: &Scene, options: &PDFOptions) -> Option<PDF>;
+
+    /// Print the tiles of the plane covering the image and the figure on top
+    /// with the size, view and style of the render options
+    fn print_plane_with_options(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        render: &RenderOptions,
+        options: &PDFOptions,
+    ) -> Option<PDF> {
+        self.print_scene(&Scene::plane_with_options(plane, figure, render), options)
+    }
+}
+
+impl Print for PDFBackend {
+    fn print_scene(&self, scene: &Scene, options: &PDFOptions) -> Option<PDF> {
+        let (width, height) = options.printable();
+        if scene.width <= 0.0 || scene.height <= 0.0 || width <= 0.0 || height <= 0.0 {
+            return None;
+        }
+        let (scale, overlap) = if options.poster {
+            (
+                options.scale,
+                options.overlap.clamp(0.0, width.min(height) / 2.0),
+            )
+        } else {
+            let fit = (width / scene.width).min(height / scene.height);
+            (options.scale.min(fit), 0.0)
+        };
+        // the pages after the first start `overlap` before the end of the
+        // one before
+        let count = |size: f32, printable: f32| -> usize {
+            if size <= printable + 1e-3 {
+                1
+            } else {
+                ((size - overlap) / (printable - overlap) - 1e-3).ceil() as usize
+            }
+        };
+        let columns = count(scene.width * scale, width);
+        let rows = count(scene.height * scale, height);
+        let mut pages = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            for column in 0..columns {
+                let origin = (
+                    column as f32 * (width - overlap),
+                    row as f32 * (height - overlap),
+                );
+                let mut page = Page::new(options);
+                page.scene(scene, scale, origin);
+                if options.poster && options.crop_marks {
+                    page.crop_marks(row > 0, column > 0, overlap);
+                }
+                if options.labels && rows * columns > 1 {
+                    page.label(&format!(
+                        "{}{}  page {} of {}",
+                        row_name(row),
+                        column + 1,
+                        row * columns + column + 1,
+                        rows * columns
+                    ));
+                }
+                pages.push(page.content);
+            }
+        }
+        Some(PDF {
+            data: document(options, &pages, &alphas(scene)),
+            rows,
+            columns,
+        })
+    }
+}
+
+/// Letters of a row like the columns of a spreadsheet
+fn row_name(row: usize) -> String {
+    let mut name = String::new();
+    let mut k = row + 1;
+    while k > 0 {
+        name.insert(0, (b'A' + ((k - 1) % 26) as u8) as char);
+        k = (k - 1) / 26;
+    }
+    name
+}
+
+/// Number with at most three decimals
+fn number(x: f32) -> String {
+    let s = format!("{:.3}", x);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+/// Name of the graphics state with an opacity
+fn alpha_name(alpha: u8) -> String {
+    format!("A{}", alpha)
+}
+
+/// Every opacity of the colors of a scene below opaque
+fn alphas(scene: &Scene) -> Vec<u8> {
+    let style = &scene.style;
+    let mut colors: Vec<Color> = style.palette.clone();
+    colors.push(style.background);
+    colors.push(style.overlay.color);
+    colors.extend(style.stroke.iter().map(|s| s.color));
+    for decoration in &scene.decorations {
+        colors.extend(decoration.fill);
+        colors.extend(decoration.stroke);
+    }
+    let mut res: Vec<u8> = colors.iter().map(|c| c.a).filter(|&a| a < 255).collect();
+    res.sort_unstable();
+    res.dedup();
+    res
+}
+
+/// Content stream of a page
+struct Page<'a> {
+    options: &'a PDFOptions,
+    content: String,
+    /// opacity of the graphics state
+    alpha: u8,
+}
+
+impl<'a> Page<'a> {
+    fn new(options: &'a PDFOptions) -> Self {
+        Self {
+            options,
+            content: String::new(),
+            alpha: 255,
+        }
+    }
+
+    /// Set the opacity of the color, false when it is not drawn at all
+    fn opacity(&mut self, color: Color) -> bool {
+        if color.a == 0 {
+            return false;
+        }
+        if color.a != self.alpha {
+            self.alpha = color.a;
+            let name = if color.a == 255 {
+                "Opaque".to_string()
+            } else {
+                alpha_name(color.a)
+            };
+            let _ = writeln!(self.content, "/{} gs", name);
+        }
+        true
+    }
+
+    fn fill_color(&mut self, color: Color) {
+        let _ = writeln!(
+            self.content,
+            "{} {} {} rg",
+            number(color.r as f32 / 255.0),
+            number(color.g as f32 / 255.0),
+            number(color.b as f32 / 255.0)
+        );
+    }
+
+    fn stroke_color(&mut self, color: Color) {
+        let _ = writeln!(
+            self.content,
+            "{} {} {} RG",
+            number(color.r as f32 / 255.0),
+            number(color.g as f32 / 255.0),
+            number(color.b as f32 / 255.0)
+        );
+    }
+
+    fn line_style(&mut self, width: f32, join: LineJoin, cap: LineCap) {
+        let join = match join {
+            LineJoin::Miter => 0,
+            LineJoin::Round => 1,
+            LineJoin::Bevel => 2,
+        };
+        let cap = match cap {
+            LineCap::Butt => 0,
+            LineCap::Round => 1,
+            LineCap::Square => 2,
+        };
+        let _ = writeln!(self.content, "{} w {} j {} J", number(width), join, cap);
+    }
+
+    fn polygon(&mut self, points: &[Point], m: &Transform) {
+        for (k, p) in points.iter().enumerate() {
+            let p = m.transform_point(*p);
+            let op = if k == 0 { "m" } else { "l" };
+            let _ = writeln!(self.content, "{} {} {}", number(p.x), number(p.y), op);
+        }
+        self.content.push_str("h\n");
+    }
+
+    /// Fill and stroke the outline, `None` for a stroke that is not drawn
+    fn paint(
+        &mut self,
+        polygons: &[Vec<Point>],
+        m: &Transform,
+        fill: Option<Color>,
+        stroke: Option<&Stroke>,
+    ) {
+        let fill = fill.filter(|&c| self.opacity(c));
+        if let Some(color) = fill {
+            self.fill_color(color);
+            for points in polygons {
+                self.polygon(points, m);
+            }
+            self.content.push_str("f\n");
+        }
+        if let Some(stroke) = stroke.filter(|s| self.opacity(s.color)) {
+            self.stroke_color(stroke.color);
+            self.line_style(stroke.pixel_width(m), stroke.join, stroke.cap);
+            for points in polygons {
+                self.polygon(points, m);
+            }
+            self.content.push_str("S\n");
+        }
+    }
+
+    /// Draw the decorations in tile coordinates transformed with `m`
+    fn decorations(&mut self, decorations: &[Decoration], m: &Transform) {
+        let alpha = self.alpha;
+        let _ = writeln!(
+            self.content,
+            "q {} {} {} {} {} {} cm",
+            number(m.m11),
+            number(m.m12),
+            number(m.m21),
+            number(m.m22),
+            number(m.m31),
+            number(m.m32)
+        );
+        for decoration in decorations {
+            let mut path = String::new();
+            let mut current = Point::origin();
+            for segment in decoration.segments() {
+                match segment {
+                    DecorationSegment::MoveTo(p) => {
+                        let _ = writeln!(path, "{} {} m", p.x, p.y);
+                        current = p;
+                    }
+                    DecorationSegment::LineTo(p) => {
+                        let _ = writeln!(path, "{} {} l", p.x, p.y);
+                        current = p;
+                    }
+                    DecorationSegment::QuadTo(c, p) => {
+                        // the same curve as a cubic one
+                        let c1 = current.lerp(c, 2.0 / 3.0);
+                        let c2 = p.lerp(c, 2.0 / 3.0);
+                        let _ = writeln!(
+                            path,
+                            "{} {} {} {} {} {} c",
+                            c1.x, c1.y, c2.x, c2.y, p.x, p.y
+                        );
+                        current = p;
+                    }
+                    DecorationSegment::Close => path.push_str("h\n"),
+                }
+            }
+            if let Some(fill) = decoration.fill.filter(|&c| self.opacity(c)) {
+                self.fill_color(fill);
+                self.content.push_str(&path);
+                self.content.push_str("f\n");
+            }
+            if let Some(stroke) = decoration.stroke.filter(|&c| self.opacity(c)) {
+                self.stroke_color(stroke);
+                // in tile units like the points
+                self.line_style(decoration.stroke_width, LineJoin::Round, LineCap::Round);
+                self.content.push_str(&path);
+                self.content.push_str("S\n");
+            }
+        }
+        self.content.push_str("Q\n");
+        // the opacity is restored with the graphics state
+        self.alpha = alpha;
+    }
+
+    /// Draw the part of the scene at `origin` in millimetres from the top
+    /// left corner of the image at `scale` millimetres per pixel into the
+    /// printable area of the page
+    fn scene(&mut self, scene: &Scene, scale: f32, origin: (f32, f32)) {
+        let options = self.options;
+        let (width, height) = options.printable();
+        let k = POINTS_PER_MM;
+        // clip to the printable area, the y axis of the page is up
+        let _ = writeln!(
+            self.content,
+            "q {} {} {} {} re W n",
+            number(options.margin * k),
+            number(options.margin * k),
+            number(width * k),
+            number(height * k)
+        );
+        let s = scale * k;
+        let _ = writeln!(
+            self.content,
+            "{} 0 0 {} {} {} cm",
+            number(s),
+            number(-s),
+            number((options.margin - origin.0) * k),
+            number((options.paper_height - options.margin + origin.1) * k)
+        );
+        // and to the image
+        let _ = writeln!(
+            self.content,
+            "0 0 {} {} re W n",
+            number(scene.width),
+            number(scene.height)
+        );
+        let image = [vec![
+            Point::new(0.0, 0.0),
+            Point::new(scene.width, 0.0),
+            Point::new(scene.width, scene.height),
+            Point::new(0.0, scene.height),
+        ]];
+        self.paint(
+            &image,
+            &Transform::identity(),
+            Some(scene.style.background),
+            None,
+        );
+        // the pixels of the image on the page with room for the lines
+        let pad = scene.style.stroke.map_or(0.0, |s| s.width) + 1.0;
+        let region = (
+            Point::new(origin.0 / scale, origin.1 / scale).max(Point::origin())
+                - Vector::new(pad, pad),
+            Point::new((origin.0 + width) / scale, (origin.1 + height) / scale)
+                .min(Point::new(scene.width, scene.height))
+                + Vector::new(pad, pad),
+        );
+        let visible = |points: &mut dyn Iterator<Item = Point>, m: &Transform| {
+            let (mut min, mut max) = (
+                Point::new(f32::MAX, f32::MAX),
+                Point::new(f32::MIN, f32::MIN),
+            );
+            for p in points {
+                let p = m.transform_point(p);
+                min = min.min(p);
+                max = max.max(p);
+            }
+            min.x <= region.1.x && min.y <= region.1.y && max.x >= region.0.x && max.y >= region.0.y
+        };
+        let outlines = scene.tile_outlines();
+        for tile in &scene.tiles {
+            let m = tile.transform.then(&scene.view);
+            if !visible(&mut scene.outlines[tile.outline].iter().copied(), &m) {
+                continue;
+            }
+            self.paint(
+                &outlines[tile.outline],
+                &m,
+                Some(scene.color(tile)),
+                scene.style.stroke.as_ref(),
+            );
+        }
+        // decorations on top of all the tiles
+        if !scene.decorations.is_empty() {
+            for tile in scene.tiles.iter().filter(|t| t.decorated) {
+                let m = tile.transform.then(&scene.view);
+                let mut points = scene
+                    .decorations
+                    .iter()
+                    .flat_map(|d| d.points.iter().copied());
+                if visible(&mut points, &m) {
+                    self.decorations(&scene.decorations, &m);
+                }
+            }
+        }
+        for overlay in &scene.overlays {
+            self.paint(
+                std::slice::from_ref(&scene.outlines[overlay.outline]),
+                &overlay.transform,
+                None,
+                Some(&scene.style.overlay),
+            );
+            if overlay.decorated && !scene.decorations.is_empty() {
+                self.decorations(&scene.decorations, &overlay.transform);
+            }
+        }
+        self.content.push_str("Q\n");
+        self.alpha = 255;
+    }
+
+    /// Marks in the margin where the page is cut, the overlap is cut off at
+    /// the top and left of the pages that are glued over the page before
+    fn crop_marks(&mut self, top: bool, left: bool, overlap: f32) {
+        let options = self.options;
+        let (width, height) = options.printable();
+        let m = options.margin;
+        let xs = [m + if left { overlap } else { 0.0 }, m + width];
+        // from the top of the page
+        let ys = [m + if top { overlap } else { 0.0 }, m + height];
+        let length = CROP_MARK.min(m - CROP_GAP);
+        if length <= 0.0 {
+            return;
+        }
+        let k = POINTS_PER_MM;
+        let ph = options.paper_height;
+        let _ = writeln!(self.content, "q 0 0 0 RG {} w 0 J", number(0.25 * k / 2.0));
+        let line = |content: &mut String, a: (f32, f32), b: (f32, f32)| {
+            let _ = writeln!(
+                content,
+                "{} {} m {} {} l S",
+                number(a.0 * k),
+                number((ph - a.1) * k),
+                number(b.0 * k),
+                number((ph - b.1) * k)
+            );
+        };
+        for &x in &xs {
+            line(
+                &mut self.content,
+                (x, m - CROP_GAP),
+                (x, m - CROP_GAP - length),
+            );
+            let y = m + height + CROP_GAP;
+            line(&mut self.content, (x, y), (x, y + length));
+        }
+        for &y in &ys {
+            line(
+                &mut self.content,
+                (m - CROP_GAP, y),
+                (m - CROP_GAP - length, y),
+            );
+            let x = m + width + CROP_GAP;
+            line(&mut self.content, (x, y), (x + length, y));
+        }
+        self.content.push_str("Q\n");
+    }
+
+    /// Text in the bottom margin
+    fn label(&mut self, text: &str) {
+        let options = self.options;
+        let k = POINTS_PER_MM;
+        let baseline = (options.margin / 2.0) * k - LABEL_SIZE / 3.0;
+        let _ = writeln!(
+            self.content,
+            "q 0 0 0 rg BT /F1 {} Tf {} {} Td ({}) Tj ET Q",
+            number(LABEL_SIZE),
+            number(options.margin * k),
+            number(baseline.max(0.0)),
+            text.replace('\\', "\\\\")
+                .replace('(', "\\(")
+                .replace(')', "\\)")
+        );
+    }
+}
+
+/// Document with a page for every content stream
+fn document(options: &PDFOptions, pages: &[String], alphas: &[u8]) -> Vec<u8> {
+    let k = POINTS_PER_MM;
+    let mut states = String::from("/Opaque << /ca 1 /CA 1 >>");
+    for &alpha in alphas {
+        let a = number(alpha as f32 / 255.0);
+        let _ = write!(states, " /{} << /ca {} /CA {} >>", alpha_name(alpha), a, a);
+    }
+    let resources = format!("<< /Font << /F1 3 0 R >> /ExtGState << {} >> >>", states);
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", 4 + 2 * i))
+        .collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+    for (i, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources {} /Contents {} 0 R >>",
+            number(options.paper_width * k),
+            number(options.paper_height * k),
+            resources,
+            5 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+    let mut data = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(data.len());
+        data.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+    let xref = data.len();
+    let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(table, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        table,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    data.extend_from_slice(table.as_bytes());
+    data
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_row_names() {
+        assert_eq!(row_name(0), "A");
+        assert_eq!(row_name(25), "Z");
+        assert_eq!(row_name(26), "AA");
+    }
+
+    #[test]
+    fn test_number() {
+        assert_eq!(number(1.0), "1");
+        assert_eq!(number(0.25), "0.25");
+        assert_eq!(number(-0.0001), "0");
+        assert_eq!(number(2.00049), "2");
+    }
+}
@@ -0,0 +1,200 @@
+use crate::render::Scene;
+use crate::tessellationfigure::{TessellationFigure, TessellationPlane};
+use crate::tessellationline::Point;
+use crate::tessellationpolygon::{order_strokes, strokes, travel};
+use svg::node::element::path::Data;
+use svg::node::element::{Group, Path, SVG};
+use svg::{Document, Node};
+
+/// Plotter units per millimetre of hpgl
+const HPGL_UNITS: f32 = 40.0;
+
+/// Units and pen of a plot
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotOptions {
+    /// millimetres per tile unit
+    pub scale: f32,
+    /// points of the edges closer than this in millimetres are the same
+    pub tolerance: f32,
+    /// width of the lines in millimetres, only used by svg
+    pub pen_width: f32,
+}
+
+impl PlotOptions {
+    /// Options for a plot with `scale` millimetres per tile unit
+    pub fn new(scale: f32) -> Self {
+        Self {
+            scale,
+            tolerance: 1e-3,
+            pen_width: 0.3,
+        }
+    }
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self::new(20.0)
+    }
+}
+
+/// Strokes of a pen drawing every edge once, in millimetres from the top
+/// left corner of the sheet in the order they are drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plot {
+    /// size of the sheet in millimetres
+    pub width: f32,
+    pub height: f32,
+    /// a closed stroke ends with its first point
+    pub strokes: Vec<Vec<Point>>,
+    pub pen_width: f32,
+}
+
+impl Plot {
+    /// Plot of the edges of the closed polygons in millimetres
+    pub fn new(polygons: &[Vec<Point>], width: f32, height: f32, options: &PlotOptions) -> Self {
+        Self {
+            width,
+            height,
+            strokes: order_strokes(strokes(polygons, options.tolerance), Point::origin()),
+            pen_width: options.pen_width,
+        }
+    }
+
+    /// Distance the pen draws
+    pub fn length(&self) -> f32 {
+        self.strokes
+            .iter()
+            .flat_map(|stroke| stroke.windows(2))
+            .map(|l| (l[1] - l[0]).length())
+            .sum()
+    }
+
+    /// Distance the pen moves up from the corner of the sheet
+    pub fn travel(&self) -> f32 {
+        travel(&self.strokes, Point::origin())
+    }
+
+    /// Document with a path for every stroke in millimetres
+    pub fn svg(&self) -> SVG {
+        let mut group = Group::new()
+            .set("fill", "none")
+            .set("stroke", "#000000")
+            .set("stroke-width", self.pen_width)
+            .set("stroke-linecap", "round")
+            .set("stroke-linejoin", "round");
+        for stroke in &self.strokes {
+            let mut data = Data::new().move_to((stroke[0].x, stroke[0].y));
+            let closed = stroke.len() > 2 && stroke[0] == stroke[stroke.len() - 1];
+            let end = if closed {
+                stroke.len() - 1
+            } else {
+                stroke.len()
+            };
+            for p in &stroke[1..end] {
+                data = data.line_to((p.x, p.y));
+            }
+            if closed {
+                data = data.close();
+            }
+            group.append(Path::new().set("d", data));
+        }
+        Document::new()
+            .set("viewBox", (0, 0, self.width, self.height))
+            .set("width", format!("{}mm", self.width))
+            .set("height", format!("{}mm", self.height))
+            .add(group)
+    }
+
+    /// Hpgl program drawing the strokes with pen 1, the y axis is up
+    pub fn hpgl(&self) -> String {
+        let unit = |p: &Point| {
+            (
+                (p.x * HPGL_UNITS).round() as i32,
+                ((self.height - p.y) * HPGL_UNITS).round() as i32,
+            )
+        };
+        let mut res = String::from("IN;SP1;");
+        for stroke in &self.strokes {
+            let (x, y) = unit(&stroke[0]);
+            let points: Vec<String> = stroke[1..]
+                .iter()
+                .map(|p| {
+                    let (x, y) = unit(p);
+                    format!("{},{}", x, y)
+                })
+                .collect();
+            res.push_str(&format!("PU{},{};PD{};", x, y, points.join(",")));
+        }
+        res.push_str("PU;SP0;\n");
+        res
+    }
+
+    pub fn save_svg(&self, path: &std::path::Path) -> bool {
+        svg::save(path, &self.svg()).is_ok()
+    }
+
+    pub fn save_hpgl(&self, path: &std::path::Path) -> bool {
+        std::fs::write(path, self.hpgl()).is_ok()
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct PlotterBackend;
+
+pub trait Plotter {
+    /// Plot the edges of the tiles of a scene in world coordinates on a sheet
+    /// around them, the view of the scene is not used
+    fn plot_scene(&self, scene: &Scene, options: &PlotOptions) -> Option<Plot>;
+
+    /// Plot the edges of the tiles of the plane in the rectangle `min` `max`
+    /// in tile units, the tiles crossing the border are cut
+    fn plot_plane(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        options: &PlotOptions,
+    ) -> Plot {
+        let polygons: Vec<Vec<Point>> = plane
+            .tiles(figure, min, max, 1)
+            .into_iter()
+            .flat_map(|tile| tile.polygons)
+            .map(|points| {
+                points
+                    .iter()
+                    .map(|&p| ((p - min) * options.scale).to_point())
+                    .collect()
+            })
+            .collect();
+        let size = (max - min) * options.scale;
+        Plot::new(&polygons, size.x, size.y, options)
+    }
+}
+
+impl Plotter for PlotterBackend {
+    fn plot_scene(&self, scene: &Scene, options: &PlotOptions) -> Option<Plot> {
+        let polygons: Vec<Vec<Point>> = scene
+            .tiles
+            .iter()
+            .map(|tile| {
+                let m = tile.transform.then_scale(options.scale, options.scale);
+                scene.outlines[tile.outline]
+                    .iter()
+                    .map(|&p| m.transform_point(p))
+                    .collect()
+            })
+            .collect();
+        let points = polygons.iter().flatten();
+        let min = points.clone().fold(None, |a: Option<Point>, p| {
+            Some(a.map_or(*p, |a| a.min(*p)))
+        })?;
+        let max = points.fold(min, |a, p| a.max(*p));
+        let polygons: Vec<Vec<Point>> = polygons
+            .iter()
+            .map(|points| points.iter().map(|&p| (p - min).to_point()).collect())
+            .collect();
+        let size = max - min;
+        Some(Plot::new(&polygons, size.x, size.y, options))
+    }
+}
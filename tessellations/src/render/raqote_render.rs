@@ -1,240 +1,327 @@
-use crate::tessellationfigure::{TessellationFigure, TessellationPlane};
-use euclid::Angle;
+use crate::render::{RenderOptions, Scene, METADATA_KEYWORD};
+use crate::tessellationfigure::{
+    Decoration, DecorationSegment, TessellationFigure, TessellationPlane, TessellationStrip,
+    TextureImage,
+};
+use crate::tessellationhyperbolic::HyperbolicFigure;
+use crate::tessellationpolygon::ClipShape;
+use crate::tessellationstyle::{self, Color, Stroke, Style};
+use crate::tessellationsubstitution::SubstitutionTiling;
+use crate::tessellationwarp::Warp;
 use raqote::*;
 
+/// Pixels around a period that are rendered and cut off
+const PERIOD_MARGIN: f32 = 2.0;
+
 #[derive(Clone, Copy)]
 pub struct Backend;
 
 pub trait Render {
+    /// Render a scene to a image
+    fn render_scene(&self, scene: &Scene) -> Option<Box<dyn OutputImage>>;
+
     /// Render a figure to a image
     fn render_to_image(
         &self,
         figure: &TessellationFigure,
         m: &Transform,
-    ) -> Option<Box<dyn OutputImage>>;
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::figure(figure, m, style))
+    }
 
-    /// Render a figure and tiling on a plane to a image
+    /// Render the tiles of the plane covering a image of 400 by 400 pixels
+    /// and the figure on top, `m` maps the tiles to the image
     fn render_plane_to_image(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
         m: &Transform,
-    ) -> Option<Box<dyn OutputImage>>;
-}
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        let mut options = RenderOptions::new(400.0, 400.0, *m);
+        options.style = style.clone();
+        self.render_scene(&Scene::plane_with_options(plane, figure, &options))
+    }
 
-impl Render for Backend {
-    fn render_to_image(
+    /// Render a figure to a image with the size, resolution and view of the
+    /// options
+    fn render_with_options(
         &self,
         figure: &TessellationFigure,
-        m: &Transform,
+        options: &RenderOptions,
     ) -> Option<Box<dyn OutputImage>> {
-        let mut dt = DrawTarget::new(400, 400);
-        let colors = vec![
-            SolidSource {
-                r: 0x0,
-                g: 0x88,
-                b: 0x0,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0x0,
-                g: 0x0,
-                b: 0x88,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0xff,
-                g: 0x88,
-                b: 0x0,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0xff,
-                g: 0x0,
-                b: 0xff,
-                a: 0xff,
-            },
-        ];
-        // white background
-        dt.clear(SolidSource {
-            r: 0xff,
-            g: 0xff,
-            b: 0xff,
-            a: 0xff,
-        });
+        self.render_scene(&Scene::figure_with_options(figure, options))
+    }
 
-        let mut pb = PathBuilder::new();
-        let points = figure
-            .points()
-            .windows(2)
-            .filter_map(|l| {
-                if l[0] != l[1] {
-                    Some(m.transform_point(l[0]))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Point>>();
-        let p1 = points[0];
-        pb.move_to(p1.x, p1.y);
-        for p in points.iter().skip(1) {
-            pb.line_to(p.x, p.y);
-        }
-        pb.close();
-        let path = pb.finish();
+    /// Render the tiles of the plane covering the image and the figure on
+    /// top with the size, resolution and view of the options
+    fn render_plane_with_options(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        options: &RenderOptions,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::plane_with_options(plane, figure, options))
+    }
 
-        dt.stroke(
-            &path,
-            &Source::Solid(colors[0]),
-            &StrokeStyle {
-                cap: LineCap::Square,
-                join: LineJoin::Bevel,
-                width: 1.0,
-                miter_limit: 1.,
-                dash_array: vec![],
-                dash_offset: 0.,
-            },
-            &DrawOptions::new(),
-        );
+    /// Render the tiles of the plane inside `shape` in world units with the
+    /// size, resolution and view of the options
+    fn render_clipped_plane(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        shape: &ClipShape,
+        options: &RenderOptions,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::clipped_plane(plane, figure, shape, options))
+    }
 
-        Some(Box::new(dt))
+    /// Render one rectangular period of the plane at `scale` pixels per tile
+    /// unit, the image repeats without seams as a texture
+    fn render_period_to_image(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        scale: f32,
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        let mut scene = Scene::period(plane, figure, scale, style)?;
+        let (width, height) = (scene.width as usize, scene.height as usize);
+        // render with a margin and cut it off, the pixels at the border of
+        // an image collect the coverage of the shapes beyond it
+        scene.width += 2.0 * PERIOD_MARGIN;
+        scene.height += 2.0 * PERIOD_MARGIN;
+        scene.view = scene
+            .view
+            .then_translate(Vector::new(PERIOD_MARGIN, PERIOD_MARGIN));
+        let image = self.render_scene(&scene)?;
+        let (stride, margin) = (scene.width as usize, PERIOD_MARGIN as usize);
+        let mut dt = DrawTarget::new(width as i32, height as i32);
+        for (y, row) in dt.get_data_mut().chunks_exact_mut(width).enumerate() {
+            let start = (y + margin) * stride + margin;
+            row.copy_from_slice(&image.get_data()[start..start + width]);
+        }
+        Some(with_metadata(dt, scene.metadata))
     }
-    fn render_plane_to_image(
+
+    /// Render the tiling placed with `m` and mapped through `warp`, `view`
+    /// maps the warped plane to the image
+    fn render_warped_to_image(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
+        warp: &Warp,
         m: &Transform,
+        view: &Transform,
+        style: &Style,
     ) -> Option<Box<dyn OutputImage>> {
-        let mut dt = DrawTarget::new(400, 400);
-        let colors = vec![
-            SolidSource {
-                r: 0xf6,
-                g: 0x88,
-                b: 0xbb,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0xe8,
-                g: 0xf9,
-                b: 0xe9,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0xba,
-                g: 0xfa,
-                b: 0xa1,
-                a: 0xff,
-            },
-            SolidSource {
-                r: 0x9d,
-                g: 0xe3,
-                b: 0xd0,
-                a: 0xff,
-            },
-        ];
-        // white background
-        dt.clear(SolidSource {
-            r: 0xff,
-            g: 0xff,
-            b: 0xff,
-            a: 0xff,
-        });
-        let mut row = 0;
-        let g = plane.grid(figure, 12.0, 12.0);
-        let mut c = 0;
-
-        for rotdiv in 1..=figure.rotdiv {
-            let angle = Angle::degrees(360.0 * (rotdiv as f32) / (figure.rotdiv as f32));
-
-            for gridrow in &g {
-                if !figure.is_reversed {
-                    c = row % 2; // use for brick
-                }
-                for gridpoint in gridrow {
-                    if figure.is_reversed {
-                        c = rotdiv - 1; // for diamond
-                    }
-                    if !figure.is_reversed && figure.gridincy < figure.gridincx {
-                        c = row % 3; // used for hexagon
-                    }
+        self.render_scene(&Scene::warped(plane, figure, warp, m, view, style))
+    }
+
+    /// Render a frieze of the figure to a image `length` by `height` pixels,
+    /// the strip is scaled to the height of the image
+    fn render_strip_to_image(
+        &self,
+        strip: &TessellationStrip,
+        figure: &TessellationFigure,
+        length: f32,
+        height: f32,
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::strip(strip, figure, length, height, style))
+    }
+
+    /// Render the tiles of a substitution tiling after `depth` substitutions
+    fn render_substitution_to_image(
+        &self,
+        tiling: &SubstitutionTiling,
+        depth: usize,
+        m: &Transform,
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::substitution(tiling, depth, m, style))
+    }
+
+    /// Render the cells of a hyperbolic tiling up to `depth` edges from the
+    /// center on a image of 400 by 400 pixels, `m` maps the unit disk to the
+    /// image
+    fn render_hyperbolic_to_image(
+        &self,
+        figure: &HyperbolicFigure,
+        depth: usize,
+        m: &Transform,
+        style: &Style,
+    ) -> Option<Box<dyn OutputImage>> {
+        let mut options = RenderOptions::new(400.0, 400.0, *m);
+        options.style = style.clone();
+        self.render_hyperbolic_with_options(figure, depth, &options)
+    }
 
-                    let m = Transform::rotation(angle)
-                        .then_scale(70.0, 70.0)
-                        .then_translate(euclid::vec2(gridpoint.x * 70.0, gridpoint.y * 70.0));
-                    let points = figure
-                        .points()
-                        .windows(2)
-                        .filter_map(|l| {
-                            if l[0] != l[1] {
-                                Some(m.transform_point(l[0]))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<Point>>();
-                    let p1 = points[0];
-                    let mut pb = PathBuilder::new();
-
-                    pb.move_to(p1.x, p1.y);
-                    for p in points.iter().skip(1) {
-                        pb.line_to(p.x, p.y);
+    /// Render the cells of a hyperbolic tiling up to `depth` edges from the
+    /// center with the size, resolution and view of the options, the view
+    /// maps the unit disk to the image
+    fn render_hyperbolic_with_options(
+        &self,
+        figure: &HyperbolicFigure,
+        depth: usize,
+        options: &RenderOptions,
+    ) -> Option<Box<dyn OutputImage>> {
+        self.render_scene(&Scene::hyperbolic(figure, depth, options))
+    }
+}
+
+impl Render for Backend {
+    fn render_scene(&self, scene: &Scene) -> Option<Box<dyn OutputImage>> {
+        if scene.width < 1.0 || scene.height < 1.0 {
+            return None;
+        }
+        let mut dt = DrawTarget::new(scene.width as i32, scene.height as i32);
+        let b = scene.style.background;
+        dt.clear(SolidSource::from_unpremultiplied_argb(b.a, b.r, b.g, b.b));
+
+        let outlines = scene.tile_outlines();
+        let images: Vec<Option<TextureImage>> = scene.textures.iter().map(|t| t.decode()).collect();
+        for tile in &scene.tiles {
+            let m = tile.transform.then(&scene.view);
+            let path = polygons_path(&outlines[tile.outline], &m);
+            dt.fill(&path, &solid(scene.color(tile)), &DrawOptions::new());
+            if let Some(k) = scene.texture(tile) {
+                if let Some(image) = &images[k] {
+                    // the source maps pixels to the image, the image repeats like an
+                    // svg pattern
+                    let to_image = scene.textures[k]
+                        .transform(image.width, image.height)
+                        .then(&m)
+                        .inverse();
+                    if let Some(to_image) = to_image {
+                        let source = Source::Image(
+                            Image {
+                                width: image.width as i32,
+                                height: image.height as i32,
+                                data: &image.data,
+                            },
+                            ExtendMode::Repeat,
+                            FilterMode::Bilinear,
+                            to_image,
+                        );
+                        dt.fill(&path, &source, &DrawOptions::new());
                     }
-                    pb.close();
-                    let path = pb.finish();
-
-                    dt.fill(
-                        &path,
-                        &Source::Solid(colors[(c % 4) as usize]),
-                        &DrawOptions::new(),
-                    );
-                    c += 1;
                 }
-                row += 1;
+            }
+            if let Some(stroke) = &scene.style.stroke {
+                dt.stroke(
+                    &path,
+                    &solid(stroke.color),
+                    &stroke_style(stroke, &m),
+                    &DrawOptions::new(),
+                );
             }
         }
 
-        // render image
-        let mut pb = PathBuilder::new();
-        let points = figure
-            .points()
-            .windows(2)
-            .filter_map(|l| {
-                if l[0] != l[1] {
-                    Some(m.transform_point(l[0]))
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<Point>>();
-        let p1 = points[0];
-        pb.move_to(p1.x, p1.y);
-        for p in points.iter().skip(1) {
-            pb.line_to(p.x, p.y);
+        // decorations on top of all the tiles
+        for tile in scene.tiles.iter().filter(|t| t.decorated) {
+            draw_decorations(
+                &mut dt,
+                &scene.decorations,
+                &tile.transform.then(&scene.view),
+            );
         }
-        pb.close();
-        let path = pb.finish();
 
-        dt.stroke(
-            &path,
-            &Source::Solid(SolidSource {
-                r: 0x0,
-                g: 0x88,
-                b: 0x0,
-                a: 0xff,
-            }),
-            &StrokeStyle {
-                cap: LineCap::Square,
-                join: LineJoin::Bevel,
-                width: 3.0,
-                miter_limit: 1.,
-                dash_array: vec![],
-                dash_offset: 0.,
-            },
-            &DrawOptions::new(),
-        );
+        for overlay in &scene.overlays {
+            dt.stroke(
+                &polygons_path(
+                    std::slice::from_ref(&scene.outlines[overlay.outline]),
+                    &overlay.transform,
+                ),
+                &solid(scene.style.overlay.color),
+                &stroke_style(&scene.style.overlay, &overlay.transform),
+                &DrawOptions::new(),
+            );
+            if overlay.decorated {
+                draw_decorations(&mut dt, &scene.decorations, &overlay.transform);
+            }
+        }
+
+        Some(with_metadata(dt, scene.metadata.clone()))
+    }
+}
+
+/// Path of the closed `polygons` transformed with `m`
+fn polygons_path(polygons: &[Vec<Point>], m: &Transform) -> Path {
+    let mut pb = PathBuilder::new();
+    for points in polygons {
+        if let Some(p1) = points.first() {
+            let p1 = m.transform_point(*p1);
+            pb.move_to(p1.x, p1.y);
+            for p in points.iter().skip(1) {
+                let p = m.transform_point(*p);
+                pb.line_to(p.x, p.y);
+            }
+            pb.close();
+        }
+    }
+    pb.finish()
+}
+
+/// Raqote stroke style of a line drawn with `m` from tile units to pixels
+fn stroke_style(stroke: &Stroke, m: &Transform) -> StrokeStyle {
+    StrokeStyle {
+        cap: match stroke.cap {
+            tessellationstyle::LineCap::Butt => LineCap::Butt,
+            tessellationstyle::LineCap::Round => LineCap::Round,
+            tessellationstyle::LineCap::Square => LineCap::Square,
+        },
+        join: match stroke.join {
+            tessellationstyle::LineJoin::Miter => LineJoin::Miter,
+            tessellationstyle::LineJoin::Round => LineJoin::Round,
+            tessellationstyle::LineJoin::Bevel => LineJoin::Bevel,
+        },
+        width: stroke.pixel_width(m),
+        miter_limit: 4.,
+        dash_array: vec![],
+        dash_offset: 0.,
+    }
+}
+
+fn solid(color: Color) -> Source<'static> {
+    Source::Solid(SolidSource::from_unpremultiplied_argb(
+        color.a, color.r, color.g, color.b,
+    ))
+}
 
-        Some(Box::new(dt))
+/// Draw the decorations in tile coordinates transformed with `m`
+fn draw_decorations(dt: &mut DrawTarget, decorations: &[Decoration], m: &Transform) {
+    dt.set_transform(m);
+    for decoration in decorations {
+        let mut pb = PathBuilder::new();
+        for segment in decoration.segments() {
+            match segment {
+                DecorationSegment::MoveTo(p) => pb.move_to(p.x, p.y),
+                DecorationSegment::LineTo(p) => pb.line_to(p.x, p.y),
+                DecorationSegment::QuadTo(c, p) => pb.quad_to(c.x, c.y, p.x, p.y),
+                DecorationSegment::Close => pb.close(),
+            }
+        }
+        let path = pb.finish();
+        if let Some(fill) = decoration.fill {
+            dt.fill(&path, &solid(fill), &DrawOptions::new());
+        }
+        if let Some(stroke) = decoration.stroke {
+            dt.stroke(
+                &path,
+                &solid(stroke),
+                &StrokeStyle {
+                    cap: LineCap::Round,
+                    join: LineJoin::Round,
+                    width: decoration.stroke_width,
+                    ..StrokeStyle::default()
+                },
+                &DrawOptions::new(),
+            );
+        }
     }
+    dt.set_transform(&Transform::identity());
 }
 
 pub trait OutputImage {
@@ -249,6 +336,69 @@ pub trait OutputImage {
     fn get_data_u8(&mut self) -> &mut [u8];
 }
 
+/// Image with the json of the figure and style, saved in a png text chunk
+struct ImageWithMetadata {
+    target: DrawTarget,
+    metadata: String,
+}
+
+/// The image with the metadata of the scene when it has any
+fn with_metadata(target: DrawTarget, metadata: Option<String>) -> Box<dyn OutputImage> {
+    match metadata {
+        Some(metadata) => Box::new(ImageWithMetadata { target, metadata }),
+        None => Box::new(target),
+    }
+}
+
+impl OutputImage for ImageWithMetadata {
+    fn save_png(&self, path: &std::path::Path) -> bool {
+        let file = match std::fs::File::create(path) {
+            Ok(file) => file,
+            Err(_) => return false,
+        };
+        let mut encoder = png::Encoder::new(
+            std::io::BufWriter::new(file),
+            self.target.width() as u32,
+            self.target.height() as u32,
+        );
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        if encoder
+            .add_itxt_chunk(METADATA_KEYWORD.to_string(), self.metadata.clone())
+            .is_err()
+        {
+            return false;
+        }
+        let mut writer = match encoder.write_header() {
+            Ok(writer) => writer,
+            Err(_) => return false,
+        };
+        // unpremultiplied rgba like raqote writes it
+        let data: Vec<u8> = self
+            .target
+            .get_data()
+            .iter()
+            .flat_map(|pixel| {
+                let a = pixel >> 24 & 0xff;
+                let c = |shift: u32| {
+                    let v = pixel >> shift & 0xff;
+                    (v * 255).checked_div(a).unwrap_or(v) as u8
+                };
+                [c(16), c(8), c(0), a as u8]
+            })
+            .collect();
+        writer.write_image_data(&data).is_ok()
+    }
+
+    fn get_data(&self) -> &[u32] {
+        self.target.get_data()
+    }
+
+    fn get_data_u8(&mut self) -> &mut [u8] {
+        self.target.get_data_u8_mut()
+    }
+}
+
 impl OutputImage for raqote::DrawTarget {
     fn save_png(&self, path: &::std::path::Path) -> bool {
         self.write_png(path).is_ok()
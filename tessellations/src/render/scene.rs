@@ -0,0 +1,533 @@
+use crate::render::{ImageMetadata, RenderOptions};
+use crate::tessellationfigure::{
+    Decoration, TessellationFigure, TessellationPlane, TessellationStrip, Texture,
+};
+use crate::tessellationhyperbolic::HyperbolicFigure;
+use crate::tessellationline::{Point, Transform};
+use crate::tessellationpolygon::{intersection, offset_loops, signed_area, ClipShape};
+use crate::tessellationstyle::{Color, LineJoin, Stroke, Style};
+use crate::tessellationsubstitution::SubstitutionTiling;
+use crate::tessellationwarp::Warp;
+
+/// Corners in tile units of the part of the plane that is warped
+pub(crate) const WARPED_MIN: (f32, f32) = (-6.0, -6.0);
+pub(crate) const WARPED_MAX: (f32, f32) = (6.0, 6.0);
+
+/// Outline without the repeated points where the lines of a figure meet and
+/// without the closing point
+pub fn outline(points: &[Point]) -> Vec<Point> {
+    points
+        .windows(2)
+        .filter_map(|l| if l[0] != l[1] { Some(l[0]) } else { None })
+        .collect()
+}
+
+/// Outline of the scene placed in the world
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneTile {
+    /// index into the outlines of the scene
+    pub outline: usize,
+    /// maps the outline to world coordinates
+    pub transform: Transform,
+    /// index into the colors of the scene
+    pub color: usize,
+    /// rotation class of the tile, chooses the texture
+    pub rotation: usize,
+    /// draw the decorations of the scene with this tile
+    pub decorated: bool,
+}
+
+/// Outline drawn on top of the tiles, like the figure being edited
+#[derive(Debug, Clone, PartialEq)]
+pub struct SceneOverlay {
+    /// index into the outlines of the scene
+    pub outline: usize,
+    /// maps the outline to the image, the view is not used
+    pub transform: Transform,
+    /// draw the decorations of the scene with the outline
+    pub decorated: bool,
+}
+
+/// Picture of placed tiles that every backend draws the same way.
+///
+/// The backends fill the tiles in order, then draw the decorations of the
+/// decorated tiles, then stroke the overlays.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scene {
+    /// size of the image in pixels
+    pub width: f32,
+    pub height: f32,
+    /// maps world coordinates to the image
+    pub view: Transform,
+    pub style: Style,
+    /// closed outlines without repeated points
+    pub outlines: Vec<Vec<Point>>,
+    pub tiles: Vec<SceneTile>,
+    /// decorations in tile coordinates
+    pub decorations: Vec<Decoration>,
+    /// images painted inside the tiles in tile coordinates
+    pub textures: Vec<Texture>,
+    pub overlays: Vec<SceneOverlay>,
+    /// json of the figure and style, embedded in the exported images
+    pub metadata: Option<String>,
+}
+
+impl Scene {
+    /// An empty scene of 400 by 400 pixels
+    pub fn new(style: &Style) -> Self {
+        Self {
+            width: 400.0,
+            height: 400.0,
+            view: Transform::identity(),
+            style: style.clone(),
+            outlines: Vec::new(),
+            tiles: Vec::new(),
+            decorations: Vec::new(),
+            textures: Vec::new(),
+            overlays: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    /// The outline of every prototile of `figure` placed with `m` as overlay
+    pub fn figure(figure: &TessellationFigure, m: &Transform, style: &Style) -> Self {
+        let mut scene = Scene::new(style);
+        scene.add_figure(figure);
+        scene.add_overlays(m);
+        scene
+    }
+
+    /// The tiles of the plane that reach into the rectangle `min` `max` in
+    /// tile units, the view is the identity
+    pub fn region(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        style: &Style,
+    ) -> Self {
+        let mut scene = Scene::new(style);
+        scene.add_plane(plane, figure, min, max);
+        scene
+    }
+
+    /// An empty scene with the size, view and style of the options, the
+    /// widths of the lines are scaled to device pixels
+    pub fn canvas(options: &RenderOptions) -> Self {
+        let mut scene = Scene::new(&options.style.scaled(options.dpr));
+        (scene.width, scene.height) = options.pixel_size();
+        scene.view = options.device_view();
+        scene
+    }
+
+    /// The outline of every prototile of `figure` placed with the view of
+    /// the options as overlay
+    pub fn figure_with_options(figure: &TessellationFigure, options: &RenderOptions) -> Self {
+        let mut scene = Scene::canvas(options);
+        scene.add_figure(figure);
+        scene.metadata = Some(ImageMetadata::json(figure, &options.style));
+        let view = scene.view;
+        scene.add_overlays(&view);
+        scene
+    }
+
+    /// The tiles of the plane covering the image of the options with the
+    /// figure as overlay
+    pub fn plane_with_options(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        options: &RenderOptions,
+    ) -> Self {
+        let mut scene = Scene::canvas(options);
+        scene.add_visible_plane(plane, figure);
+        scene.metadata = Some(ImageMetadata::json(figure, &options.style));
+        let view = scene.view;
+        scene.add_overlays(&view);
+        scene
+    }
+
+    /// Add the figure and the tiles of the plane that are visible in the
+    /// image, the colors only depend on the place of a tile on the lattice
+    pub fn add_visible_plane(&mut self, plane: &TessellationPlane, figure: &TessellationFigure) {
+        let inverse = match self.view.inverse() {
+            Some(inverse) => inverse,
+            None => {
+                self.add_figure(figure);
+                return;
+            }
+        };
+        let corners = [
+            Point::new(0.0, 0.0),
+            Point::new(self.width, 0.0),
+            Point::new(0.0, self.height),
+            Point::new(self.width, self.height),
+        ]
+        .map(|p| inverse.transform_point(p));
+        let min = corners.iter().fold(corners[0], |a, p| a.min(*p));
+        let max = corners.iter().fold(corners[0], |a, p| a.max(*p));
+        self.add_plane(plane, figure, min, max);
+    }
+
+    /// Add the figure and the tiles of the plane that reach into the
+    /// rectangle `min` `max` in tile units with the colors of the plane
+    pub fn add_plane(
+        &mut self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+    ) {
+        let first = self.outlines.len();
+        self.add_figure(figure);
+        let colors = self.style.palette.len();
+        for placement in plane.placements(figure, min, max, colors) {
+            self.tiles.push(SceneTile {
+                outline: first + placement.tile,
+                transform: placement.transform,
+                color: placement.color,
+                rotation: placement.rotation,
+                decorated: placement.tile == 0,
+            });
+        }
+    }
+
+    /// The tiles of the smallest rectangular period of the plane in tile
+    /// units, the rectangle is the size of the scene. `None` when the plane
+    /// has no period.
+    pub fn fundamental(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        style: &Style,
+    ) -> Option<Self> {
+        let period = plane.period(figure, style.palette.len())?;
+        let mut scene = Scene::new(style);
+        scene.width = period.x;
+        scene.height = period.y;
+        scene.add_visible_plane(plane, figure);
+        Some(scene)
+    }
+
+    /// Exactly one rectangular period of the plane at `scale` pixels per
+    /// tile unit, the tiles crossing the border are cut so the image
+    /// repeats without seams. `None` when the plane has no period.
+    ///
+    /// The scale is adjusted a little so the period is a whole number of
+    /// pixels.
+    pub fn period(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        scale: f32,
+        style: &Style,
+    ) -> Option<Self> {
+        let mut scene = Scene::fundamental(plane, figure, style)?;
+        let width = (scene.width * scale).round().max(1.0);
+        let height = (scene.height * scale).round().max(1.0);
+        scene.view = Transform::scale(width / scene.width, height / scene.height);
+        scene.width = width;
+        scene.height = height;
+        Some(scene)
+    }
+
+    /// The tiles of the plane inside `shape` in world units on the image of
+    /// the options, the tiles on the border of the shape are cut
+    pub fn clipped_plane(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        shape: &ClipShape,
+        options: &RenderOptions,
+    ) -> Self {
+        let mut scene = Scene::canvas(options);
+        scene.add_visible_plane(plane, figure);
+        scene.metadata = Some(ImageMetadata::json(figure, &options.style));
+        // a quarter of a pixel in world units
+        let tolerance = 0.25 / scene.view.determinant().abs().sqrt();
+        scene.clip(shape, tolerance)
+    }
+
+    /// Cut the tiles with `shape` in world units, the outline of a circle
+    /// is within `tolerance` of the curve.
+    ///
+    /// The parts of a cut tile get outlines of their own in tile coordinates
+    /// so they keep the place, color and texture of the tile. Only the tiles
+    /// that are not cut keep their decorations.
+    pub fn clip(&self, shape: &ClipShape, tolerance: f32) -> Self {
+        let mut scene = self.clone();
+        scene.tiles.clear();
+        let outline = shape.outline(tolerance);
+        for tile in &self.tiles {
+            let inverse = match tile.transform.inverse() {
+                Some(inverse) => inverse,
+                None => continue,
+            };
+            let local: Vec<Point> = outline
+                .iter()
+                .map(|&p| inverse.transform_point(p))
+                .collect();
+            let points = &self.outlines[tile.outline];
+            let parts = intersection(points, &local);
+            let whole = signed_area(points).abs();
+            if let [part] = parts.as_slice() {
+                if (signed_area(part).abs() - whole).abs() <= 1e-5 * whole {
+                    scene.tiles.push(tile.clone());
+                    continue;
+                }
+            }
+            for part in parts {
+                scene.outlines.push(part);
+                scene.tiles.push(SceneTile {
+                    outline: scene.outlines.len() - 1,
+                    decorated: false,
+                    ..tile.clone()
+                });
+            }
+        }
+        scene
+    }
+
+    /// A frieze of the figure on an image `length` by `height` pixels, the
+    /// strip is scaled to the height of the image
+    pub fn strip(
+        strip: &TessellationStrip,
+        figure: &TessellationFigure,
+        length: f32,
+        height: f32,
+        style: &Style,
+    ) -> Self {
+        let mut scene = Scene::new(style);
+        scene.width = length;
+        scene.height = height;
+        let count = scene.add_figure(figure);
+        let (top, bottom) = strip.bounds(figure);
+        if bottom <= top {
+            return scene;
+        }
+        let scale = height / (bottom - top);
+        scene.view = Transform::scale(scale, scale).then_translate(euclid::vec2(0.0, -top * scale));
+        let copies = strip.copies();
+        for (i, transform) in strip.strip(figure, length / scale).into_iter().enumerate() {
+            for k in 0..count {
+                // with more than one prototile every prototile has its own color
+                scene.tiles.push(SceneTile {
+                    outline: k,
+                    transform,
+                    color: if count > 1 { k } else { i % copies },
+                    rotation: i % copies,
+                    decorated: k == 0,
+                });
+            }
+        }
+        scene
+    }
+
+    /// The tiles of a substitution tiling after `depth` substitutions with
+    /// a thin black stroke unless the style has a stroke, `m` maps the
+    /// tiling to the image
+    pub fn substitution(
+        tiling: &SubstitutionTiling,
+        depth: usize,
+        m: &Transform,
+        style: &Style,
+    ) -> Self {
+        let mut scene = Scene::new(style);
+        scene.view = *m;
+        // neighbours can have the same color
+        scene.style.stroke = Some(style.stroke.unwrap_or(Stroke::HAIRLINE));
+        for tile in tiling.tiles(depth) {
+            scene.outlines.push(outline(&tiling.outline(&tile)));
+            scene.tiles.push(SceneTile {
+                outline: scene.outlines.len() - 1,
+                transform: Transform::identity(),
+                color: tile.prototile,
+                rotation: 0,
+                decorated: false,
+            });
+        }
+        scene
+    }
+
+    /// The cells of a hyperbolic tiling up to `depth` edges from the center
+    /// on the image of the options, the view maps the unit disk to the
+    /// image. The cells and the rim of the disk get a thin black stroke
+    /// unless the style has a stroke.
+    pub fn hyperbolic(figure: &HyperbolicFigure, depth: usize, options: &RenderOptions) -> Self {
+        let mut options = options.clone();
+        let stroke = options.style.stroke.unwrap_or(Stroke::HAIRLINE);
+        options.style.stroke = Some(stroke);
+        options.style.overlay = stroke;
+        let mut scene = Scene::canvas(&options);
+        // a quarter of a pixel in disk units
+        let tolerance = 0.25 / scene.view.determinant().abs().sqrt();
+        for cell in figure.cells(depth) {
+            scene
+                .outlines
+                .push(figure.polygon(&cell.transform, tolerance));
+            scene.tiles.push(SceneTile {
+                outline: scene.outlines.len() - 1,
+                transform: Transform::identity(),
+                color: cell.layer % 2,
+                rotation: 0,
+                decorated: false,
+            });
+        }
+        let rim = ClipShape::Circle {
+            center: Point::origin(),
+            radius: 1.0,
+        };
+        scene.outlines.push(rim.outline(tolerance));
+        scene.overlays.push(SceneOverlay {
+            outline: scene.outlines.len() - 1,
+            transform: scene.view,
+            decorated: false,
+        });
+        scene
+    }
+
+    /// The tiles of the plane placed with `m` and mapped through `warp`,
+    /// `view` maps the warped plane to the image
+    pub fn warped(
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        warp: &Warp,
+        m: &Transform,
+        view: &Transform,
+        style: &Style,
+    ) -> Self {
+        let scene = Scene::region(plane, figure, WARPED_MIN.into(), WARPED_MAX.into(), style);
+        // a quarter of a pixel in warped units
+        let tolerance = 0.25 / view.determinant().abs().sqrt();
+        scene.warp(warp, m, view, tolerance)
+    }
+
+    /// Map the tiles placed with `m` through `warp`, every tile gets its own
+    /// outline with edges within `tolerance` of the curve
+    pub fn warp(&self, warp: &Warp, m: &Transform, view: &Transform, tolerance: f32) -> Self {
+        let mut scene = Scene::new(&self.style);
+        scene.view = *view;
+        scene.metadata = self.metadata.clone();
+        for tile in &self.tiles {
+            let t = tile.transform.then(m);
+            let points = self.outlines[tile.outline]
+                .iter()
+                .map(|&p| t.transform_point(p))
+                .collect::<Vec<Point>>();
+            let points = warp.warp_polygon(&points, tolerance);
+            if points.is_empty() {
+                continue;
+            }
+            scene.outlines.push(points);
+            scene.tiles.push(SceneTile {
+                outline: scene.outlines.len() - 1,
+                transform: Transform::identity(),
+                color: tile.color,
+                rotation: tile.rotation,
+                decorated: false,
+            });
+        }
+        scene
+    }
+
+    /// Add the outlines, decorations and textures of the figure, returns the
+    /// number of prototiles
+    pub fn add_figure(&mut self, figure: &TessellationFigure) -> usize {
+        let prototiles = figure.prototile_points();
+        for points in &prototiles {
+            self.outlines.push(outline(points));
+        }
+        self.decorations = figure.decorations.clone();
+        self.textures = figure.textures.clone();
+        self.metadata = Some(ImageMetadata::json(figure, &self.style));
+        prototiles.len()
+    }
+
+    /// Add every outline as overlay placed with `m`
+    pub fn add_overlays(&mut self, m: &Transform) {
+        for k in 0..self.outlines.len() {
+            self.overlays.push(SceneOverlay {
+                outline: k,
+                transform: *m,
+                decorated: k == 0,
+            });
+        }
+    }
+
+    /// The outlines of the tiles shrunk by half the grout of the style, an
+    /// outline splits into several parts at a waist narrower than the grout
+    pub fn tile_outlines(&self) -> Vec<Vec<Vec<Point>>> {
+        if self.style.grout <= 0.0 {
+            return self
+                .outlines
+                .iter()
+                .map(|points| vec![points.clone()])
+                .collect();
+        }
+        self.outlines
+            .iter()
+            .map(|points| offset_loops(points, -self.style.grout / 2.0, LineJoin::Miter))
+            .collect()
+    }
+
+    /// Returns the index into the textures of the image painted inside a tile
+    pub fn texture(&self, tile: &SceneTile) -> Option<usize> {
+        Texture::find(&self.textures, tile.rotation)
+    }
+
+    /// Returns the color of a tile
+    pub fn color(&self, tile: &SceneTile) -> Color {
+        self.style.color(tile.color)
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self::new(&Style::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_outline() {
+        let f = TessellationFigure::square();
+        assert_eq!(
+            outline(&f.points()),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 1.0),
+                Point::new(1.0, 1.0),
+                Point::new(1.0, 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plane_rotations() {
+        let f = TessellationFigure::square90();
+        let scene = Scene::region(
+            &TessellationPlane {},
+            &f,
+            Point::new(-2.0, -2.0),
+            Point::new(2.0, 2.0),
+            &Style::default(),
+        );
+        // the first rotation is not rotated and has the first color
+        let first = &scene.tiles[0];
+        assert_eq!(first.transform.m11, 1.0);
+        assert_eq!(first.color, 0);
+        let last = &scene.tiles[scene.tiles.len() - 1];
+        assert_eq!(last.color, 3);
+    }
+
+    #[test]
+    fn test_figure_overlays() {
+        let f = TessellationFigure::two_triangles();
+        let scene = Scene::figure(&f, &Transform::identity(), &Style::default());
+        assert_eq!(scene.outlines.len(), 2);
+        assert!(scene.tiles.is_empty());
+        assert_eq!(scene.overlays.len(), 2);
+        assert!(scene.overlays[0].decorated);
+        assert!(!scene.overlays[1].decorated);
+    }
+}
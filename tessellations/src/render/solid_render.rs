@@ -0,0 +1,383 @@
+use crate::render::{Builder, LyonBackend};
+use crate::tessellationfigure::TessellationFigure;
+use crate::tessellationline::Point;
+use crate::tessellationpolygon::{clean, offset_loops, signed_area};
+use crate::tessellationstyle::LineJoin;
+use lyon::path::{Event, Path, Polygon};
+use lyon::tessellation::{
+    BuffersBuilder, FillOptions, FillRule, FillTessellator, FillVertex, VertexBuffers,
+};
+use std::collections::HashMap;
+
+/// Distance in millimetres between the solids of the prototiles
+const GAP: f32 = 5.0;
+
+/// Points closer than this in millimetres are the same
+const TOLERANCE: f32 = 1e-3;
+
+/// Size in millimetres of an extruded tile or cookie cutter
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtrudeOptions {
+    /// millimetres per tile unit
+    pub scale: f32,
+    /// height of the solid
+    pub height: f32,
+    /// thickness of the wall inside the outline, a solid tile when 0
+    pub wall: f32,
+    /// width of the flange around the foot of the outline, none when 0
+    pub flange_width: f32,
+    /// thickness of the flange, no flange unless below the height
+    pub flange_height: f32,
+}
+
+impl ExtrudeOptions {
+    /// Options for a solid tile with `scale` millimetres per tile unit
+    /// that is `height` millimetres high
+    pub fn new(scale: f32, height: f32) -> Self {
+        Self {
+            scale,
+            height,
+            wall: 0.0,
+            flange_width: 0.0,
+            flange_height: 0.0,
+        }
+    }
+
+    /// Options for a cookie cutter with a wall of `wall` millimetres and a
+    /// flange of 5 by 1 millimetres
+    pub fn cutter(scale: f32, height: f32, wall: f32) -> Self {
+        Self {
+            wall,
+            flange_width: 5.0,
+            flange_height: 1.0,
+            ..Self::new(scale, height)
+        }
+    }
+}
+
+impl Default for ExtrudeOptions {
+    fn default() -> Self {
+        Self::new(40.0, 4.0)
+    }
+}
+
+/// Closed triangle mesh in millimetres, the z axis is up and the triangles
+/// turn counterclockwise seen from outside
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Solid {
+    pub vertices: Vec<[f32; 3]>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl Solid {
+    /// Index of the vertex at `p`, equal points share a vertex
+    fn vertex(&mut self, index: &mut HashMap<[u32; 3], u32>, p: [f32; 3]) -> u32 {
+        // 0.0 and -0.0 are the same point
+        let key = p.map(|c| (c + 0.0).to_bits());
+        *index.entry(key).or_insert_with(|| {
+            self.vertices.push(p);
+            self.vertices.len() as u32 - 1
+        })
+    }
+
+    /// Volume enclosed by the mesh in cubic millimetres
+    pub fn volume(&self) -> f32 {
+        self.triangles
+            .iter()
+            .map(|t| {
+                let [a, b, c] = t.map(|i| self.vertices[i as usize]);
+                (a[0] * (b[1] * c[2] - b[2] * c[1]) - a[1] * (b[0] * c[2] - b[2] * c[0])
+                    + a[2] * (b[0] * c[1] - b[1] * c[0]))
+                    / 6.0
+            })
+            .sum()
+    }
+
+    /// True when every edge of a triangle is crossed the other way by
+    /// exactly one other triangle
+    pub fn is_watertight(&self) -> bool {
+        let mut edges = HashMap::<(u32, u32), i32>::new();
+        for t in &self.triangles {
+            for i in 0..3 {
+                let (a, b) = (t[i], t[(i + 1) % 3]);
+                if a == b {
+                    return false;
+                }
+                *edges.entry((a, b)).or_insert(0) += 1;
+            }
+        }
+        edges
+            .iter()
+            .all(|(&(a, b), &count)| count == 1 && edges.get(&(b, a)) == Some(&1))
+    }
+
+    /// Binary stl of the mesh
+    pub fn stl(&self) -> Vec<u8> {
+        let mut res = vec![0u8; 80];
+        res[..13].copy_from_slice(b"tessellations");
+        res.extend_from_slice(&(self.triangles.len() as u32).to_le_bytes());
+        for t in &self.triangles {
+            let [a, b, c] = t.map(|i| self.vertices[i as usize]);
+            let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+            let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+            let n = [
+                u[1] * v[2] - u[2] * v[1],
+                u[2] * v[0] - u[0] * v[2],
+                u[0] * v[1] - u[1] * v[0],
+            ];
+            let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            let n = if length > 0.0 {
+                n.map(|c| c / length)
+            } else {
+                [0.0; 3]
+            };
+            for c in [n, a, b, c].iter().flatten() {
+                res.extend_from_slice(&c.to_le_bytes());
+            }
+            res.extend_from_slice(&[0, 0]);
+        }
+        res
+    }
+
+    /// Wavefront obj of the mesh
+    pub fn obj(&self) -> String {
+        let mut res = String::from("o tessellation\n");
+        for v in &self.vertices {
+            res.push_str(&format!("v {} {} {}\n", v[0], v[1], v[2]));
+        }
+        for t in &self.triangles {
+            res.push_str(&format!("f {} {} {}\n", t[0] + 1, t[1] + 1, t[2] + 1));
+        }
+        res
+    }
+
+    pub fn save_stl(&self, path: &std::path::Path) -> bool {
+        std::fs::write(path, self.stl()).is_ok()
+    }
+
+    pub fn save_obj(&self, path: &std::path::Path) -> bool {
+        std::fs::write(path, self.obj()).is_ok()
+    }
+}
+
+/// Adds faces to a solid, sharing the vertices at the same point
+struct SolidBuilder {
+    solid: Solid,
+    index: HashMap<[u32; 3], u32>,
+}
+
+impl SolidBuilder {
+    fn triangle(&mut self, a: [f32; 3], b: [f32; 3], c: [f32; 3]) {
+        let t = [a, b, c].map(|p| self.solid.vertex(&mut self.index, p));
+        self.solid.triangles.push(t);
+    }
+
+    /// Fill the region inside an odd number of the `loops` at height `z`,
+    /// facing up or down
+    fn cap(&mut self, loops: &[Vec<Point>], z: f32, up: bool) -> Option<()> {
+        let mut builder = Path::builder();
+        for points in loops {
+            let points: Vec<lyon::math::Point> =
+                points.iter().map(|p| lyon::math::point(p.x, p.y)).collect();
+            builder.add_polygon(Polygon {
+                points: &points,
+                closed: true,
+            });
+        }
+        let mut buffers: VertexBuffers<lyon::math::Point, u32> = VertexBuffers::new();
+        FillTessellator::new()
+            .tessellate_path(
+                &builder.build(),
+                &FillOptions::default().with_fill_rule(FillRule::EvenOdd),
+                &mut BuffersBuilder::new(&mut buffers, |v: FillVertex| v.position()),
+            )
+            .ok()?;
+        for t in buffers.indices.chunks(3) {
+            let [a, b, c] = [t[0], t[1], t[2]].map(|i| buffers.vertices[i as usize]);
+            // triangles without area along a straight edge are left out
+            let longest = [b - a, c - b, a - c]
+                .iter()
+                .map(|e| e.length())
+                .fold(0.0, f32::max);
+            if (b - a).cross(c - a).abs() <= TOLERANCE * longest {
+                continue;
+            }
+            let ccw = (b - a).cross(c - a) >= 0.0;
+            let corners = if ccw == up { [a, b, c] } else { [a, c, b] };
+            // the tessellator can leave a corner in the middle of an edge,
+            // like where a notch lines up with a tab, then the triangle is
+            // split around its centre so the edge matches its neighbours
+            let mut ring = Vec::<lyon::math::Point>::new();
+            for i in 0..3 {
+                let (p, q) = (corners[i], corners[(i + 1) % 3]);
+                let length = (q - p).length();
+                let mut between: Vec<(f32, lyon::math::Point)> = buffers
+                    .vertices
+                    .iter()
+                    .filter_map(|&v| {
+                        let t = (v - p).dot(q - p) / (length * length);
+                        let distance = (v - p).cross(q - p).abs() / length;
+                        let inside = t * length > TOLERANCE && (1.0 - t) * length > TOLERANCE;
+                        (inside && distance <= TOLERANCE).then_some((t, v))
+                    })
+                    .collect();
+                between.sort_by(|a, b| a.0.total_cmp(&b.0));
+                ring.push(p);
+                ring.extend(between.iter().map(|&(_, v)| v));
+            }
+            let at = |p: lyon::math::Point| [p.x, p.y, z];
+            if ring.len() == 3 {
+                self.triangle(at(ring[0]), at(ring[1]), at(ring[2]));
+            } else {
+                let [a, b, c] = corners;
+                let centre = lyon::math::point((a.x + b.x + c.x) / 3.0, (a.y + b.y + c.y) / 3.0);
+                for i in 0..ring.len() {
+                    self.triangle(at(ring[i]), at(ring[(i + 1) % ring.len()]), at(centre));
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Side of the loop from `z0` up to `z1`, facing to the right of the
+    /// direction of the points
+    fn wall(&mut self, points: &[Point], z0: f32, z1: f32) {
+        let n = points.len();
+        for i in 0..n {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            let (a0, b0) = ([a.x, a.y, z0], [b.x, b.y, z0]);
+            let (a1, b1) = ([a.x, a.y, z1], [b.x, b.y, z1]);
+            self.triangle(a0, b0, b1);
+            self.triangle(a0, b1, a1);
+        }
+    }
+}
+
+/// The closed polygon turning counterclockwise when `ccw`, clockwise if not
+fn oriented(mut points: Vec<Point>, ccw: bool) -> Vec<Point> {
+    if (signed_area(&points) > 0.0) != ccw {
+        points.reverse();
+    }
+    points
+}
+
+/// Outlines of the subpaths of a path of polygons
+fn polygons(path: &Path) -> Vec<Vec<Point>> {
+    let mut res = Vec::<Vec<Point>>::new();
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => res.push(vec![Point::new(at.x, at.y)]),
+            Event::Line { to, .. } | Event::Quadratic { to, .. } | Event::Cubic { to, .. } => {
+                if let Some(points) = res.last_mut() {
+                    points.push(Point::new(to.x, to.y));
+                }
+            }
+            Event::End { .. } => {}
+        }
+    }
+    res
+}
+
+pub trait Extrude {
+    /// Extrude the outline of every prototile of the figure, side by side,
+    /// into a closed mesh. `None` when the outlines have no area or the wall
+    /// is thicker than a tile
+    fn extrude_figure(
+        &self,
+        figure: &TessellationFigure,
+        options: &ExtrudeOptions,
+    ) -> Option<Solid>;
+}
+
+impl Extrude for LyonBackend {
+    fn extrude_figure(
+        &self,
+        figure: &TessellationFigure,
+        options: &ExtrudeOptions,
+    ) -> Option<Solid> {
+        let path = self.build(figure)?;
+        let s = options.scale;
+        let height = options.height;
+        let flange_height = options.flange_height;
+        let flange = options.flange_width > 0.0 && flange_height > 0.0 && flange_height < height;
+        let mut builder = SolidBuilder {
+            solid: Solid::default(),
+            index: HashMap::new(),
+        };
+        let mut left = 0.0;
+        for points in polygons(&path) {
+            // millimetres with the y axis up
+            let points: Vec<Point> = points.iter().map(|p| Point::new(p.x, -p.y) * s).collect();
+            let outline = oriented(clean(&points, TOLERANCE), true);
+            if outline.is_empty() {
+                return None;
+            }
+            // the loops of an offset turning clockwise are holes, a wall
+            // splits its hole at a narrow waist of the outline and a flange
+            // closes off the chambers behind a narrow slot
+            let loops = |distance: f32| -> Vec<Vec<Point>> {
+                offset_loops(&outline, distance, LineJoin::Miter)
+                    .iter()
+                    .map(|points| clean(points, TOLERANCE))
+                    .filter(|points| !points.is_empty())
+                    .collect()
+            };
+            let holes: Vec<Vec<Point>> = if options.wall > 0.0 {
+                let holes = loops(-options.wall);
+                if holes.is_empty() {
+                    return None;
+                }
+                holes
+                    .into_iter()
+                    .map(|hole| oriented(hole, false))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let foot: Vec<Vec<Point>> = if flange {
+                loops(options.flange_width)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(k, points)| oriented(points, k == 0))
+                    .collect()
+            } else {
+                vec![outline.clone()]
+            };
+            if foot.is_empty() {
+                return None;
+            }
+
+            // next to the solid before
+            let min = foot[0].iter().fold(foot[0][0], |a, p| a.min(*p));
+            let max = foot[0].iter().fold(foot[0][0], |a, p| a.max(*p));
+            let shift = Point::new(left, 0.0) - min;
+            left += max.x - min.x + GAP;
+            let moved =
+                |points: &[Point]| -> Vec<Point> { points.iter().map(|&p| p + shift).collect() };
+            let outline = moved(&outline);
+            let foot: Vec<Vec<Point>> = foot.iter().map(|points| moved(points)).collect();
+            let holes: Vec<Vec<Point>> = holes.iter().map(|hole| moved(hole)).collect();
+
+            let with_holes = |outer: &[Vec<Point>]| -> Vec<Vec<Point>> {
+                outer.iter().chain(&holes).cloned().collect()
+            };
+            builder.cap(&with_holes(&foot), 0.0, false)?;
+            if flange {
+                for points in &foot {
+                    builder.wall(points, 0.0, flange_height);
+                }
+                let mut ring = foot.clone();
+                ring.push(outline.clone());
+                builder.cap(&ring, flange_height, true)?;
+                builder.wall(&outline, flange_height, height);
+            } else {
+                builder.wall(&outline, 0.0, height);
+            }
+            builder.cap(&with_holes(std::slice::from_ref(&outline)), height, true)?;
+            for hole in &holes {
+                builder.wall(hole, 0.0, height);
+            }
+        }
+        Some(builder.solid)
+    }
+}
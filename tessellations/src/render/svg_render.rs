@@ -1,154 +1,431 @@
-use crate::tessellationfigure::{TessellationFigure, TessellationPlane};
-use euclid::Angle;
+use crate::render::metadata::escape_xml;
+use crate::render::{ImageMetadata, RenderOptions, Scene};
+use crate::tessellationfigure::{
+    Decoration, DecorationSegment, TessellationFigure, TessellationPlane, TessellationStrip,
+    Texture,
+};
+use crate::tessellationhyperbolic::HyperbolicFigure;
+use crate::tessellationpolygon::ClipShape;
+use crate::tessellationstyle::{LineCap, LineJoin, Stroke, Style};
+use crate::tessellationsubstitution::SubstitutionTiling;
+use crate::tessellationwarp::Warp;
 use raqote::*;
 
 use svg::node::element::path::{Command, Data, Position::Absolute};
-use svg::node::element::{Definitions, Path, Use, SVG};
+use svg::node::element::{Definitions, Element, Group, Image, Path, Pattern, Rectangle, Use, SVG};
+use svg::node::Text;
 use svg::{Document, Node};
 
 #[derive(Clone, Copy)]
 pub struct SVGBackend;
 
 pub trait Compose {
+    /// Compose a scene to a document
+    fn compose_scene(&self, scene: &Scene) -> Option<SVG>;
+
     /// Compose a figure to a document
-    fn compose(&self, figure: &TessellationFigure, m: &Transform) -> Option<SVG>;
+    fn compose(&self, figure: &TessellationFigure, m: &Transform, style: &Style) -> Option<SVG> {
+        self.compose_scene(&Scene::figure(figure, m, style))
+    }
 
+    /// Compose the tiles of the plane covering a document of 400 by 400
+    /// pixels and the figure on top, `m` maps the tiles to the document
     fn compose_plane(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
         m: &Transform,
+        style: &Style,
+    ) -> Option<SVG> {
+        let mut options = RenderOptions::new(400.0, 400.0, *m);
+        options.style = style.clone();
+        self.compose_scene(&Scene::plane_with_options(plane, figure, &options))
+    }
+
+    /// Compose the tiles of the plane covering the document and the figure
+    /// on top with the size, view and style of the options
+    fn compose_plane_with_options(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        options: &RenderOptions,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::plane_with_options(plane, figure, options))
+    }
+
+    /// Compose the tiles of the plane inside `shape` in world units with the
+    /// size, view and style of the options
+    fn compose_clipped_plane(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        shape: &ClipShape,
+        options: &RenderOptions,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::clipped_plane(plane, figure, shape, options))
+    }
+
+    /// Compose one rectangular period of the plane at `scale` pixels per tile
+    /// unit, the document repeats without seams as a texture
+    fn compose_period(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        scale: f32,
+        style: &Style,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::period(plane, figure, scale, style)?)
+    }
+
+    /// Compose one rectangular period of the plane as a pattern that fills
+    /// the document of the options, the pattern repeats without end so it
+    /// can fill any shape
+    fn compose_plane_pattern(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        options: &RenderOptions,
     ) -> Option<SVG>;
-}
 
-impl Compose for SVGBackend {
-    fn compose(&self, figure: &TessellationFigure, m: &Transform) -> Option<SVG> {
-        let mut document: SVG = Document::new().set("viewBox", (0, 0, 400, 400));
+    /// Compose the tiling placed with `m` and mapped through `warp`, `view`
+    /// maps the warped plane to the document
+    fn compose_warped(
+        &self,
+        plane: &TessellationPlane,
+        figure: &TessellationFigure,
+        warp: &Warp,
+        m: &Transform,
+        view: &Transform,
+        style: &Style,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::warped(plane, figure, warp, m, view, style))
+    }
 
-        let points = figure
-            .points()
-            .windows(2)
-            .filter_map(|l| if l[0] != l[1] { Some(l[0]) } else { None })
-            .collect::<Vec<Point>>();
-        let p1 = points[0];
+    /// Compose a frieze of the figure `length` by `height` units, the strip
+    /// is scaled to the height
+    fn compose_strip(
+        &self,
+        strip: &TessellationStrip,
+        figure: &TessellationFigure,
+        length: f32,
+        height: f32,
+        style: &Style,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::strip(strip, figure, length, height, style))
+    }
 
-        let mut pb = Data::new();
-        pb.append(Command::Move(Absolute, (p1.x, p1.y).into()));
+    /// Compose the tiles of a substitution tiling after `depth` substitutions
+    fn compose_substitution(
+        &self,
+        tiling: &SubstitutionTiling,
+        depth: usize,
+        m: &Transform,
+        style: &Style,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::substitution(tiling, depth, m, style))
+    }
 
-        for p in points.iter().skip(1) {
-            pb.append(Command::Line(Absolute, (p.x, p.y).into()));
-        }
-        pb.append(Command::Close);
-        let path = Path::new()
-            .set("vector-effect", "non-scaling-stroke")
-            .set("d", pb)
-            .set("id", "figure");
+    /// Compose the cells of a hyperbolic tiling up to `depth` edges from the
+    /// center on a document of 400 by 400 pixels, `m` maps the unit disk to
+    /// the document
+    fn compose_hyperbolic(
+        &self,
+        figure: &HyperbolicFigure,
+        depth: usize,
+        m: &Transform,
+        style: &Style,
+    ) -> Option<SVG> {
+        let mut options = RenderOptions::new(400.0, 400.0, *m);
+        options.style = style.clone();
+        self.compose_hyperbolic_with_options(figure, depth, &options)
+    }
 
-        let defs = Definitions::new().add(path);
-        document.append(defs);
+    /// Compose the cells of a hyperbolic tiling up to `depth` edges from the
+    /// center with the size, view and style of the options, the view maps
+    /// the unit disk to the document
+    fn compose_hyperbolic_with_options(
+        &self,
+        figure: &HyperbolicFigure,
+        depth: usize,
+        options: &RenderOptions,
+    ) -> Option<SVG> {
+        self.compose_scene(&Scene::hyperbolic(figure, depth, options))
+    }
+}
 
-        let main_figure = Use::new()
-            .set(
-                "transform",
-                format!(
-                    "matrix({},{},{},{},{},{})",
-                    m.m11, m.m12, m.m21, m.m22, m.m31, m.m32
-                ),
-            )
-            .set("stroke", "yellow")
-            .set("stroke-width", "3px")
-            .set("fill", "none")
-            .set("href", "#figure");
-        document.append(main_figure);
+impl Compose for SVGBackend {
+    fn compose_scene(&self, scene: &Scene) -> Option<SVG> {
+        if scene.width <= 0.0 || scene.height <= 0.0 {
+            return None;
+        }
+        let mut document = Document::new().set("viewBox", (0, 0, scene.width, scene.height));
+        if let Some(json) = &scene.metadata {
+            document.append(compose_metadata(json));
+        }
+        let (defs, ids, tile_ids) = compose_definitions(scene);
+        document.append(defs);
+        document.append(
+            Rectangle::new()
+                .set("width", scene.width)
+                .set("height", scene.height)
+                .set("fill", scene.style.background.to_hex()),
+        );
+        append_tiles(&mut document, scene, &tile_ids, &scene.view);
+        for overlay in &scene.overlays {
+            let outline = Use::new()
+                .set("transform", matrix(&overlay.transform))
+                .set("fill", "none")
+                .set("href", format!("#{}", ids[overlay.outline]));
+            document.append(set_stroke(
+                outline,
+                &scene.style.overlay,
+                &overlay.transform,
+            ));
+            if overlay.decorated && !scene.decorations.is_empty() {
+                document.append(use_decorations(&overlay.transform));
+            }
+        }
 
         Some(document)
     }
 
-    fn compose_plane(
+    fn compose_plane_pattern(
         &self,
         plane: &TessellationPlane,
         figure: &TessellationFigure,
-        m: &Transform,
+        options: &RenderOptions,
     ) -> Option<SVG> {
-        let mut row = 0;
-        let g = plane.grid(figure, 12.0, 12.0);
-        let mut c = 0;
-        let mut document = Document::new().set("viewBox", (0, 0, 400, 400));
-        let colors = vec!["red", "green", "blue", "black"];
-
-        let points = figure
-            .points()
-            .windows(2)
-            .filter_map(|l| if l[0] != l[1] { Some(l[0]) } else { None })
-            .collect::<Vec<Point>>();
-        let p1 = points[0];
+        let canvas = Scene::canvas(options);
+        if canvas.width <= 0.0 || canvas.height <= 0.0 {
+            return None;
+        }
+        let cell = Scene::fundamental(plane, figure, &canvas.style)?;
+        let mut document = Document::new().set("viewBox", (0, 0, canvas.width, canvas.height));
+        document.append(compose_metadata(&ImageMetadata::json(
+            figure,
+            &options.style,
+        )));
+        let (mut defs, _, tile_ids) = compose_definitions(&cell);
+        let mut pattern = Pattern::new()
+            .set("id", "plane")
+            .set("patternUnits", "userSpaceOnUse")
+            .set("width", cell.width)
+            .set("height", cell.height)
+            .set("patternTransform", matrix(&canvas.view))
+            .add(
+                Rectangle::new()
+                    .set("width", cell.width)
+                    .set("height", cell.height)
+                    .set("fill", cell.style.background.to_hex()),
+            );
+        append_tiles(&mut pattern, &cell, &tile_ids, &canvas.view);
+        defs.append(pattern);
+        document.append(defs);
+        document.append(
+            Rectangle::new()
+                .set("width", canvas.width)
+                .set("height", canvas.height)
+                .set("fill", "url(#plane)"),
+        );
+        Some(document)
+    }
+}
 
-        let mut pb = Data::new();
-        pb.append(Command::Move(Absolute, (p1.x, p1.y).into()));
-        for p in points.iter().skip(1) {
-            pb.append(Command::Line(Absolute, (p.x, p.y).into()));
+/// Ids of the outline paths, `figure` for a single outline and `figure-k`
+/// for more outlines
+fn outline_ids(count: usize) -> Vec<String> {
+    if count > 1 {
+        (0..count).map(|k| format!("figure-{}", k)).collect()
+    } else {
+        vec!["figure".to_string()]
+    }
+}
+
+/// Element with the json of the figure and style
+fn compose_metadata(json: &str) -> Element {
+    let mut metadata = Element::new("metadata");
+    metadata.append(Text::new(escape_xml(json)));
+    metadata
+}
+
+/// Definitions of the outlines, textures and decorations of the scene with
+/// the ids of the outlines and of the outlines of the tiles
+fn compose_definitions(scene: &Scene) -> (Definitions, Vec<String>, Vec<String>) {
+    let ids = outline_ids(scene.outlines.len());
+    let mut defs = Definitions::new();
+    for (points, id) in scene.outlines.iter().zip(ids.iter()) {
+        defs.append(
+            Path::new()
+                .set("vector-effect", "non-scaling-stroke")
+                .set("d", polygons_data(std::slice::from_ref(points)))
+                .set("id", id.as_str()),
+        );
+    }
+    // the tiles use their own outlines when they are shrunk for grout
+    let tile_ids = if scene.style.grout > 0.0 {
+        let tile_ids: Vec<String> = ids.iter().map(|id| format!("{}-grout", id)).collect();
+        for (polygons, id) in scene.tile_outlines().iter().zip(tile_ids.iter()) {
+            defs.append(
+                Path::new()
+                    .set("vector-effect", "non-scaling-stroke")
+                    .set("d", polygons_data(polygons))
+                    .set("id", id.as_str()),
+            );
         }
-        pb.append(Command::Close);
+        tile_ids
+    } else {
+        ids.clone()
+    };
+    for (k, texture) in scene.textures.iter().enumerate() {
+        if let Some(pattern) = compose_texture(texture, k) {
+            defs.append(pattern);
+        }
+    }
+    if let Some(decorations) = compose_decorations(&scene.decorations) {
+        defs.append(decorations);
+    }
+    (defs, ids, tile_ids)
+}
 
-        let path = Path::new()
-            .set("vector-effect", "non-scaling-stroke")
-            .set("d", pb)
-            .set("id", "figure");
+/// Append the tiles of the scene and their decorations to `parent`, the
+/// strokes are as wide as the style asks after mapping with `screen`
+fn append_tiles<N: Node>(parent: &mut N, scene: &Scene, tile_ids: &[String], screen: &Transform) {
+    for tile in &scene.tiles {
+        let m = tile.transform.then(&scene.view);
+        let mut tile_figure = Use::new()
+            .set("href", format!("#{}", tile_ids[tile.outline]))
+            .set("fill", scene.color(tile).to_hex())
+            .set("transform", matrix(&m));
+        if let Some(k) = scene.texture(tile) {
+            // the image on top of the color shows through transparent pixels
+            parent.append(tile_figure);
+            tile_figure = Use::new()
+                .set("href", format!("#{}", tile_ids[tile.outline]))
+                .set("fill", format!("url(#texture-{})", k))
+                .set("transform", matrix(&m));
+        }
+        if let Some(stroke) = &scene.style.stroke {
+            tile_figure = set_stroke(tile_figure, stroke, &tile.transform.then(screen));
+        }
+        parent.append(tile_figure);
+    }
+    // decorations on top of all the tiles
+    if !scene.decorations.is_empty() {
+        for tile in scene.tiles.iter().filter(|t| t.decorated) {
+            parent.append(use_decorations(&tile.transform.then(&scene.view)));
+        }
+    }
+}
 
-        let defs = Definitions::new().add(path);
-        document.append(defs);
+/// Path data of the closed `polygons`
+fn polygons_data(polygons: &[Vec<Point>]) -> Data {
+    let mut pb = Data::new();
+    for points in polygons {
+        if let Some(p1) = points.first() {
+            pb.append(Command::Move(Absolute, (p1.x, p1.y).into()));
+            for p in points.iter().skip(1) {
+                pb.append(Command::Line(Absolute, (p.x, p.y).into()));
+            }
+            pb.append(Command::Close);
+        }
+    }
+    pb
+}
 
-        for rotdiv in 0..figure.rotdiv {
-            let angle = Angle::degrees(360.0 * (rotdiv as f32) / (figure.rotdiv as f32));
+/// Pattern with the image of the texture in tile coordinates, `None` when
+/// the png is not valid
+fn compose_texture(texture: &Texture, k: usize) -> Option<Pattern> {
+    let (width, height) = texture.pixel_size()?;
+    // the pattern starts at the corner of the texture
+    let m = Transform::scale(texture.width / width as f32, texture.height / height as f32);
+    Some(
+        Pattern::new()
+            .set("id", format!("texture-{}", k))
+            .set("patternUnits", "userSpaceOnUse")
+            .set("x", texture.x)
+            .set("y", texture.y)
+            .set("width", texture.width)
+            .set("height", texture.height)
+            .add(
+                Image::new()
+                    .set("href", texture.data_url())
+                    .set("width", width)
+                    .set("height", height)
+                    .set("transform", matrix(&m)),
+            ),
+    )
+}
 
-            for gridrow in &g {
-                if !figure.is_reversed {
-                    c = row % 2; // use for brick
-                }
-                for gridpoint in gridrow {
-                    if figure.is_reversed {
-                        c = rotdiv; // for diamond
-                    }
-                    if !figure.is_reversed && figure.gridincy < figure.gridincx {
-                        c = row % 3; // used for hexagon
-                    }
-                    let m = Transform::rotation(angle)
-                        .then_scale(70.0, 70.0)
-                        .then_translate(euclid::vec2(gridpoint.x * 70.0, gridpoint.y * 70.0));
-
-                    let tile_figure = Use::new()
-                        .set("href", "#figure")
-                        .set("fill", colors[(c % 4) as usize])
-                        .set(
-                            "transform",
-                            format!(
-                                "matrix({},{},{},{},{},{})",
-                                m.m11, m.m12, m.m21, m.m22, m.m31, m.m32
-                            ),
-                        );
-                    document.append(tile_figure);
-
-                    c += 1;
+/// Group with the decorations in tile coordinates
+fn compose_decorations(decorations: &[Decoration]) -> Option<Group> {
+    if decorations.is_empty() {
+        return None;
+    }
+    let mut group = Group::new().set("id", "decorations");
+    for decoration in decorations {
+        let mut pb = Data::new();
+        for segment in decoration.segments() {
+            pb.append(match segment {
+                DecorationSegment::MoveTo(p) => Command::Move(Absolute, (p.x, p.y).into()),
+                DecorationSegment::LineTo(p) => Command::Line(Absolute, (p.x, p.y).into()),
+                DecorationSegment::QuadTo(c, p) => {
+                    Command::QuadraticCurve(Absolute, (c.x, c.y, p.x, p.y).into())
                 }
-                row += 1;
-            }
+                DecorationSegment::Close => Command::Close,
+            });
         }
-        let edit_figure = Use::new()
-            .set(
-                "transform",
-                format!(
-                    "matrix({},{},{},{},{},{})",
-                    m.m11, m.m12, m.m21, m.m22, m.m31, m.m32
-                ),
-            )
-            .set("stroke", "yellow")
-            .set("stroke-width", "3px")
-            .set("fill", "none")
-            .set("href", "#figure");
-        document.append(edit_figure);
+        let mut path = Path::new().set("d", pb);
+        path = match decoration.fill {
+            Some(fill) => path
+                .set("fill", fill.to_hex())
+                .set("fill-opacity", fill.opacity()),
+            None => path.set("fill", "none"),
+        };
+        if let Some(stroke) = decoration.stroke {
+            path = path
+                .set("stroke", stroke.to_hex())
+                .set("stroke-opacity", stroke.opacity())
+                .set("stroke-width", decoration.stroke_width)
+                .set("stroke-linecap", "round")
+                .set("stroke-linejoin", "round");
+        }
+        group.append(path);
+    }
+    Some(group)
+}
 
-        Some(document)
+fn use_decorations(m: &Transform) -> Use {
+    Use::new()
+        .set("href", "#decorations")
+        .set("transform", matrix(m))
+}
+
+/// Set the stroke attributes of a line drawn with `m` from tile units to
+/// pixels, the corners and ends are only set when they differ from the
+/// svg defaults
+fn set_stroke(node: Use, stroke: &Stroke, m: &Transform) -> Use {
+    let mut node = node
+        .set("stroke", stroke.color.to_hex())
+        .set("stroke-width", format!("{}px", stroke.pixel_width(m)));
+    match stroke.join {
+        LineJoin::Miter => {}
+        LineJoin::Round => node = node.set("stroke-linejoin", "round"),
+        LineJoin::Bevel => node = node.set("stroke-linejoin", "bevel"),
     }
+    match stroke.cap {
+        LineCap::Butt => {}
+        LineCap::Round => node = node.set("stroke-linecap", "round"),
+        LineCap::Square => node = node.set("stroke-linecap", "square"),
+    }
+    node
+}
+
+/// The transform attribute for `m`
+fn matrix(m: &Transform) -> String {
+    format!(
+        "matrix({},{},{},{},{},{})",
+        m.m11, m.m12, m.m21, m.m22, m.m31, m.m32
+    )
 }
 
 pub trait SVGImage {
@@ -172,7 +449,7 @@ impl SVGImage for Document {
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
-    use crate::tessellationfigure::TessellationFigure;
+    use crate::tessellationfigure::{Decoration, TessellationFigure};
 
     #[test]
     fn test_square_svg() {
@@ -181,8 +458,75 @@ mod tests {
             Transform::scale(100.0, 100.0).then_translate(euclid::vec2(100.0, 100.0));
 
         let svgbackend = Box::new(SVGBackend);
-        let svg_document = svgbackend.compose(&f, &m).unwrap();
-        let expected_svg = "<svg viewBox=\"0 0 400 400\" xmlns=\"http://www.w3.org/2000/svg\">\n<defs>\n<path d=\"M0,0 L0,1 L1,1 L1,0 z\" id=\"figure\" vector-effect=\"non-scaling-stroke\"/>\n</defs>\n<use fill=\"none\" href=\"#figure\" stroke=\"yellow\" stroke-width=\"3px\" transform=\"matrix(100,0,0,100,100,100)\"/>\n</svg>";
+        let svg_document = svgbackend.compose(&f, &m, &Style::default()).unwrap();
+        let expected_svg = format!("<svg viewBox=\"0 0 400 400\" xmlns=\"http://www.w3.org/2000/svg\">\n<metadata>\n{}\n</metadata>\n<defs>\n<path d=\"M0,0 L0,1 L1,1 L1,0 z\" id=\"figure\" vector-effect=\"non-scaling-stroke\"/>\n</defs>\n<rect fill=\"#ffffff\" height=\"400\" width=\"400\"/>\n<use fill=\"none\" href=\"#figure\" stroke=\"#ffff00\" stroke-width=\"3px\" transform=\"matrix(100,0,0,100,100,100)\"/>\n</svg>", escape_xml(&ImageMetadata::json(&f, &Style::default())));
+        assert_eq!(svg_document.get_data(), expected_svg);
+    }
+
+    #[test]
+    fn test_substitution_svg() {
+        let t = SubstitutionTiling::penrose_kite_dart();
+        let svg_document = SVGBackend
+            .compose_substitution(&t, 0, &Transform::scale(100.0, 100.0), &Style::default())
+            .unwrap();
+        assert_eq!(svg_document.get_data().matches("<path").count(), 5);
+    }
+
+    #[test]
+    fn test_strip_svg() {
+        let f = TessellationFigure::square();
+        let strip = TessellationStrip::new(crate::tessellationfrieze::FriezeGroup::Sidle);
+        let svg_document = SVGBackend
+            .compose_strip(&strip, &f, 400.0, 100.0, &Style::default())
+            .unwrap();
+        let data = svg_document.get_data();
+        assert!(data.contains("viewBox=\"0 0 400 100\""));
+        // periods of two tiles from -1 to 3
+        assert_eq!(data.matches("<use").count(), 10);
+    }
+
+    #[test]
+    fn test_warped_svg() {
+        let f = TessellationFigure::square();
+        let warp = Warp::droste(euclid::vec2(0.0, 1.0), 1.0);
+        let svg_document = SVGBackend
+            .compose_warped(
+                &TessellationPlane {},
+                &f,
+                &warp,
+                &Transform::scale(0.25, 0.25),
+                &Transform::scale(100.0, 100.0).then_translate(euclid::vec2(200.0, 200.0)),
+                &Style::default(),
+            )
+            .unwrap();
+        let data = svg_document.get_data();
+        assert!(data.matches("<path").count() > 100);
+        assert!(data.contains("matrix(100,0,0,100,200,200)"));
+    }
+
+    #[test]
+    fn test_hyperbolic_svg() {
+        let f = HyperbolicFigure::new(5, 4).unwrap();
+        let data = SVGBackend
+            .compose_hyperbolic(&f, 1, &Transform::scale(100.0, 100.0), &Style::default())
+            .unwrap()
+            .get_data();
+        // the center cell and its five neighbours and the rim of the disk
+        assert_eq!(data.matches("<use").count(), 7);
+        assert!(data.contains("viewBox=\"0 0 400 400\""));
+    }
+
+    #[test]
+    fn test_square_decoration_svg() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::polyline(vec![
+            Point::new(0.25, 0.5),
+            Point::new(0.75, 0.5),
+        ]));
+        let m: Transform = Transform::scale(100.0, 100.0);
+
+        let svg_document = SVGBackend.compose(&f, &m, &Style::default()).unwrap();
+        let expected_svg = format!("<svg viewBox=\"0 0 400 400\" xmlns=\"http://www.w3.org/2000/svg\">\n<metadata>\n{}\n</metadata>\n<defs>\n<path d=\"M0,0 L0,1 L1,1 L1,0 z\" id=\"figure\" vector-effect=\"non-scaling-stroke\"/>\n<g id=\"decorations\">\n<path d=\"M0.25,0.5 L0.75,0.5\" fill=\"none\" stroke=\"#000000\" stroke-linecap=\"round\" stroke-linejoin=\"round\" stroke-opacity=\"1\" stroke-width=\"0.02\"/>\n</g>\n</defs>\n<rect fill=\"#ffffff\" height=\"400\" width=\"400\"/>\n<use fill=\"none\" href=\"#figure\" stroke=\"#ffff00\" stroke-width=\"3px\" transform=\"matrix(100,0,0,100,0,0)\"/>\n<use href=\"#decorations\" transform=\"matrix(100,0,0,100,0,0)\"/>\n</svg>", escape_xml(&ImageMetadata::json(&f, &Style::default())));
         assert_eq!(svg_document.get_data(), expected_svg);
     }
 }
@@ -0,0 +1,250 @@
+use crate::tessellationanalysis::heesch::{Boundary, Match};
+use crate::tessellationanalysis::isometry::Isometry;
+use crate::tessellationfigure::{Prototile, PrototileEdge, TessellationFigure};
+use crate::tessellationline::{Point, TessellationLine, Transform, Vector};
+use crate::tessellationshape::TessellationShape;
+use euclid::Angle;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Length of the words of boundary maps searched for rotations and translations
+const GROUP_DEPTH: usize = 4;
+
+/// Reasons a polygon is not recognized as a tile
+#[derive(Debug, PartialEq, Clone)]
+pub enum AnalysisError {
+    /// the polygon has less than three different points
+    TooFewPoints,
+    /// the polygon has no area
+    ZeroArea,
+    /// no split of the boundary matches a supported Heesch type
+    NoHeeschType,
+    /// the boundary matches a Heesch type but the copies overlap
+    NoLattice,
+}
+
+impl fmt::Display for AnalysisError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnalysisError::TooFewPoints => write!(f, "the outline needs at least three points"),
+            AnalysisError::ZeroArea => write!(f, "the outline has no area"),
+            AnalysisError::NoHeeschType => {
+                write!(f, "the boundary does not split into matching sides")
+            }
+            AnalysisError::NoLattice => write!(f, "the matching sides give overlapping tiles"),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// A polygon recognized as a tile
+#[derive(Debug)]
+pub struct Analysis {
+    /// the Heesch type, like `TTTT` or `CC4C4`
+    pub heesch_type: &'static str,
+    /// figure with the sides of the polygon as lines and the lattice to tile the plane
+    pub figure: TessellationFigure,
+}
+
+/// Find out if the closed outline `points` tiles the plane.
+///
+/// The boundary is split into sides that match by a translation, a half
+/// turn or a rotation around a corner, following the Heesch types with
+/// Conway's criterion. Sides can end at the corners and the midpoints of the
+/// edges. The figure is moved so the origin is a center of the largest
+/// rotation and the first lattice vector is on the x axis.
+pub fn analyze(points: &[Point]) -> Result<Analysis, AnalysisError> {
+    let size = points
+        .iter()
+        .flat_map(|p| points.iter().map(move |q| (*p - *q).length()))
+        .fold(0.0, f32::max);
+    let eps = 1e-3 * size;
+    // drop repeated points, also where the lines of a figure meet
+    let mut polygon = points.to_vec();
+    polygon.dedup_by(|p, q| (*p - *q).length() <= eps);
+    while polygon.len() > 1 && (polygon[0] - polygon[polygon.len() - 1]).length() <= eps {
+        polygon.pop();
+    }
+    if polygon.len() < 3 {
+        return Err(AnalysisError::TooFewPoints);
+    }
+    let area = polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(p, q)| p.to_vector().cross(q.to_vector()))
+        .sum::<f32>()
+        .abs()
+        / 2.0;
+    if area < 1e-6 * size * size {
+        return Err(AnalysisError::ZeroArea);
+    }
+    let boundary = Boundary::new(&polygon, eps);
+    let mut matched = false;
+    boundary
+        .search(&mut |m: &Match| {
+            matched = true;
+            tile(&polygon, &boundary, m, area, eps)
+        })
+        .ok_or(if matched {
+            AnalysisError::NoLattice
+        } else {
+            AnalysisError::NoHeeschType
+        })
+}
+
+/// Build the figure for a match, None if the copies would overlap
+fn tile(
+    polygon: &[Point],
+    boundary: &Boundary,
+    m: &Match,
+    area: f32,
+    eps: f32,
+) -> Option<Analysis> {
+    let group = group(boundary, m, eps as f64);
+    let mut angles = group.iter().map(|g| g.twelfths()).collect::<Vec<i64>>();
+    angles.sort();
+    angles.dedup();
+    let order = angles.len();
+    if ![1, 2, 3, 4, 6].contains(&order) {
+        return None;
+    }
+    let (v1, v2) = lattice(&group, order, order as f32 * area, eps)?;
+
+    // a center of the largest rotation near the middle of the tile
+    let centroid = polygon
+        .iter()
+        .fold(Vector::zero(), |s, p| s + p.to_vector())
+        / polygon.len() as f32;
+    let origin = group
+        .iter()
+        .filter(|g| order > 1 && g.twelfths() == 12 / order as i64)
+        .filter_map(|g| g.center())
+        .min_by(|a, b| {
+            let da = (a.to_vector() - centroid).length();
+            let db = (b.to_vector() - centroid).length();
+            da.total_cmp(&db)
+        })
+        .unwrap_or(polygon[0]);
+
+    let normalize =
+        Transform::translation(-origin.x, -origin.y).then_rotate(-Angle::radians(v1.y.atan2(v1.x)));
+    let mut v2 = normalize.transform_vector(v2);
+    if v2.y < 0.0 {
+        v2 = -v2;
+    }
+    let polygon = polygon
+        .iter()
+        .map(|&p| normalize.transform_point(p))
+        .collect::<Vec<Point>>();
+    let boundary = Boundary::new(&polygon, eps);
+
+    let mut f = TessellationFigure::new();
+    f.gridincx = v1.length();
+    f.gridincy = v2.y;
+    f.shiftx = v2.x.rem_euclid(f.gridincx);
+    f.shifty = 0.0;
+    f.rotdiv = order as u32;
+    f.is_reversed = order > 1;
+    f.shape = TessellationShape::S;
+    for p in &m.pairings {
+        let g = p.isometry(&boundary);
+        let mut line =
+            TessellationLine::new(g.x as f32, g.y as f32, -(g.angle.to_degrees() as f32));
+        for k in 0..=p.len {
+            line.append(boundary.point(p.first + k));
+        }
+        f.append(line);
+    }
+    f.add_prototile(Prototile::new(
+        m.sides
+            .iter()
+            .map(|s| PrototileEdge::new(s.pairing, s.second, s.second))
+            .collect(),
+    ));
+    Some(Analysis {
+        heesch_type: m.name,
+        figure: f,
+    })
+}
+
+/// The maps of the boundary combined up to `GROUP_DEPTH` times
+fn group(boundary: &Boundary, m: &Match, unit: f64) -> Vec<Isometry> {
+    let generators = m
+        .pairings
+        .iter()
+        .map(|p| p.isometry(boundary))
+        .flat_map(|g| [g, g.inverse()])
+        .collect::<Vec<Isometry>>();
+    let mut elements = HashMap::<(i64, i64, i64), Isometry>::new();
+    let identity = Isometry::identity();
+    elements.insert(identity.key(unit), identity);
+    let mut frontier = vec![identity];
+    for _ in 0..GROUP_DEPTH {
+        let mut next = Vec::<Isometry>::new();
+        for e in &frontier {
+            for g in &generators {
+                let x = e.then(g);
+                if let std::collections::hash_map::Entry::Vacant(v) = elements.entry(x.key(unit)) {
+                    v.insert(x);
+                    next.push(x);
+                }
+            }
+        }
+        frontier = next;
+    }
+    elements.into_values().collect()
+}
+
+/// Two translations of the group that span the lattice of the tiling,
+/// `covolume` is the area of the copies of the tile for one lattice point
+fn lattice(group: &[Isometry], order: usize, covolume: f32, eps: f32) -> Option<(Vector, Vector)> {
+    let rotations = group
+        .iter()
+        .filter(|g| g.twelfths() != 0 || order == 1)
+        .map(|g| Isometry {
+            x: 0.0,
+            y: 0.0,
+            ..*g
+        })
+        .collect::<Vec<Isometry>>();
+    let mut vectors = group
+        .iter()
+        .filter(|g| g.twelfths() == 0)
+        .map(|g| Vector::new(g.x as f32, g.y as f32))
+        .filter(|v| v.length() > eps)
+        .collect::<Vec<Vector>>();
+    vectors.sort_by(|a, b| a.length().total_cmp(&b.length()));
+    vectors.truncate(24);
+    let short = vectors.clone();
+    for a in &short {
+        for r in &rotations {
+            vectors.push(r.transform_point(a.to_point()).to_vector());
+        }
+        for b in &short {
+            vectors.push(*a + *b);
+            vectors.push(*a - *b);
+        }
+    }
+    vectors.retain(|v| v.length() > eps);
+    vectors.sort_by(|a, b| a.length().total_cmp(&b.length()));
+
+    let mut best: Option<(Vector, Vector)> = None;
+    for (i, a) in vectors.iter().enumerate() {
+        for b in &vectors[i + 1..] {
+            let cross = a.cross(*b).abs();
+            if cross < 1e-3 * covolume {
+                continue;
+            }
+            // a finer lattice puts copies on top of each other
+            if cross < 0.99 * covolume {
+                return None;
+            }
+            let length = |(c, d): (Vector, Vector)| c.length() + d.length();
+            if cross < 1.01 * covolume && length((*a, *b)) < best.map_or(f32::INFINITY, length) {
+                best = Some((*a, *b));
+            }
+        }
+    }
+    best
+}
@@ -0,0 +1,408 @@
+use crate::tessellationanalysis::isometry::Isometry;
+use crate::tessellationline::{Point, Vector};
+use std::f64::consts::PI;
+
+/// Kind of an arc of a boundary template
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    /// first arc of translation pair `id`
+    T(usize),
+    /// second arc of translation pair `id`, the first arc reversed and translated
+    Tp(usize),
+    /// arc with a half turn symmetry around its midpoint
+    C,
+    /// two arcs where the second is the first rotated by `360 / n` degrees
+    /// around their common point
+    R(u32),
+}
+
+/// Boundary of a Heesch type as a sequence of arcs
+pub(crate) struct Template {
+    pub name: &'static str,
+    kinds: &'static [Kind],
+    /// arcs can have no points, Conway's criterion allows this
+    allow_empty: bool,
+}
+
+/// The supported Heesch types, the simplest symmetry first
+pub(crate) const TEMPLATES: [Template; 9] = [
+    Template {
+        name: "TTTT",
+        kinds: &[Kind::T(0), Kind::T(1), Kind::Tp(0), Kind::Tp(1)],
+        allow_empty: false,
+    },
+    Template {
+        name: "TTTTTT",
+        kinds: &[
+            Kind::T(0),
+            Kind::T(1),
+            Kind::T(2),
+            Kind::Tp(0),
+            Kind::Tp(1),
+            Kind::Tp(2),
+        ],
+        allow_empty: false,
+    },
+    Template {
+        name: "TCCTCC",
+        kinds: &[Kind::T(0), Kind::C, Kind::C, Kind::Tp(0), Kind::C, Kind::C],
+        allow_empty: true,
+    },
+    Template {
+        name: "C4C4C4C4",
+        kinds: &[Kind::R(4), Kind::R(4)],
+        allow_empty: false,
+    },
+    Template {
+        name: "CC4C4",
+        kinds: &[Kind::C, Kind::R(4)],
+        allow_empty: false,
+    },
+    Template {
+        name: "C3C3C3C3C3C3",
+        kinds: &[Kind::R(3), Kind::R(3), Kind::R(3)],
+        allow_empty: false,
+    },
+    Template {
+        name: "CC3C3",
+        kinds: &[Kind::C, Kind::R(3)],
+        allow_empty: false,
+    },
+    Template {
+        name: "CC6C6",
+        kinds: &[Kind::C, Kind::R(6)],
+        allow_empty: false,
+    },
+    Template {
+        name: "C3C3C6C6",
+        kinds: &[Kind::R(3), Kind::R(6)],
+        allow_empty: false,
+    },
+];
+
+/// How the second arc of a pair follows from the first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PairKind {
+    Translation,
+    HalfTurn,
+    /// rotation in radians around the common point of the arcs
+    Rotation(f64),
+}
+
+/// Two arcs of `len` segments where `second[k]` is the image of
+/// `first[len - k]`, the starts are indices into the cyclic boundary
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Pairing {
+    pub first: usize,
+    pub second: usize,
+    pub len: usize,
+    pub kind: PairKind,
+}
+
+impl Pairing {
+    /// The isometry that maps the first arc on the second arc of `boundary`
+    pub fn isometry(&self, boundary: &Boundary) -> Isometry {
+        match self.kind {
+            PairKind::Translation => {
+                let t = boundary.point(self.second) - boundary.point(self.first + self.len);
+                Isometry::translation(t.x as f64, t.y as f64)
+            }
+            PairKind::HalfTurn => Isometry::rotation(boundary.point(self.second), PI),
+            PairKind::Rotation(angle) => Isometry::rotation(boundary.point(self.second), angle),
+        }
+    }
+}
+
+/// Side of the tile in the order of the boundary, the second arc of a pair
+/// is walked in the other direction
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Side {
+    pub pairing: usize,
+    pub second: bool,
+}
+
+/// Boundary decomposition that matches a template
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Match {
+    pub name: &'static str,
+    pub pairings: Vec<Pairing>,
+    pub sides: Vec<Side>,
+}
+
+/// Closed polygon with the midpoint of every edge added so arcs can end
+/// halfway an edge
+pub(crate) struct Boundary {
+    pub points: Vec<Point>,
+    /// sign of the area, the interior is on this side of the edges
+    orientation: f32,
+    eps: f32,
+}
+
+impl Boundary {
+    pub fn new(polygon: &[Point], eps: f32) -> Self {
+        let n = polygon.len();
+        let mut points = Vec::<Point>::new();
+        let mut area = 0.0;
+        for (i, &p) in polygon.iter().enumerate() {
+            let q = polygon[(i + 1) % n];
+            points.push(p);
+            points.push(p.lerp(q, 0.5));
+            area += p.to_vector().cross(q.to_vector());
+        }
+        Self {
+            points,
+            orientation: area.signum(),
+            eps,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn point(&self, i: usize) -> Point {
+        self.points[i % self.points.len()]
+    }
+
+    fn close(&self, a: Point, b: Point) -> bool {
+        (a - b).length() < self.eps
+    }
+
+    /// arc at `second` is the arc at `first` reversed and translated
+    fn is_translation(&self, first: usize, second: usize, len: usize) -> bool {
+        let t: Vector = self.point(second) - self.point(first + len);
+        (0..=len).all(|k| self.close(self.point(second + k), self.point(first + len - k) + t))
+    }
+
+    /// arc at `start` has a half turn symmetry around its midpoint
+    fn is_centrosymmetric(&self, start: usize, len: usize) -> bool {
+        let sum = self.point(start).to_vector() + self.point(start + len).to_vector();
+        (0..=len / 2).all(|k| {
+            self.close(
+                (self.point(start + k).to_vector() + self.point(start + len - k).to_vector())
+                    .to_point(),
+                sum.to_point(),
+            )
+        })
+    }
+
+    /// The arc at `start + len` is the arc at `start` rotated by `360 / n`
+    /// degrees around their common point, returns the signed angle
+    fn rotation(&self, start: usize, len: usize, n: u32) -> Option<f64> {
+        let v = self.point(start + len);
+        let prev = self.point(start + len - 1);
+        let next = self.point(start + len + 1);
+        let (d1, d2) = (v - prev, next - v);
+        // the copies around the common point fill a full turn
+        let turn = d1.cross(d2).atan2(d1.dot(d2));
+        let interior = std::f32::consts::PI - self.orientation * turn;
+        let expected = 2.0 * std::f32::consts::PI / n as f32;
+        if (interior - expected).abs() > 1e-3 {
+            return None;
+        }
+        let (u, w) = (prev - v, next - v);
+        let angle = (u.cross(w).signum() as f64) * 2.0 * PI / n as f64;
+        let r = Isometry::rotation(v, angle);
+        (1..=len)
+            .all(|k| {
+                self.close(
+                    self.point(start + len + k),
+                    r.transform_point(self.point(start + len - k)),
+                )
+            })
+            .then_some(angle)
+    }
+
+    /// Returns the first decomposition of the boundary that matches a
+    /// template and is accepted by `accept`
+    pub fn search<R, F>(&self, accept: &mut F) -> Option<R>
+    where
+        F: FnMut(&Match) -> Option<R>,
+    {
+        for template in &TEMPLATES {
+            for start in 0..self.len() {
+                let mut state = State {
+                    template,
+                    pairings: Vec::new(),
+                    open: [None; 3],
+                    end: start + self.len(),
+                };
+                if let Some(r) = self.matches(&mut state, 0, start, accept) {
+                    return Some(r);
+                }
+            }
+        }
+        None
+    }
+
+    fn matches<R, F>(&self, state: &mut State, ki: usize, cur: usize, accept: &mut F) -> Option<R>
+    where
+        F: FnMut(&Match) -> Option<R>,
+    {
+        let template = state.template;
+        if ki == template.kinds.len() {
+            if cur != state.end {
+                return None;
+            }
+            let mut sides = state
+                .pairings
+                .iter()
+                .enumerate()
+                .flat_map(|(i, p)| {
+                    [(p.first, false), (p.second, true)]
+                        .map(|(at, second)| (at, Side { pairing: i, second }))
+                })
+                .collect::<Vec<(usize, Side)>>();
+            sides.sort_by_key(|s| s.0);
+            return accept(&Match {
+                name: template.name,
+                pairings: state.pairings.clone(),
+                sides: sides.into_iter().map(|s| s.1).collect(),
+            });
+        }
+        let remaining = state.end - cur;
+        let min = if template.allow_empty { 0 } else { 1 };
+        match template.kinds[ki] {
+            Kind::T(id) => {
+                for len in min..=remaining {
+                    state.open[id] = Some((cur, len));
+                    if let Some(r) = self.matches(state, ki + 1, cur + len, accept) {
+                        return Some(r);
+                    }
+                }
+                None
+            }
+            Kind::Tp(id) => {
+                let (first, len) = state.open[id]?;
+                if len > remaining || !self.is_translation(first, cur, len) {
+                    return None;
+                }
+                if len == 0 {
+                    return self.matches(state, ki + 1, cur, accept);
+                }
+                state.pairings.push(Pairing {
+                    first,
+                    second: cur,
+                    len,
+                    kind: PairKind::Translation,
+                });
+                let r = self.matches(state, ki + 1, cur + len, accept);
+                state.pairings.pop();
+                r
+            }
+            Kind::C => {
+                for len in (min.max(2)..=remaining).step_by(2) {
+                    if !self.is_centrosymmetric(cur, len) {
+                        continue;
+                    }
+                    let r = self.push_pair(state, ki, cur, len / 2, PairKind::HalfTurn, accept);
+                    if r.is_some() {
+                        return r;
+                    }
+                }
+                if min == 0 {
+                    return self.matches(state, ki + 1, cur, accept);
+                }
+                None
+            }
+            Kind::R(n) => {
+                for len in 1..=remaining / 2 {
+                    if let Some(angle) = self.rotation(cur, len, n) {
+                        let r =
+                            self.push_pair(state, ki, cur, len, PairKind::Rotation(angle), accept);
+                        if r.is_some() {
+                            return r;
+                        }
+                    }
+                }
+                None
+            }
+        }
+    }
+
+    /// Match the rest after a pair of two arcs of `len` segments at `cur`
+    fn push_pair<R, F>(
+        &self,
+        state: &mut State,
+        ki: usize,
+        cur: usize,
+        len: usize,
+        kind: PairKind,
+        accept: &mut F,
+    ) -> Option<R>
+    where
+        F: FnMut(&Match) -> Option<R>,
+    {
+        state.pairings.push(Pairing {
+            first: cur,
+            second: cur + len,
+            len,
+            kind,
+        });
+        let r = self.matches(state, ki + 1, cur + 2 * len, accept);
+        state.pairings.pop();
+        r
+    }
+}
+
+struct State<'a> {
+    template: &'a Template,
+    pairings: Vec<Pairing>,
+    /// first arcs of the translation pairs
+    open: [Option<(usize, usize)>; 3],
+    end: usize,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn rectangle() -> Boundary {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(2.0, 1.0),
+            Point::new(2.0, 0.0),
+        ];
+        Boundary::new(&points, 1e-4)
+    }
+
+    #[test]
+    fn test_midpoints() {
+        let b = rectangle();
+        assert_eq!(b.len(), 8);
+        assert_eq!(b.point(3), Point::new(1.0, 1.0));
+        assert_eq!(b.point(9), Point::new(0.0, 0.5));
+    }
+
+    #[test]
+    fn test_arcs() {
+        let b = rectangle();
+        // left side and right side walked the other way
+        assert!(b.is_translation(0, 4, 2));
+        assert!(!b.is_translation(0, 2, 2));
+        assert!(b.is_centrosymmetric(2, 2));
+        assert!(!b.is_centrosymmetric(1, 4));
+        // the sides at the corner have different lengths
+        assert!(b.rotation(0, 2, 4).is_none());
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+        ];
+        let b = Boundary::new(&square, 1e-4);
+        assert!(b.rotation(0, 2, 4).is_some());
+        assert!(b.rotation(0, 2, 3).is_none());
+    }
+
+    #[test]
+    fn test_first_match() {
+        let b = rectangle();
+        let m = b.search(&mut |m: &Match| Some(m.clone())).unwrap();
+        assert_eq!(m.name, "TTTT");
+        assert_eq!(m.pairings.len(), 2);
+        assert_eq!(m.sides.len(), 4);
+    }
+}
@@ -0,0 +1,118 @@
+use crate::tessellationline::Point;
+use std::f64::consts::PI;
+
+/// Rotation followed by a translation, `p -> R(angle) p + (x, y)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Isometry {
+    pub angle: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Isometry {
+    pub fn identity() -> Self {
+        Self::translation(0.0, 0.0)
+    }
+
+    pub fn translation(x: f64, y: f64) -> Self {
+        Self { angle: 0.0, x, y }
+    }
+
+    /// Rotation by `angle` radians around `center`
+    pub fn rotation(center: Point, angle: f64) -> Self {
+        let (cx, cy) = (center.x as f64, center.y as f64);
+        let (s, c) = angle.sin_cos();
+        Self {
+            angle,
+            x: cx - (c * cx - s * cy),
+            y: cy - (s * cx + c * cy),
+        }
+    }
+
+    /// First apply self then `other`
+    pub fn then(&self, other: &Isometry) -> Self {
+        let (s, c) = other.angle.sin_cos();
+        Self {
+            angle: (self.angle + other.angle).rem_euclid(2.0 * PI),
+            x: c * self.x - s * self.y + other.x,
+            y: s * self.x + c * self.y + other.y,
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let (s, c) = (-self.angle).sin_cos();
+        Self {
+            angle: (-self.angle).rem_euclid(2.0 * PI),
+            x: -(c * self.x - s * self.y),
+            y: -(s * self.x + c * self.y),
+        }
+    }
+
+    pub fn transform_point(&self, p: Point) -> Point {
+        let (s, c) = self.angle.sin_cos();
+        let (x, y) = (p.x as f64, p.y as f64);
+        Point::new(
+            (c * x - s * y + self.x) as f32,
+            (s * x + c * y + self.y) as f32,
+        )
+    }
+
+    /// Multiple of 30 degrees of the rotation
+    pub fn twelfths(&self) -> i64 {
+        (self.angle / (PI / 6.0)).round().rem_euclid(12.0) as i64
+    }
+
+    /// Key to find the same isometry, `unit` is the precision of the translation
+    pub fn key(&self, unit: f64) -> (i64, i64, i64) {
+        (
+            self.twelfths(),
+            (self.x / unit).round() as i64,
+            (self.y / unit).round() as i64,
+        )
+    }
+
+    /// The point kept in place by a rotation
+    pub fn center(&self) -> Option<Point> {
+        let (s, c) = self.angle.sin_cos();
+        // solve (I - R) p = t
+        let (a, b, d) = (1.0 - c, s, 1.0 - c);
+        let det = a * d + b * b;
+        if det.abs() < 1e-9 {
+            return None;
+        }
+        Some(Point::new(
+            ((d * self.x - b * self.y) / det) as f32,
+            ((b * self.x + a * self.y) / det) as f32,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_rotation_center() {
+        let r = Isometry::rotation(Point::new(1.0, 2.0), PI / 2.0);
+        let c = r.center().unwrap();
+        assert!((c - Point::new(1.0, 2.0)).length() < 1e-5);
+        assert!(Isometry::translation(1.0, 0.0).center().is_none());
+    }
+
+    #[test]
+    fn test_inverse() {
+        let r = Isometry::rotation(Point::new(1.0, 2.0), PI / 3.0)
+            .then(&Isometry::translation(0.5, 0.0));
+        let i = r.then(&r.inverse());
+        assert_eq!(i.key(1e-6), Isometry::identity().key(1e-6));
+    }
+
+    #[test]
+    fn test_two_half_turns_translate() {
+        let a = Isometry::rotation(Point::new(0.0, 0.0), PI);
+        let b = Isometry::rotation(Point::new(1.0, 0.0), PI);
+        let t = a.then(&b);
+        assert_eq!(t.key(1e-6), Isometry::translation(2.0, 0.0).key(1e-6));
+    }
+}
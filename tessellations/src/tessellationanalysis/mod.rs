@@ -0,0 +1,5 @@
+mod analysis;
+mod heesch;
+mod isometry;
+
+pub use analysis::{analyze, Analysis, AnalysisError};
@@ -0,0 +1,153 @@
+use crate::tessellationline::{hit, points_deserialize, points_serialize, Point};
+use crate::tessellationstyle::Color;
+use serde::{Deserialize, Serialize};
+
+/// Type to store the index of a point on a decoration
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DecorationIndexPath {
+    pub decoration_index: usize,
+    pub point_index: usize,
+}
+
+/// Drawing command of a decoration in tile coordinates
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DecorationSegment {
+    MoveTo(Point),
+    LineTo(Point),
+    /// quadratic curve with a control point to a point
+    QuadTo(Point, Point),
+    Close,
+}
+
+/// Interior detail of a tile like an eye, scales or a fin line.
+///
+/// The points are in tile coordinates so the decoration is repeated with
+/// every copy of the tile. The stroke width is in tile units.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Decoration {
+    #[serde(
+        serialize_with = "points_serialize",
+        deserialize_with = "points_deserialize"
+    )]
+    pub points: Vec<Point>,
+    pub closed: bool,
+    pub curve: bool,
+    pub stroke: Option<Color>,
+    pub stroke_width: f32,
+    pub fill: Option<Color>,
+}
+
+impl Decoration {
+    /// An open polyline with a thin black stroke
+    pub fn polyline(points: Vec<Point>) -> Self {
+        Self {
+            points,
+            closed: false,
+            curve: false,
+            stroke: Some(Color::BLACK),
+            stroke_width: 0.02,
+            fill: None,
+        }
+    }
+
+    /// A closed smooth curve through four points around `center`
+    pub fn eye(center: Point, radius: f32) -> Self {
+        Self {
+            points: vec![
+                Point::new(center.x - radius, center.y),
+                Point::new(center.x, center.y - radius),
+                Point::new(center.x + radius, center.y),
+                Point::new(center.x, center.y + radius),
+            ],
+            closed: true,
+            curve: true,
+            stroke: Some(Color::BLACK),
+            stroke_width: 0.02,
+            fill: Some(Color::WHITE),
+        }
+    }
+
+    /// Returns the drawing commands, a curve is a quadratic spline with the
+    /// points as control points.
+    pub fn segments(&self) -> Vec<DecorationSegment> {
+        let mut res = Vec::<DecorationSegment>::new();
+        let n = self.points.len();
+        if n < 2 {
+            return res;
+        }
+        let p = &self.points;
+        let mid = |i: usize, j: usize| p[i].lerp(p[j], 0.5);
+        if !self.curve || n < 3 {
+            res.push(DecorationSegment::MoveTo(p[0]));
+            for &x in &p[1..] {
+                res.push(DecorationSegment::LineTo(x));
+            }
+        } else if self.closed {
+            res.push(DecorationSegment::MoveTo(mid(n - 1, 0)));
+            for (i, &x) in p.iter().enumerate() {
+                res.push(DecorationSegment::QuadTo(x, mid(i, (i + 1) % n)));
+            }
+        } else {
+            res.push(DecorationSegment::MoveTo(p[0]));
+            for (i, &x) in p.iter().enumerate().take(n - 2).skip(1) {
+                res.push(DecorationSegment::QuadTo(x, mid(i, i + 1)));
+            }
+            res.push(DecorationSegment::QuadTo(p[n - 2], p[n - 1]));
+        }
+        if self.closed {
+            res.push(DecorationSegment::Close);
+        }
+        res
+    }
+
+    /// Check if a point is within rectsize of a point of the decoration
+    pub fn hitpoint(&self, point: Point, rectsize: f32) -> Option<usize> {
+        self.points.iter().position(|&p| hit(point, p, rectsize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_polyline_segments() {
+        let d = Decoration::polyline(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0)]);
+        assert_eq!(
+            d.segments(),
+            vec![
+                DecorationSegment::MoveTo(Point::new(0.0, 0.0)),
+                DecorationSegment::LineTo(Point::new(1.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_curve_segments() {
+        let mut d = Decoration::polyline(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 1.0),
+            Point::new(3.0, 0.0),
+        ]);
+        d.curve = true;
+        assert_eq!(
+            d.segments(),
+            vec![
+                DecorationSegment::MoveTo(Point::new(0.0, 0.0)),
+                DecorationSegment::QuadTo(Point::new(1.0, 1.0), Point::new(1.5, 1.0)),
+                DecorationSegment::QuadTo(Point::new(2.0, 1.0), Point::new(3.0, 0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eye_segments() {
+        let d = Decoration::eye(Point::new(0.0, 0.0), 1.0);
+        let s = d.segments();
+        assert_eq!(s.len(), 6);
+        assert_eq!(s[0], DecorationSegment::MoveTo(Point::new(-0.5, 0.5)));
+        assert_eq!(s[5], DecorationSegment::Close);
+    }
+}
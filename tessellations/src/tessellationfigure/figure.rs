@@ -1,8 +1,18 @@
-use crate::tessellationline::{Point, PointIndexPath, TessellationLine};
+use crate::tessellationfigure::{
+    Decoration, DecorationIndexPath, PointSelection, Prototile, Texture,
+};
+use crate::tessellationline::{Point, PointIndexPath, TessellationLine, Transform, Vector};
+use crate::tessellationpolygon::offset_loops;
 use crate::tessellationshape::TessellationShape;
+use crate::tessellationstyle::LineJoin;
+use euclid::Angle;
 use serde::{Deserialize, Serialize};
 
 /// Figure type with lines
+///
+/// Without prototiles the outline of the single tile is the lines followed by
+/// the corresponding lines. With prototiles every prototile has its own
+/// outline from the shared lines and all are placed on the same lattice.
 #[derive(Default, Debug, Serialize, Deserialize)]
 pub struct TessellationFigure {
     pub lines: Vec<TessellationLine>,
@@ -13,6 +23,12 @@ pub struct TessellationFigure {
     pub rotdiv: u32,
     pub is_reversed: bool,
     pub shape: TessellationShape,
+    #[serde(default)]
+    pub decorations: Vec<Decoration>,
+    #[serde(default)]
+    pub prototiles: Vec<Prototile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub textures: Vec<Texture>,
 }
 
 impl TessellationFigure {
@@ -26,6 +42,9 @@ impl TessellationFigure {
             is_reversed: false, // not per line??
             shape: TessellationShape::S,
             rotdiv: 0,
+            decorations: Vec::<Decoration>::new(),
+            prototiles: Vec::<Prototile>::new(),
+            textures: Vec::<Texture>::new(),
         }
     }
 
@@ -34,9 +53,56 @@ impl TessellationFigure {
         self.lines.push(line);
     }
 
+    /// Append `prototile`, the first prototile replaces the single tile outline
+    pub fn add_prototile(&mut self, prototile: Prototile) {
+        self.prototiles.push(prototile);
+    }
+
+    /// Append `texture`, a texture for a rotation class replaces the texture
+    /// without a class for the tiles of that class
+    pub fn add_texture(&mut self, texture: Texture) {
+        self.textures.push(texture);
+    }
+
+    /// Returns the texture of the tiles of rotation class `rotation`
+    pub fn texture(&self, rotation: usize) -> Option<&Texture> {
+        Texture::find(&self.textures, rotation).map(|k| &self.textures[k])
+    }
+
+    /// Returns the number of different tiles
+    pub fn prototile_count(&self) -> usize {
+        self.prototiles.len().max(1)
+    }
+
+    /// Returns the outline of every prototile
+    pub fn prototile_points(&self) -> Vec<Vec<Point>> {
+        if self.prototiles.is_empty() {
+            vec![self.points()]
+        } else {
+            self.prototiles
+                .iter()
+                .map(|t| t.points(&self.lines))
+                .collect()
+        }
+    }
+
+    /// Returns the outline of every prototile moved `distance` outwards, or
+    /// inwards when negative, like the line a blade of that half width cuts.
+    /// An outline gives several loops where it splits at a narrow waist or
+    /// closes off a hole, see [`offset_loops`]
+    pub fn offset_prototile_points(&self, distance: f32, join: LineJoin) -> Vec<Vec<Vec<Point>>> {
+        self.prototile_points()
+            .iter()
+            .map(|points| offset_loops(points, distance, join))
+            .collect()
+    }
+
     /// Returns a list of the points and transformed points in the order of the figure.
+    /// With prototiles this is the outline of the first prototile.
     pub fn points(&self) -> Vec<Point> {
-        if self.is_reversed {
+        if let Some(t) = self.prototiles.first() {
+            t.points(&self.lines)
+        } else if self.is_reversed {
             (self.lines)
                 .iter()
                 .flat_map(|l| l.dpoints())
@@ -99,6 +165,120 @@ impl TessellationFigure {
         self.lines[point_index_path.line_index].update(point_index_path.point_index, p1);
     }
 
+    /// Returns the average of the points of the figure
+    pub fn center(&self) -> Point {
+        let points = self.points();
+        if points.is_empty() {
+            return Point::origin();
+        }
+        let sum = points
+            .iter()
+            .fold(Vector::zero(), |sum, p| sum + p.to_vector());
+        (sum / points.len() as f32).to_point()
+    }
+
+    /// Append the decoration `decoration`
+    pub fn add_decoration(&mut self, decoration: Decoration) {
+        self.decorations.push(decoration);
+    }
+
+    /// Check if a point is within rectsize of a point of the decorations
+    pub fn hitdecoration(&self, point: Point, rectsize: f32) -> Option<DecorationIndexPath> {
+        for (i, decoration) in self.decorations.iter().enumerate() {
+            if let Some(x) = decoration.hitpoint(point, rectsize) {
+                return Some(DecorationIndexPath {
+                    decoration_index: i,
+                    point_index: x,
+                });
+            }
+        }
+        None
+    }
+
+    /// Update a decoration `point` at `decoration_index_path`
+    pub fn update_decoration(&mut self, decoration_index_path: DecorationIndexPath, point: Point) {
+        self.decorations[decoration_index_path.decoration_index].points
+            [decoration_index_path.point_index] = point;
+    }
+
+    /// Returns the position of the point at `point_index_path`
+    pub fn point(&self, point_index_path: PointIndexPath) -> Point {
+        self.lines[point_index_path.line_index]
+            .point(point_index_path.point_index, point_index_path.corrp)
+    }
+
+    /// Returns the points inside the rectangle with corners `p1` and `p2`
+    pub fn hitrect(&self, p1: Point, p2: Point) -> Vec<PointIndexPath> {
+        self.hitpolygon(&[
+            Point::new(p1.x, p1.y),
+            Point::new(p2.x, p1.y),
+            Point::new(p2.x, p2.y),
+            Point::new(p1.x, p2.y),
+        ])
+    }
+
+    /// Returns the points inside the closed lasso `polygon` of all the lines
+    pub fn hitpolygon(&self, polygon: &[Point]) -> Vec<PointIndexPath> {
+        self.lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                line.hitpolygon(polygon)
+                    .into_iter()
+                    .map(move |x| PointIndexPath {
+                        line_index: i,
+                        point_index: x.point_index,
+                        corrp: x.corrp,
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the centroid of the points in `selection`
+    pub fn centroid(&self, selection: &PointSelection) -> Option<Point> {
+        if selection.is_empty() {
+            return None;
+        }
+        let sum = selection
+            .iter()
+            .fold(Vector::zero(), |sum, &h| sum + self.point(h).to_vector());
+        Some((sum / selection.len() as f32).to_point())
+    }
+
+    /// Transform all the points in `selection` with `m`, the corresponding
+    /// lines follow the selected points.
+    pub fn transform(&mut self, selection: &PointSelection, m: &Transform) {
+        for &h in selection.iter() {
+            let p = m.transform_point(self.point(h));
+            self.update(h, p);
+        }
+    }
+
+    /// Move all the points in `selection` by `v`
+    pub fn translate(&mut self, selection: &PointSelection, v: Vector) {
+        self.transform(selection, &Transform::translation(v.x, v.y));
+    }
+
+    /// Rotate all the points in `selection` by `angle` around their centroid
+    pub fn rotate(&mut self, selection: &PointSelection, angle: Angle<f32>) {
+        if let Some(c) = self.centroid(selection) {
+            let m = Transform::translation(-c.x, -c.y)
+                .then_rotate(angle)
+                .then_translate(c.to_vector());
+            self.transform(selection, &m);
+        }
+    }
+
+    /// Scale all the points in `selection` by `factor` from their centroid
+    pub fn scale(&mut self, selection: &PointSelection, factor: f32) {
+        if let Some(c) = self.centroid(selection) {
+            let m = Transform::translation(-c.x, -c.y)
+                .then_scale(factor, factor)
+                .then_translate(c.to_vector());
+            self.transform(selection, &m);
+        }
+    }
+
     pub fn load(&mut self, other: Self) {
         self.lines = other.lines;
         self.gridincx = other.gridincx;
@@ -108,5 +288,8 @@ impl TessellationFigure {
         self.is_reversed = other.is_reversed;
         self.shape = other.shape;
         self.rotdiv = other.rotdiv;
+        self.decorations = other.decorations;
+        self.prototiles = other.prototiles;
+        self.textures = other.textures;
     }
 }
@@ -1,5 +1,10 @@
+mod decoration;
 mod figure;
 mod plane;
+mod prototile;
+mod selection;
+mod strip;
+mod texture;
 
 mod brick;
 mod diamond;
@@ -7,6 +12,12 @@ mod hexagon;
 mod square;
 mod square90;
 mod triangle;
+mod two_triangles;
 
+pub use decoration::{Decoration, DecorationIndexPath, DecorationSegment};
 pub use figure::TessellationFigure;
-pub use plane::TessellationPlane;
+pub use plane::{GridPoint, PlaneTile, TessellationPlane, TilePlacement};
+pub use prototile::{Prototile, PrototileEdge};
+pub use selection::PointSelection;
+pub use strip::TessellationStrip;
+pub use texture::{Texture, TextureImage};
@@ -1,8 +1,61 @@
 use crate::tessellationfigure::TessellationFigure;
-use crate::tessellationline::Point;
+use crate::tessellationline::{Point, Transform, Vector};
+use crate::tessellationpolygon::{clean, intersection, signed_area};
+use euclid::Angle;
+
+/// Largest number of rows searched for a rectangular period
+const MAX_PERIOD_ROWS: i32 = 64;
+
+/// Largest distance in tile units between the ends of a period
+const PERIOD_TOLERANCE: f32 = 1e-3;
+
+/// Parts of a clipped tile below this fraction of its area are slivers left
+/// where the border of the region lies on the edges of the tiles
+const SLIVER: f32 = 1e-4;
 
 pub struct TessellationPlane {}
 
+/// Grid point with its row and column on the lattice of a figure
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridPoint {
+    pub row: i32,
+    pub column: i32,
+    pub point: Point,
+}
+
+/// Copy of a prototile placed on the plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TilePlacement {
+    /// index of the prototile
+    pub tile: usize,
+    /// rotation class, the number of turns of `360 / rotdiv` degrees
+    pub rotation: usize,
+    /// index into the colors of a palette
+    pub color: usize,
+    /// grid point of the tile
+    pub row: i32,
+    pub column: i32,
+    /// maps the outline of the prototile to world coordinates
+    pub transform: Transform,
+}
+
+/// Tile of the plane in world coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaneTile {
+    /// index of the prototile
+    pub tile: usize,
+    /// rotation class, the number of turns of `360 / rotdiv` degrees
+    pub rotation: usize,
+    /// index into the colors of a palette
+    pub color: usize,
+    /// grid point of the tile
+    pub row: i32,
+    pub column: i32,
+    /// the parts of the tile inside the region, one polygon unless the
+    /// border cuts the tile apart
+    pub polygons: Vec<Vec<Point>>,
+}
+
 impl TessellationPlane {
     /// for a figure and view and scale generate a grid of grid points
     pub fn grid(&self, figure: &TessellationFigure, width: f32, height: f32) -> Vec<Vec<Point>> {
@@ -35,4 +88,191 @@ impl TessellationPlane {
         }
         grid
     }
+
+    /// The grid points of the lattice through the origin that are within
+    /// `margin` of the rectangle `min` `max`, row by row
+    pub fn cover(
+        &self,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        margin: f32,
+    ) -> Vec<GridPoint> {
+        let igx = figure.gridincx;
+        let igy = figure.gridincy;
+        let shx = figure.shiftx;
+        let mut res = Vec::<GridPoint>::new();
+        if igx <= 0.0 || igy <= 0.0 || !(min.x <= max.x && min.y <= max.y) {
+            return res;
+        }
+        let (miny, maxy) = (min.y - margin, max.y + margin);
+        let (minx, maxx) = (min.x - margin, max.x + margin);
+        for row in (miny / igy).floor() as i32..=(maxy / igy).ceil() as i32 {
+            let x0 = row as f32 * shx;
+            let y = row as f32 * igy;
+            for column in ((minx - x0) / igx).floor() as i32..=((maxx - x0) / igx).ceil() as i32 {
+                // in double precision so far away rows do not drift
+                let x = row as f64 * shx as f64 + column as f64 * igx as f64;
+                res.push(GridPoint {
+                    row,
+                    column,
+                    point: Point::new(x as f32, y),
+                });
+            }
+        }
+        res
+    }
+
+    /// Index of the color of the copy `rotdiv` of the tile at `gridpoint`
+    /// with `colors` colors, it only depends on the place of the tile on the
+    /// lattice
+    pub fn color(
+        &self,
+        figure: &TessellationFigure,
+        gridpoint: &GridPoint,
+        rotdiv: usize,
+        colors: usize,
+    ) -> usize {
+        let colors = colors.max(1) as i32;
+        let color = if figure.is_reversed {
+            rotdiv as i32 // for diamond
+        } else if figure.gridincy < figure.gridincx {
+            gridpoint.row.rem_euclid(3) // used for hexagon
+        } else {
+            // use for brick
+            gridpoint.row.rem_euclid(2) + gridpoint.column
+        };
+        color.rem_euclid(colors) as usize
+    }
+
+    /// Size of the smallest rectangle at the origin that repeats the plane
+    /// with its colors, `None` when no period is found.
+    ///
+    /// The sides are translations of the lattice, for an oblique lattice
+    /// the height spans several rows so the shifts of the rows add up to
+    /// whole columns. The rotated copies share the grid points so they
+    /// repeat with the lattice.
+    pub fn period(&self, figure: &TessellationFigure, colors: usize) -> Option<Vector> {
+        let igx = figure.gridincx;
+        let igy = figure.gridincy;
+        if igx <= 0.0 || igy <= 0.0 {
+            return None;
+        }
+        let at = |row: i32, column: i32| GridPoint {
+            row,
+            column,
+            point: Point::origin(),
+        };
+        // the colors repeat after a shift of `rows` rows and `columns` columns
+        let repeats = |rows: i32, columns: i32| {
+            (0..figure.rotdiv as usize).all(|rotdiv| {
+                (0..6).all(|row| {
+                    (0..colors.max(1) as i32 + 1).all(|column| {
+                        self.color(figure, &at(row, column), rotdiv, colors)
+                            == self.color(figure, &at(row + rows, column + columns), rotdiv, colors)
+                    })
+                })
+            })
+        };
+        let columns = (1..=colors.max(1) as i32).find(|&columns| repeats(0, columns))?;
+        (1..=MAX_PERIOD_ROWS).find_map(|rows| {
+            // shift back the columns gained by the rows
+            let shift = rows as f32 * figure.shiftx / igx;
+            let column = shift.round();
+            if (shift - column).abs() * igx > PERIOD_TOLERANCE || !repeats(rows, -column as i32) {
+                return None;
+            }
+            Some(Vector::new(columns as f32 * igx, rows as f32 * igy))
+        })
+    }
+
+    /// Every copy of every prototile that reaches into the rectangle `min`
+    /// `max` with its color out of `colors`, rotation class by rotation
+    /// class and row by row
+    pub fn placements(
+        &self,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        colors: usize,
+    ) -> Vec<TilePlacement> {
+        let prototiles = figure.prototile_points();
+        let count = prototiles.len();
+        // every rotation of a tile stays within this distance of its grid point
+        let radius = prototiles
+            .iter()
+            .flatten()
+            .map(|p| p.to_vector().length())
+            .fold(0.0, f32::max);
+        let points = self.cover(figure, min, max, radius);
+        let mut res = Vec::<TilePlacement>::new();
+        for rotdiv in 0..figure.rotdiv as usize {
+            let angle = Angle::degrees(360.0 * (rotdiv as f32) / (figure.rotdiv as f32));
+            for gridpoint in &points {
+                let c = self.color(figure, gridpoint, rotdiv, colors);
+                let transform =
+                    Transform::rotation(angle).then_translate(gridpoint.point.to_vector());
+                for k in 0..count {
+                    // with more than one prototile every prototile has its own color
+                    res.push(TilePlacement {
+                        tile: k,
+                        rotation: rotdiv,
+                        color: if count > 1 { k % colors.max(1) } else { c },
+                        row: gridpoint.row,
+                        column: gridpoint.column,
+                        transform,
+                    });
+                }
+            }
+        }
+        res
+    }
+
+    /// Every tile of the plane in the rectangle `min` `max` in world
+    /// coordinates with its color out of `colors`, the tiles crossing the
+    /// border are clipped to the rectangle
+    pub fn tiles(
+        &self,
+        figure: &TessellationFigure,
+        min: Point,
+        max: Point,
+        colors: usize,
+    ) -> Vec<PlaneTile> {
+        let prototiles: Vec<Vec<Point>> = figure
+            .prototile_points()
+            .iter()
+            .map(|points| clean(points, 1e-6))
+            .collect();
+        let region = [min, Point::new(min.x, max.y), max, Point::new(max.x, min.y)];
+        let inside = |p: &Point| p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y;
+        self.placements(figure, min, max, colors)
+            .iter()
+            .filter_map(|placement| {
+                let points: Vec<Point> = prototiles[placement.tile]
+                    .iter()
+                    .map(|&p| placement.transform.transform_point(p))
+                    .collect();
+                let polygons = if points.iter().all(inside) {
+                    vec![points]
+                } else {
+                    let sliver = signed_area(&points).abs() * SLIVER;
+                    intersection(&points, &region)
+                        .into_iter()
+                        .filter(|part| signed_area(part).abs() > sliver)
+                        .collect()
+                };
+                if polygons.is_empty() {
+                    return None;
+                }
+                Some(PlaneTile {
+                    tile: placement.tile,
+                    rotation: placement.rotation,
+                    color: placement.color,
+                    row: placement.row,
+                    column: placement.column,
+                    polygons,
+                })
+            })
+            .collect()
+    }
 }
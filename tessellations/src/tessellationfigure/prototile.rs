@@ -0,0 +1,57 @@
+use crate::tessellationline::{Point, TessellationLine};
+use serde::{Deserialize, Serialize};
+
+/// Reference to a line of the figure as an edge of a prototile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrototileEdge {
+    pub line_index: usize,
+    /// use the points transformed to the corresponding line
+    pub corrp: bool,
+    /// walk the points from the last to the first
+    pub reversed: bool,
+}
+
+impl PrototileEdge {
+    pub fn new(line_index: usize, corrp: bool, reversed: bool) -> Self {
+        Self {
+            line_index,
+            corrp,
+            reversed,
+        }
+    }
+
+    /// Returns the points of the edge from the `lines` of the figure
+    pub fn points(&self, lines: &[TessellationLine]) -> Vec<Point> {
+        let line = &lines[self.line_index];
+        let mut points = if self.corrp {
+            line.cpoints()
+        } else {
+            line.dpoints()
+        };
+        if self.reversed {
+            points.reverse();
+        }
+        points
+    }
+}
+
+/// One of the different tiles of a figure with more than one prototile.
+///
+/// The outline is a closed sequence of lines of the figure. A line is shared
+/// by two prototiles when one uses the line and the other its corresponding
+/// line, so edge A of tile 1 matches edge B of tile 2.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Prototile {
+    pub edges: Vec<PrototileEdge>,
+}
+
+impl Prototile {
+    pub fn new(edges: Vec<PrototileEdge>) -> Self {
+        Self { edges }
+    }
+
+    /// Returns the outline of the prototile from the `lines` of the figure
+    pub fn points(&self, lines: &[TessellationLine]) -> Vec<Point> {
+        self.edges.iter().flat_map(|e| e.points(lines)).collect()
+    }
+}
@@ -0,0 +1,107 @@
+use crate::tessellationline::PointIndexPath;
+
+/// Set of selected points of a figure, in selection order.
+///
+/// A point is selected at most once, either on its own line or on the
+/// corresponding line, so moving the selection moves every point only once.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct PointSelection {
+    paths: Vec<PointIndexPath>,
+}
+
+impl PointSelection {
+    pub fn new() -> Self {
+        Self {
+            paths: Vec::<PointIndexPath>::new(),
+        }
+    }
+
+    /// Add `path`, replacing the point if it was selected on the other line
+    pub fn insert(&mut self, path: PointIndexPath) {
+        self.paths.retain(|p| !same_point(p, &path));
+        self.paths.push(path);
+    }
+
+    /// Remove `path` from the selection
+    pub fn remove(&mut self, path: PointIndexPath) {
+        self.paths.retain(|p| *p != path);
+    }
+
+    /// Remove `path` if it is selected otherwise add it
+    pub fn toggle(&mut self, path: PointIndexPath) {
+        if self.contains(path) {
+            self.remove(path);
+        } else {
+            self.insert(path);
+        }
+    }
+
+    pub fn contains(&self, path: PointIndexPath) -> bool {
+        self.paths.contains(&path)
+    }
+
+    pub fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PointIndexPath> {
+        self.paths.iter()
+    }
+}
+
+impl From<PointIndexPath> for PointSelection {
+    fn from(path: PointIndexPath) -> Self {
+        Self { paths: vec![path] }
+    }
+}
+
+impl Extend<PointIndexPath> for PointSelection {
+    fn extend<I: IntoIterator<Item = PointIndexPath>>(&mut self, iter: I) {
+        for path in iter {
+            self.insert(path);
+        }
+    }
+}
+
+fn same_point(p1: &PointIndexPath, p2: &PointIndexPath) -> bool {
+    p1.line_index == p2.line_index && p1.point_index == p2.point_index
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn path(point_index: usize, corrp: bool) -> PointIndexPath {
+        PointIndexPath {
+            line_index: 0,
+            point_index,
+            corrp,
+        }
+    }
+
+    #[test]
+    fn test_insert_corrp_replaces() {
+        let mut s = PointSelection::new();
+        s.insert(path(1, false));
+        s.insert(path(1, true));
+        assert_eq!(s.len(), 1);
+        assert!(s.contains(path(1, true)));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut s = PointSelection::from(path(1, false));
+        s.toggle(path(2, false));
+        s.toggle(path(1, false));
+        assert_eq!(s, PointSelection::from(path(2, false)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::tessellationfigure::TessellationFigure;
+use crate::tessellationfrieze::FriezeGroup;
+use crate::tessellationline::Transform;
+
+/// Places copies of a figure along the x axis with the symmetry of a frieze group.
+///
+/// The cell of the figure is `gridincx` by `gridincy` with its top left
+/// corner at the origin. Edges on a mirror line should be straight.
+pub struct TessellationStrip {
+    pub group: FriezeGroup,
+}
+
+impl TessellationStrip {
+    pub fn new(group: FriezeGroup) -> Self {
+        Self { group }
+    }
+
+    /// Returns the top and bottom of the strip in tile units
+    pub fn bounds(&self, figure: &TessellationFigure) -> (f32, f32) {
+        if self.group.has_horizontal_mirror() {
+            (-figure.gridincy, figure.gridincy)
+        } else {
+            (0.0, figure.gridincy)
+        }
+    }
+
+    /// Number of copies of the figure in one period, used to pick colors
+    pub fn copies(&self) -> usize {
+        self.group.transforms(1.0, 1.0).len()
+    }
+
+    /// for a figure generate the transforms of the copies covering `0..length`
+    pub fn strip(&self, figure: &TessellationFigure, length: f32) -> Vec<Transform> {
+        let period = self.group.period(figure.gridincx);
+        let copies = self.group.transforms(figure.gridincx, figure.gridincy);
+        let mut res = Vec::<Transform>::new();
+        if period <= 0.0 {
+            return res;
+        }
+        // one period more on both ends for deformed edges
+        let mut x = -period;
+        while x <= length + period {
+            for t in &copies {
+                res.push(t.then_translate(euclid::vec2(x, 0.0)));
+            }
+            x += period;
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::tessellationline::Point;
+
+    #[test]
+    fn test_hop_strip() {
+        let f = TessellationFigure::square();
+        let s = TessellationStrip::new(FriezeGroup::Hop).strip(&f, 3.0);
+        let x = s
+            .iter()
+            .map(|t| t.transform_point(Point::new(0.0, 0.0)).x)
+            .collect::<Vec<f32>>();
+        assert_eq!(x, vec![-1.0, 0.0, 1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let f = TessellationFigure::square();
+        assert_eq!(
+            TessellationStrip::new(FriezeGroup::Jump).bounds(&f),
+            (-1.0, 1.0)
+        );
+        assert_eq!(
+            TessellationStrip::new(FriezeGroup::Step).bounds(&f),
+            (0.0, 1.0)
+        );
+    }
+}
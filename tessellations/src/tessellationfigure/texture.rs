@@ -0,0 +1,238 @@
+use crate::tessellationline::{Point, Transform, Vector};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Bitmap painted inside the tiles, like a scanned drawing or a photo.
+///
+/// The image is placed in tile coordinates so it turns and moves with every
+/// copy of the tile. In json the png data is base64 encoded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Texture {
+    #[serde(
+        serialize_with = "base64_serialize",
+        deserialize_with = "base64_deserialize"
+    )]
+    pub png: Vec<u8>,
+    /// top left corner of the image in tile coordinates
+    pub x: f32,
+    pub y: f32,
+    /// size of the image in tile units
+    pub width: f32,
+    pub height: f32,
+    /// rotation class of the tiles using the image, `None` for the tiles of
+    /// every class without an image of its own
+    #[serde(default)]
+    pub rotation: Option<usize>,
+}
+
+/// Decoded texture with premultiplied argb pixels row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureImage {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u32>,
+}
+
+impl Texture {
+    /// The png image `png` covering the rectangle at `origin` of `size`
+    pub fn new(png: Vec<u8>, origin: Point, size: Vector) -> Self {
+        Self {
+            png,
+            x: origin.x,
+            y: origin.y,
+            width: size.x,
+            height: size.y,
+            rotation: None,
+        }
+    }
+
+    /// Encode `width` by `height` rgba pixels as png covering the rectangle
+    /// at `origin` of `size`
+    pub fn from_rgba(
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        origin: Point,
+        size: Vector,
+    ) -> Option<Self> {
+        if rgba.len() != (width * height * 4) as usize {
+            return None;
+        }
+        let mut png = Vec::<u8>::new();
+        {
+            let mut encoder = png::Encoder::new(&mut png, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            let mut writer = encoder.write_header().ok()?;
+            writer.write_image_data(rgba).ok()?;
+        }
+        Some(Self::new(png, origin, size))
+    }
+
+    /// Returns the width and height in pixels from the png header
+    pub fn pixel_size(&self) -> Option<(u32, u32)> {
+        let reader = png::Decoder::new(self.png.as_slice()).read_info().ok()?;
+        let info = reader.info();
+        Some((info.width, info.height))
+    }
+
+    /// Decode the png, returns `None` when it is not a valid png
+    pub fn decode(&self) -> Option<TextureImage> {
+        let mut decoder = png::Decoder::new(self.png.as_slice());
+        decoder.set_transformations(png::Transformations::normalize_to_color8());
+        let mut reader = decoder.read_info().ok()?;
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).ok()?;
+        let channels = match info.color_type {
+            png::ColorType::Grayscale => 1,
+            png::ColorType::GrayscaleAlpha => 2,
+            png::ColorType::Rgb => 3,
+            png::ColorType::Rgba => 4,
+            png::ColorType::Indexed => return None,
+        };
+        let data = buf[..info.buffer_size()]
+            .chunks_exact(channels)
+            .map(|p| {
+                let (r, g, b, a) = match p {
+                    [v] => (*v, *v, *v, 0xff),
+                    [v, a] => (*v, *v, *v, *a),
+                    [r, g, b] => (*r, *g, *b, 0xff),
+                    [r, g, b, a] => (*r, *g, *b, *a),
+                    _ => unreachable!(),
+                };
+                let pre = |c: u8| (c as u32 * a as u32 + 127) / 255;
+                (a as u32) << 24 | pre(r) << 16 | pre(g) << 8 | pre(b)
+            })
+            .collect();
+        Some(TextureImage {
+            width: info.width,
+            height: info.height,
+            data,
+        })
+    }
+
+    /// Returns the index of the texture for rotation class `rotation`, the
+    /// texture of the class or else the first texture without a class
+    pub fn find(textures: &[Texture], rotation: usize) -> Option<usize> {
+        textures
+            .iter()
+            .position(|t| t.rotation == Some(rotation))
+            .or_else(|| textures.iter().position(|t| t.rotation.is_none()))
+    }
+
+    /// Maps the pixels of an image `width` by `height` to tile coordinates
+    pub fn transform(&self, width: u32, height: u32) -> Transform {
+        Transform::scale(self.width / width as f32, self.height / height as f32)
+            .then_translate(Vector::new(self.x, self.y))
+    }
+
+    /// The png as a data url for svg
+    pub fn data_url(&self) -> String {
+        format!("data:image/png;base64,{}", encode_base64(&self.png))
+    }
+}
+
+/// Encode with the standard base64 alphabet and padding
+pub(crate) fn encode_base64(data: &[u8]) -> String {
+    let mut res = String::with_capacity(4 * (data.len() / 3 + 1));
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for k in 0..4 {
+            if k <= chunk.len() {
+                res.push(BASE64[(n >> (18 - 6 * k) & 0x3f) as usize] as char);
+            } else {
+                res.push('=');
+            }
+        }
+    }
+    res
+}
+
+/// Decode standard base64, whitespace is skipped
+pub(crate) fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut res = Vec::<u8>::new();
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        if c.is_ascii_whitespace() || c == b'=' {
+            continue;
+        }
+        let v = BASE64.iter().position(|&b| b == c)? as u32;
+        n = n << 6 | v;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            res.push((n >> bits) as u8);
+            n &= (1 << bits) - 1;
+        }
+    }
+    Some(res)
+}
+
+fn base64_serialize<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&encode_base64(data))
+}
+
+fn base64_deserialize<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    decode_base64(&text).ok_or_else(|| serde::de::Error::custom("invalid base64"))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"] {
+            let text = encode_base64(data);
+            assert_eq!(decode_base64(&text).unwrap(), data);
+        }
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert!(decode_base64("Zm9v!").is_none());
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        let rgba = [0xff, 0x0, 0x0, 0xff, 0x0, 0x0, 0xff, 0x80];
+        let t =
+            Texture::from_rgba(2, 1, &rgba, Point::new(0.0, 0.0), Vector::new(1.0, 1.0)).unwrap();
+        assert_eq!(t.pixel_size(), Some((2, 1)));
+        let image = t.decode().unwrap();
+        assert_eq!((image.width, image.height), (2, 1));
+        assert_eq!(image.data, vec![0xffff0000, 0x80000080]);
+    }
+
+    #[test]
+    fn test_transform() {
+        let t = Texture::new(vec![], Point::new(-0.5, 0.0), Vector::new(2.0, 1.0));
+        let m = t.transform(100, 50);
+        assert_eq!(
+            m.transform_point(Point::new(100.0, 50.0)),
+            Point::new(1.5, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let mut t = Texture::new(vec![1, 2, 3], Point::new(0.0, 0.0), Vector::new(1.0, 1.0));
+        t.rotation = Some(2);
+        let json = serde_json::to_string(&t).unwrap();
+        assert!(json.contains("\"png\":\"AQID\""));
+        assert_eq!(serde_json::from_str::<Texture>(&json).unwrap(), t);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::tessellationfigure::{Prototile, PrototileEdge, TessellationFigure};
+use crate::tessellationline::{Point, TessellationLine};
+use crate::tessellationshape::TessellationShape;
+
+impl TessellationFigure {
+    /// Two prototiles, a square cut along the diagonal in two triangles
+    pub fn two_triangles() -> Self {
+        let mut f: TessellationFigure = TessellationFigure::new();
+        f.gridincx = 1.0;
+        f.gridincy = 1.0;
+        f.rotdiv = 1;
+        f.is_reversed = false;
+        f.shiftx = 0.0;
+        f.shifty = 1.0;
+        f.shape = TessellationShape::S;
+
+        // diagonal between the two triangles
+        let mut l1: TessellationLine = TessellationLine::new(0.0, 0.0, 0.0);
+        l1.append(Point::new(0.0, 0.0));
+        l1.append(Point::new(1.0, 1.0));
+        f.append(l1);
+
+        // bottom of the first triangle to the top of the second
+        let mut l2: TessellationLine = TessellationLine::new(0.0, 1.0, 0.0);
+        l2.append(Point::new(0.0, 0.0));
+        l2.append(Point::new(1.0, 0.0));
+        f.append(l2);
+
+        // right of the first triangle to the left of the second
+        let mut l3: TessellationLine = TessellationLine::new(-1.0, 0.0, 0.0);
+        l3.append(Point::new(1.0, 0.0));
+        l3.append(Point::new(1.0, 1.0));
+        f.append(l3);
+
+        f.add_prototile(Prototile::new(vec![
+            PrototileEdge::new(1, false, false),
+            PrototileEdge::new(2, false, false),
+            PrototileEdge::new(0, false, true),
+        ]));
+        f.add_prototile(Prototile::new(vec![
+            PrototileEdge::new(0, true, false),
+            PrototileEdge::new(1, true, true),
+            PrototileEdge::new(2, true, true),
+        ]));
+
+        f
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_polytwo_triangles() {
+        let f = TessellationFigure::two_triangles();
+        assert_eq!(
+            f.prototile_points(),
+            vec![
+                vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 0.0),
+                    Point::new(1.0, 0.0),
+                    Point::new(1.0, 1.0),
+                    Point::new(1.0, 1.0),
+                    Point::new(0.0, 0.0),
+                ],
+                vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 1.0),
+                    Point::new(1.0, 1.0),
+                    Point::new(0.0, 1.0),
+                    Point::new(0.0, 1.0),
+                    Point::new(0.0, 0.0),
+                ],
+            ]
+        );
+    }
+}
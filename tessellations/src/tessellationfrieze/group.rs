@@ -0,0 +1,144 @@
+use crate::tessellationline::Transform;
+use serde::{Deserialize, Serialize};
+
+/// The seven symmetry groups of a pattern repeated along a line.
+///
+/// Every group is a one-dimensional lattice with the period returned by
+/// [`FriezeGroup::period`] and the copies of the tile within one period
+/// returned by [`FriezeGroup::transforms`]. The tile fills the cell
+/// `0..width` by `0..height`, the horizontal mirror is the line `y = 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum FriezeGroup {
+    /// p1, translations only
+    #[default]
+    Hop,
+    /// p11g, glide reflection along the strip
+    Step,
+    /// p1m1, vertical mirrors
+    Sidle,
+    /// p2, half turns
+    SpinningHop,
+    /// p2mg, vertical mirrors and half turns
+    SpinningSidle,
+    /// p11m, horizontal mirror
+    Jump,
+    /// p2mm, horizontal and vertical mirrors
+    SpinningJump,
+}
+
+impl FriezeGroup {
+    pub const ALL: [FriezeGroup; 7] = [
+        FriezeGroup::Hop,
+        FriezeGroup::Step,
+        FriezeGroup::Sidle,
+        FriezeGroup::SpinningHop,
+        FriezeGroup::SpinningSidle,
+        FriezeGroup::Jump,
+        FriezeGroup::SpinningJump,
+    ];
+
+    /// International notation of the group
+    pub fn name(&self) -> &'static str {
+        match self {
+            FriezeGroup::Hop => "p1",
+            FriezeGroup::Step => "p11g",
+            FriezeGroup::Sidle => "p1m1",
+            FriezeGroup::SpinningHop => "p2",
+            FriezeGroup::SpinningSidle => "p2mg",
+            FriezeGroup::Jump => "p11m",
+            FriezeGroup::SpinningJump => "p2mm",
+        }
+    }
+
+    /// Returns true if the group mirrors the tile in the line `y = 0`, the
+    /// strip then covers `-height..height`.
+    pub fn has_horizontal_mirror(&self) -> bool {
+        matches!(self, FriezeGroup::Jump | FriezeGroup::SpinningJump)
+    }
+
+    /// Length of the translation that repeats the pattern for a tile `width` wide
+    pub fn period(&self, width: f32) -> f32 {
+        match self {
+            FriezeGroup::Hop | FriezeGroup::Jump => width,
+            FriezeGroup::SpinningSidle => 4.0 * width,
+            _ => 2.0 * width,
+        }
+    }
+
+    /// Returns the transforms of the copies of the tile within one period
+    pub fn transforms(&self, width: f32, height: f32) -> Vec<Transform> {
+        let w = width;
+        let h = height;
+        let identity = Transform::identity();
+        // (x, y) -> (2w - x, y)
+        let mirror_v = Transform::new(-1.0, 0.0, 0.0, 1.0, 2.0 * w, 0.0);
+        // (x, y) -> (x, -y)
+        let mirror_h = Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, 0.0);
+        match self {
+            FriezeGroup::Hop => vec![identity],
+            // (x, y) -> (x + w, h - y)
+            FriezeGroup::Step => vec![identity, Transform::new(1.0, 0.0, 0.0, -1.0, w, h)],
+            FriezeGroup::Sidle => vec![identity, mirror_v],
+            // half turn around (w, h / 2)
+            FriezeGroup::SpinningHop => {
+                vec![identity, Transform::new(-1.0, 0.0, 0.0, -1.0, 2.0 * w, h)]
+            }
+            // the mirrored pair turned around (2w, h / 2)
+            FriezeGroup::SpinningSidle => vec![
+                identity,
+                mirror_v,
+                Transform::new(1.0, 0.0, 0.0, -1.0, 2.0 * w, h),
+                Transform::new(-1.0, 0.0, 0.0, -1.0, 4.0 * w, h),
+            ],
+            FriezeGroup::Jump => vec![identity, mirror_h],
+            FriezeGroup::SpinningJump => vec![
+                identity,
+                mirror_v,
+                mirror_h,
+                Transform::new(-1.0, 0.0, 0.0, -1.0, 2.0 * w, 0.0),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::tessellationline::Point;
+
+    #[test]
+    fn test_copies_per_period() {
+        let counts = FriezeGroup::ALL
+            .iter()
+            .map(|g| g.transforms(1.0, 1.0).len())
+            .collect::<Vec<usize>>();
+        assert_eq!(counts, vec![1, 2, 2, 2, 4, 2, 4]);
+    }
+
+    #[test]
+    fn test_copies_stay_in_period() {
+        for g in FriezeGroup::ALL {
+            let period = g.period(1.0);
+            for t in g.transforms(1.0, 1.0) {
+                let c = t.transform_point(Point::new(0.5, 0.5));
+                assert!(c.x > 0.0 && c.x < period, "{}", g.name());
+                if g.has_horizontal_mirror() {
+                    assert!(c.y > -1.0 && c.y < 1.0);
+                } else {
+                    assert!(c.y > 0.0 && c.y < 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reflections() {
+        // groups with mirrors or glides flip the orientation of some copies
+        for g in FriezeGroup::ALL {
+            let flipped = g.transforms(1.0, 1.0).iter().any(|t| t.determinant() < 0.0);
+            let expected = !matches!(g, FriezeGroup::Hop | FriezeGroup::SpinningHop);
+            assert_eq!(flipped, expected, "{}", g.name());
+        }
+    }
+}
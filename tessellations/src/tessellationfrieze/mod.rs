@@ -0,0 +1,3 @@
+mod group;
+
+pub use group::FriezeGroup;
@@ -0,0 +1,184 @@
+use crate::tessellationhyperbolic::{GeodesicSegment, Mobius};
+use crate::tessellationline::{hit, Point};
+use euclid::Angle;
+use std::collections::HashSet;
+use std::f64::consts::PI;
+
+/// Copy of the prototile on a cell of the tiling
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HyperbolicCell {
+    pub transform: Mobius,
+    /// number of edges crossed from the center cell
+    pub layer: usize,
+}
+
+/// Prototile of a hyperbolic {p,q} tiling, q regular p-gons meet at every
+/// vertex.
+///
+/// The edited points go from the first vertex to the midpoint of the first
+/// edge. The other half of the edge is the half turn around the midpoint and
+/// the other edges are rotations around the center, so the copies on the
+/// neighbour cells fit like the lines of a [`TessellationFigure`].
+/// All points are joined by hyperbolic lines.
+///
+/// [`TessellationFigure`]: crate::tessellationfigure::TessellationFigure
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperbolicFigure {
+    pub p: u32,
+    pub q: u32,
+    pub points: Vec<Point>,
+}
+
+impl HyperbolicFigure {
+    /// The undeformed {p,q} polygon, none if the tiling is not hyperbolic
+    pub fn new(p: u32, q: u32) -> Option<Self> {
+        if p < 3 || q < 3 || (p - 2) * (q - 2) <= 4 {
+            return None;
+        }
+        Some(Self {
+            p,
+            q,
+            points: Vec::<Point>::new(),
+        })
+    }
+
+    /// Returns the distance from the center to a vertex in the disk
+    pub fn vertex_radius(&self) -> f32 {
+        // right triangle center, vertex, edge midpoint:
+        // cosh(r) = cot(pi / p) cot(pi / q)
+        let c = 1.0 / ((PI / self.p as f64).tan() * (PI / self.q as f64).tan());
+        ((c - 1.0) / (c + 1.0)).sqrt() as f32
+    }
+
+    /// Returns the distance from the center to the midpoint of an edge
+    pub fn midpoint_radius(&self) -> f32 {
+        // cosh(m) = cos(pi / q) / sin(pi / p)
+        let c = (PI / self.q as f64).cos() / (PI / self.p as f64).sin();
+        ((c - 1.0) / (c + 1.0)).sqrt() as f32
+    }
+
+    /// Returns the vertices of the polygon
+    pub fn vertices(&self) -> Vec<Point> {
+        let r = self.vertex_radius();
+        (0..self.p)
+            .map(|k| {
+                let a = 2.0 * PI * k as f64 / self.p as f64;
+                Point::new(r * a.cos() as f32, r * a.sin() as f32)
+            })
+            .collect()
+    }
+
+    /// Returns the midpoint of the first edge
+    pub fn midpoint(&self) -> Point {
+        let r = self.midpoint_radius();
+        let a = PI / self.p as f64;
+        Point::new(r * a.cos() as f32, r * a.sin() as f32)
+    }
+
+    /// Rotation of the polygon to the next edge
+    pub fn rotation(&self) -> Mobius {
+        Mobius::rotation(Angle::radians(2.0 * std::f32::consts::PI / self.p as f32))
+    }
+
+    /// Returns the outline, every edge starts with its vertex
+    pub fn outline(&self) -> Vec<Point> {
+        let h = Mobius::half_turn(self.midpoint());
+        let mut edge = vec![self.vertices()[0]];
+        edge.extend(self.points.iter().copied());
+        edge.push(self.midpoint());
+        edge.extend(self.points.iter().rev().map(|&p| h.transform_point(p)));
+
+        let mut res = Vec::<Point>::new();
+        let mut m = Mobius::identity();
+        for _ in 0..self.p {
+            res.extend(edge.iter().map(|&p| m.transform_point(p)));
+            m = m.then(&self.rotation());
+        }
+        res
+    }
+
+    /// Returns the hyperbolic lines of the outline transformed with `m`
+    pub fn segments(&self, m: &Mobius) -> Vec<GeodesicSegment> {
+        let points = self
+            .outline()
+            .iter()
+            .map(|&p| m.transform_point(p))
+            .collect::<Vec<Point>>();
+        let n = points.len();
+        (0..n)
+            .map(|i| GeodesicSegment::new(points[i], points[(i + 1) % n]))
+            .collect()
+    }
+
+    /// Returns the outline transformed with `m` with the hyperbolic lines
+    /// flattened to chords within `tolerance` of the arcs
+    pub fn polygon(&self, m: &Mobius, tolerance: f32) -> Vec<Point> {
+        self.segments(m)
+            .iter()
+            .flat_map(|segment| segment.flatten(tolerance))
+            .collect()
+    }
+
+    /// Returns the cells up to `depth` edges away from the center cell
+    pub fn cells(&self, depth: usize) -> Vec<HyperbolicCell> {
+        // moving to a neighbour is a half turn around the midpoint of an edge
+        let mut steps = Vec::<Mobius>::new();
+        let mut r = Mobius::identity();
+        let h = Mobius::half_turn(self.midpoint());
+        for _ in 0..self.p {
+            steps.push(h.then(&r));
+            r = r.then(&self.rotation());
+        }
+
+        let key = |m: &Mobius| {
+            let c = m.transform_point(Point::zero());
+            ((c.x * 1e5).round() as i64, (c.y * 1e5).round() as i64)
+        };
+        let mut found = HashSet::<(i64, i64)>::new();
+        let mut cells = vec![HyperbolicCell {
+            transform: Mobius::identity(),
+            layer: 0,
+        }];
+        found.insert(key(&cells[0].transform));
+        let mut start = 0;
+        for layer in 1..=depth {
+            let end = cells.len();
+            for i in start..end {
+                for step in &steps {
+                    let transform = step.then(&cells[i].transform);
+                    if found.insert(key(&transform)) {
+                        cells.push(HyperbolicCell { transform, layer });
+                    }
+                }
+            }
+            start = end;
+        }
+        cells
+    }
+
+    /// Check if a point is within rectsize of an edited point
+    pub fn hitpoint(&self, point: Point, rectsize: f32) -> Option<usize> {
+        self.points.iter().position(|&p| hit(point, p, rectsize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_not_hyperbolic() {
+        assert!(HyperbolicFigure::new(4, 4).is_none());
+        assert!(HyperbolicFigure::new(6, 3).is_none());
+        assert!(HyperbolicFigure::new(7, 3).is_some());
+    }
+
+    #[test]
+    fn test_half_turn_maps_vertices() {
+        let f = HyperbolicFigure::new(5, 4).unwrap();
+        let v = f.vertices();
+        let h = Mobius::half_turn(f.midpoint());
+        assert!((h.transform_point(v[0]) - v[1]).length() < 1e-5);
+    }
+}
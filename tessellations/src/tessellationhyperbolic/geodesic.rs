@@ -0,0 +1,144 @@
+use crate::tessellationline::{Point, Vector};
+
+/// Largest number of chords of a flattened arc
+const MAX_CHORDS: usize = 256;
+
+/// Hyperbolic line segment in the Poincaré disk.
+///
+/// Segments through the center are straight, all others are arcs of a
+/// circle orthogonal to the unit circle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeodesicSegment {
+    Line {
+        from: Point,
+        to: Point,
+    },
+    Arc {
+        from: Point,
+        to: Point,
+        center: Point,
+        radius: f32,
+    },
+}
+
+impl GeodesicSegment {
+    /// The segment from `from` to `to`
+    pub fn new(from: Point, to: Point) -> Self {
+        // the center c of an orthogonal circle through z has
+        // z . c = (|z|^2 + 1) / 2
+        let (z1, z2) = (from.to_vector(), to.to_vector());
+        let det = z1.cross(z2);
+        if det.abs() < 1e-6 {
+            return GeodesicSegment::Line { from, to };
+        }
+        let r1 = (z1.square_length() + 1.0) / 2.0;
+        let r2 = (z2.square_length() + 1.0) / 2.0;
+        let center = Point::new((r1 * z2.y - r2 * z1.y) / det, (z1.x * r2 - z2.x * r1) / det);
+        let radius = (center.to_vector().square_length() - 1.0).max(0.0).sqrt();
+        GeodesicSegment::Arc {
+            from,
+            to,
+            center,
+            radius,
+        }
+    }
+
+    pub fn from(&self) -> Point {
+        match *self {
+            GeodesicSegment::Line { from, .. } => from,
+            GeodesicSegment::Arc { from, .. } => from,
+        }
+    }
+
+    pub fn to(&self) -> Point {
+        match *self {
+            GeodesicSegment::Line { to, .. } => to,
+            GeodesicSegment::Arc { to, .. } => to,
+        }
+    }
+
+    /// Returns the angle of the start of an arc around its center and the
+    /// signed angle to its end, positive in the direction of increasing
+    /// angles.
+    pub fn angles(&self) -> Option<(f32, f32)> {
+        match *self {
+            GeodesicSegment::Line { .. } => None,
+            GeodesicSegment::Arc {
+                from, to, center, ..
+            } => {
+                let (v1, v2) = (from - center, to - center);
+                Some((v1.y.atan2(v1.x), v1.angle_to(v2).radians))
+            }
+        }
+    }
+
+    /// Returns the points of the segment without its end, the chords of an
+    /// arc are within `tolerance` of the arc
+    pub fn flatten(&self, tolerance: f32) -> Vec<Point> {
+        match (*self, self.angles()) {
+            (
+                GeodesicSegment::Arc {
+                    from,
+                    center,
+                    radius,
+                    ..
+                },
+                Some((start, sweep)),
+            ) => {
+                // the sagitta of every chord is at most the tolerance
+                let step = 2.0 * (1.0 - tolerance.max(1e-6) / radius).clamp(-1.0, 1.0).acos();
+                let count = ((sweep.abs() / step).ceil() as usize).clamp(1, MAX_CHORDS);
+                let mut res = vec![from];
+                res.extend((1..count).map(|k| {
+                    let a = start + sweep * k as f32 / count as f32;
+                    center + Vector::new(a.cos(), a.sin()) * radius
+                }));
+                res
+            }
+            _ => vec![self.from()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_line_through_center() {
+        let s = GeodesicSegment::new(Point::new(-0.5, 0.0), Point::new(0.5, 0.0));
+        assert!(matches!(s, GeodesicSegment::Line { .. }));
+    }
+
+    #[test]
+    fn test_arc_orthogonal() {
+        let s = GeodesicSegment::new(Point::new(0.5, 0.0), Point::new(0.0, 0.5));
+        match s {
+            GeodesicSegment::Arc { center, radius, .. } => {
+                // orthogonal to the unit circle
+                assert!((center.to_vector().square_length() - radius * radius - 1.0).abs() < 1e-5);
+                assert!(((Point::new(0.5, 0.0) - center).length() - radius).abs() < 1e-5);
+                assert!(((Point::new(0.0, 0.5) - center).length() - radius).abs() < 1e-5);
+            }
+            _ => panic!("expected an arc"),
+        }
+        let (_, sweep) = s.angles().unwrap();
+        assert!(sweep < 0.0 && sweep > -std::f32::consts::PI);
+    }
+
+    #[test]
+    fn test_flatten_stays_on_the_arc() {
+        let s = GeodesicSegment::new(Point::new(0.5, 0.0), Point::new(0.0, 0.5));
+        let points = s.flatten(1e-3);
+        assert!(points.len() > 2);
+        assert_eq!(points[0], Point::new(0.5, 0.0));
+        if let GeodesicSegment::Arc { center, radius, .. } = s {
+            assert!(points
+                .iter()
+                .all(|p| ((*p - center).length() - radius).abs() < 1e-5));
+        }
+        let line = GeodesicSegment::new(Point::new(-0.5, 0.0), Point::new(0.5, 0.0));
+        assert_eq!(line.flatten(1e-3), vec![Point::new(-0.5, 0.0)]);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::tessellationline::Point;
+use euclid::Angle;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    const ZERO: Complex = Complex { re: 0.0, im: 0.0 };
+    const ONE: Complex = Complex { re: 1.0, im: 0.0 };
+
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn from_point(p: Point) -> Self {
+        Self::new(p.x as f64, p.y as f64)
+    }
+
+    fn to_point(self) -> Point {
+        Point::new(self.re as f32, self.im as f32)
+    }
+
+    fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, o: Complex) -> Complex {
+        Complex::new(self.re + o.re, self.im + o.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, o: Complex) -> Complex {
+        Complex::new(self.re - o.re, self.im - o.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, o: Complex) -> Complex {
+        Complex::new(
+            self.re * o.re - self.im * o.im,
+            self.re * o.im + self.im * o.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, o: Complex) -> Complex {
+        let n = o.norm_sqr();
+        let z = self * o.conj();
+        Complex::new(z.re / n, z.im / n)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+/// Möbius transform `z -> (a z + b) / (c z + d)` of the Poincaré disk.
+///
+/// The transforms made with the constructors map the unit disk on itself and
+/// are the isometries of the hyperbolic plane, they take the place of the
+/// affine transforms of the euclidean tilings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mobius {
+    a: Complex,
+    b: Complex,
+    c: Complex,
+    d: Complex,
+}
+
+impl Mobius {
+    pub fn identity() -> Self {
+        Self {
+            a: Complex::ONE,
+            b: Complex::ZERO,
+            c: Complex::ZERO,
+            d: Complex::ONE,
+        }
+    }
+
+    /// Rotation around the center of the disk
+    pub fn rotation(angle: Angle<f32>) -> Self {
+        let r = angle.radians as f64;
+        Self {
+            a: Complex::new(r.cos(), r.sin()),
+            b: Complex::ZERO,
+            c: Complex::ZERO,
+            d: Complex::ONE,
+        }
+    }
+
+    /// Hyperbolic translation that moves the center of the disk to `p`,
+    /// `p` must be inside the unit circle.
+    pub fn translation(p: Point) -> Self {
+        let p = Complex::from_point(p);
+        Self {
+            a: Complex::ONE,
+            b: p,
+            c: p.conj(),
+            d: Complex::ONE,
+        }
+    }
+
+    /// Rotation by half a turn around `p`
+    pub fn half_turn(p: Point) -> Self {
+        let t = Self::translation(p);
+        t.inverse()
+            .then(&Self::rotation(Angle::degrees(180.0)))
+            .then(&t)
+    }
+
+    /// Returns the transform that applies `self` and then `other`
+    pub fn then(&self, other: &Mobius) -> Self {
+        let a = other.a * self.a + other.b * self.c;
+        let b = other.a * self.b + other.b * self.d;
+        let c = other.c * self.a + other.d * self.c;
+        let d = other.c * self.b + other.d * self.d;
+        // scaling the coefficients gives the same transform, keep them small
+        // so long chains of transforms do not overflow
+        let s = Complex::new(
+            1.0 / [a, b, c, d]
+                .iter()
+                .map(|z| z.norm_sqr())
+                .fold(0.0, f64::max)
+                .sqrt(),
+            0.0,
+        );
+        Self {
+            a: a * s,
+            b: b * s,
+            c: c * s,
+            d: d * s,
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            a: self.d,
+            b: -self.b,
+            c: -self.c,
+            d: self.a,
+        }
+    }
+
+    pub fn transform_point(&self, p: Point) -> Point {
+        let z = Complex::from_point(p);
+        ((self.a * z + self.b) / (self.c * z + self.d)).to_point()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn close(p1: Point, p2: Point) -> bool {
+        (p1 - p2).length() < 1e-5
+    }
+
+    #[test]
+    fn test_translation() {
+        let m = Mobius::translation(Point::new(0.5, 0.0));
+        assert!(close(
+            m.transform_point(Point::zero()),
+            Point::new(0.5, 0.0)
+        ));
+        // the unit circle is kept
+        let p = m.transform_point(Point::new(0.0, 1.0));
+        assert!((p.to_vector().length() - 1.0).abs() < 1e-5);
+        let i = m.then(&m.inverse());
+        assert!(close(
+            i.transform_point(Point::new(0.3, 0.2)),
+            Point::new(0.3, 0.2)
+        ));
+    }
+
+    #[test]
+    fn test_half_turn() {
+        let c = Point::new(0.2, 0.3);
+        let h = Mobius::half_turn(c);
+        assert!(close(h.transform_point(c), c));
+        let p = Point::new(-0.1, 0.4);
+        assert!(close(h.then(&h).transform_point(p), p));
+    }
+}
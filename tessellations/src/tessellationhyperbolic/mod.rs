@@ -0,0 +1,7 @@
+mod figure;
+mod geodesic;
+mod mobius;
+
+pub use figure::{HyperbolicCell, HyperbolicFigure};
+pub use geodesic::GeodesicSegment;
+pub use mobius::Mobius;
@@ -7,6 +7,8 @@ use serde::{Deserialize, Serialize};
 
 pub type Point = euclid::default::Point2D<f32>;
 
+pub type Vector = euclid::default::Vector2D<f32>;
+
 pub type Transform = euclid::default::Transform2D<f32>;
 
 /// Type to store the index of a point on a line
@@ -23,7 +25,22 @@ pub struct PointDef {
     y: f32,
 }
 
-fn points_serialize<S>(v: &[Point], s: S) -> Result<S::Ok, S::Error>
+pub(crate) fn point_serialize<S>(p: &Point, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    PointDef { x: p.x, y: p.y }.serialize(s)
+}
+
+pub(crate) fn point_deserialize<'de, D>(d: D) -> Result<Point, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let p = PointDef::deserialize(d)?;
+    Ok(Point::new(p.x, p.y))
+}
+
+pub(crate) fn points_serialize<S>(v: &[Point], s: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
@@ -34,7 +51,7 @@ where
     seq.end()
 }
 
-fn points_deserialize<'de, D>(d: D) -> Result<Vec<Point>, D::Error>
+pub(crate) fn points_deserialize<'de, D>(d: D) -> Result<Vec<Point>, D::Error>
 where
     D: Deserializer<'de>,
 {
@@ -131,6 +148,21 @@ impl TessellationLine {
         self.points[index] = point;
     }
 
+    /// get the x translation to the corresponding line
+    pub fn tx(&self) -> f32 {
+        self.tx
+    }
+
+    /// get the y translation to the corresponding line
+    pub fn ty(&self) -> f32 {
+        self.ty
+    }
+
+    /// get the rotation in degrees to the corresponding line
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
     /// get a list of the points
     pub fn dpoints(&self) -> Vec<Point> {
         self.points.to_vec()
@@ -153,6 +185,15 @@ impl TessellationLine {
             .collect()
     }
 
+    /// get the point at `index`, on the corresponding line if `corrp`
+    pub fn point(&self, index: usize, corrp: bool) -> Point {
+        if corrp {
+            self.transform.transform_point(self.points[index])
+        } else {
+            self.points[index]
+        }
+    }
+
     /// transform a `point` using the transform matrix
     pub fn cpoint(&self, point: Point) -> Point {
         self.ci.transform_point(point)
@@ -181,6 +222,32 @@ impl TessellationLine {
         None
     }
 
+    /// Returns the points and corresponding points inside the closed `polygon`
+    pub fn hitpolygon(&self, polygon: &[Point]) -> Vec<PointIndexPath> {
+        let mut res = Vec::<PointIndexPath>::new();
+        if self.points.len() < 3 {
+            return res;
+        }
+        let second_last = self.points.len() - 1;
+        for (i, &p) in self.points[1..second_last].iter().enumerate() {
+            if inside(p, polygon) {
+                res.push(PointIndexPath {
+                    line_index: 0,
+                    point_index: i + 1,
+                    corrp: false,
+                });
+            }
+            if inside(self.transform.transform_point(p), polygon) {
+                res.push(PointIndexPath {
+                    line_index: 0,
+                    point_index: i + 1,
+                    corrp: true,
+                });
+            }
+        }
+        res
+    }
+
     /// Check if a point falls on a line within rectsize
     pub fn hitline(&self, p1: Point, rectsize: f32) -> Option<PointIndexPath> {
         let p2 = self.ci.transform_point(p1);
@@ -217,11 +284,26 @@ impl Default for TessellationLine {
     }
 }
 
-fn hit(p1: Point, p2: Point, rectsize: f32) -> bool {
+pub(crate) fn hit(p1: Point, p2: Point, rectsize: f32) -> bool {
     let d: Point = (p1 - p2).to_point();
     (d.x < rectsize) && (d.x > -rectsize) && (d.y < rectsize) && (d.y > -rectsize)
 }
 
+/// even-odd test if point `p` lies inside the closed `polygon`
+fn inside(p: Point, polygon: &[Point]) -> bool {
+    let mut res = false;
+    let mut j = polygon.len().wrapping_sub(1);
+    for (i, &pi) in polygon.iter().enumerate() {
+        let pj = polygon[j];
+        if (pi.y > p.y) != (pj.y > p.y) && p.x < (pj.x - pi.x) * (p.y - pi.y) / (pj.y - pi.y) + pi.x
+        {
+            res = !res;
+        }
+        j = i;
+    }
+    res
+}
+
 fn distance(p: Point) -> f32 {
     (p.x * p.x + p.y * p.y).sqrt()
 }
@@ -244,6 +326,7 @@ fn breakline(p1: Point, p2: Point, current: Point, rectsize: f32) -> bool {
 }
 
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
 
     use super::*;
@@ -356,6 +439,36 @@ mod tests {
         assert_eq!(hit(Point::new(0.0, 0.0), Point::new(10.0, 0.0), 5.0), false);
     }
 
+    #[test]
+    fn test_inside_square() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        assert!(inside(Point::new(0.5, 0.5), &square));
+        assert!(!inside(Point::new(1.5, 0.5), &square));
+    }
+
+    #[test]
+    fn test_inside_concave() {
+        let lasso = [
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 0.5),
+            Point::new(0.0, 2.0),
+        ];
+        assert!(inside(Point::new(0.5, 0.5), &lasso));
+        assert!(!inside(Point::new(1.0, 1.5), &lasso));
+    }
+
+    #[test]
+    fn test_inside_empty() {
+        assert!(!inside(Point::new(0.0, 0.0), &[]));
+    }
+
     #[test]
     fn test_transform_serialize() {
         let line = TessellationLine::default();
@@ -1,3 +1,6 @@
 mod line;
 
-pub use line::{Point, PointIndexPath, TessellationLine};
+pub(crate) use line::{
+    hit, point_deserialize, point_serialize, points_deserialize, points_serialize,
+};
+pub use line::{Point, PointIndexPath, TessellationLine, Transform, Vector};
@@ -0,0 +1,329 @@
+use serde::de::{Deserializer, Error};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Errors from parsing or evaluating an expression
+#[derive(Debug, PartialEq, Clone)]
+pub enum ExpressionError {
+    /// unexpected character or token at the byte offset
+    Syntax(usize),
+    /// the expression ended before it was complete
+    UnexpectedEnd,
+    /// the expression uses a parameter that does not exist
+    UnknownParameter(String),
+    /// the expression calls a function that does not exist
+    UnknownFunction(String),
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExpressionError::Syntax(pos) => write!(f, "syntax error at {}", pos),
+            ExpressionError::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            ExpressionError::UnknownParameter(name) => write!(f, "unknown parameter {}", name),
+            ExpressionError::UnknownFunction(name) => write!(f, "unknown function {}", name),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Number(f32),
+    Parameter(String),
+    Neg(Box<Node>),
+    Add(Box<Node>, Box<Node>),
+    Sub(Box<Node>, Box<Node>),
+    Mul(Box<Node>, Box<Node>),
+    Div(Box<Node>, Box<Node>),
+    Pow(Box<Node>, Box<Node>),
+    Function(fn(f32) -> f32, Box<Node>),
+}
+
+/// Arithmetic expression of named parameters, like `0.5 + bulge * sin(pi / 4)`.
+///
+/// Supports numbers, parameters, `+ - * / ^`, parentheses, the constant `pi`
+/// and the functions `sin`, `cos`, `tan`, `sqrt` and `abs` (radians).
+#[derive(Debug, Clone)]
+pub struct Expression {
+    source: String,
+    node: Node,
+}
+
+impl Expression {
+    /// Parse an expression from `source`
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            source: source.as_bytes(),
+            pos: 0,
+        };
+        let node = parser.expression()?;
+        parser.skip_whitespace();
+        if parser.pos < source.len() {
+            return Err(ExpressionError::Syntax(parser.pos));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            node,
+        })
+    }
+
+    /// A constant expression, written without an exponent so it parses back
+    pub fn constant(value: f32) -> Self {
+        Self {
+            source: format!("{}", value),
+            node: Node::Number(value),
+        }
+    }
+
+    /// The text the expression was parsed from
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate the expression, `lookup` returns the value of a parameter
+    pub fn eval<F>(&self, lookup: F) -> Result<f32, ExpressionError>
+    where
+        F: Fn(&str) -> Option<f32>,
+    {
+        eval(&self.node, &lookup)
+    }
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Serialize for Expression {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expression {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let source = String::deserialize(d)?;
+        Expression::parse(&source).map_err(D::Error::custom)
+    }
+}
+
+fn eval<F>(node: &Node, lookup: &F) -> Result<f32, ExpressionError>
+where
+    F: Fn(&str) -> Option<f32>,
+{
+    Ok(match node {
+        Node::Number(v) => *v,
+        Node::Parameter(name) => {
+            lookup(name).ok_or_else(|| ExpressionError::UnknownParameter(name.clone()))?
+        }
+        Node::Neg(a) => -eval(a, lookup)?,
+        Node::Add(a, b) => eval(a, lookup)? + eval(b, lookup)?,
+        Node::Sub(a, b) => eval(a, lookup)? - eval(b, lookup)?,
+        Node::Mul(a, b) => eval(a, lookup)? * eval(b, lookup)?,
+        Node::Div(a, b) => eval(a, lookup)? / eval(b, lookup)?,
+        Node::Pow(a, b) => eval(a, lookup)?.powf(eval(b, lookup)?),
+        Node::Function(f, a) => f(eval(a, lookup)?),
+    })
+}
+
+struct Parser<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.source.len() && self.source[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.source.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ExpressionError> {
+        match self.peek() {
+            Some(x) if x == c => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(_) => Err(ExpressionError::Syntax(self.pos)),
+            None => Err(ExpressionError::UnexpectedEnd),
+        }
+    }
+
+    // expression := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.term()?;
+        loop {
+            match self.peek() {
+                Some(b'+') => {
+                    self.pos += 1;
+                    node = Node::Add(Box::new(node), Box::new(self.term()?));
+                }
+                Some(b'-') => {
+                    self.pos += 1;
+                    node = Node::Sub(Box::new(node), Box::new(self.term()?));
+                }
+                _ => return Ok(node),
+            }
+        }
+    }
+
+    // term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.unary()?;
+        loop {
+            match self.peek() {
+                Some(b'*') => {
+                    self.pos += 1;
+                    node = Node::Mul(Box::new(node), Box::new(self.unary()?));
+                }
+                Some(b'/') => {
+                    self.pos += 1;
+                    node = Node::Div(Box::new(node), Box::new(self.unary()?));
+                }
+                _ => return Ok(node),
+            }
+        }
+    }
+
+    // unary := '-' unary | power
+    fn unary(&mut self) -> Result<Node, ExpressionError> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    // power := primary ('^' unary)?
+    fn power(&mut self) -> Result<Node, ExpressionError> {
+        let node = self.primary()?;
+        if self.peek() == Some(b'^') {
+            self.pos += 1;
+            return Ok(Node::Pow(Box::new(node), Box::new(self.unary()?)));
+        }
+        Ok(node)
+    }
+
+    // primary := number | name | name '(' expression ')' | '(' expression ')'
+    fn primary(&mut self) -> Result<Node, ExpressionError> {
+        match self.peek() {
+            None => Err(ExpressionError::UnexpectedEnd),
+            Some(b'(') => {
+                self.pos += 1;
+                let node = self.expression()?;
+                self.expect(b')')?;
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => self.name(),
+            Some(_) => Err(ExpressionError::Syntax(self.pos)),
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, ExpressionError> {
+        let start = self.pos;
+        while self.pos < self.source.len()
+            && (self.source[self.pos].is_ascii_digit() || self.source[self.pos] == b'.')
+        {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.source[start..self.pos])
+            .ok()
+            .and_then(|s| s.parse::<f32>().ok())
+            .map(Node::Number)
+            .ok_or(ExpressionError::Syntax(start))
+    }
+
+    fn name(&mut self) -> Result<Node, ExpressionError> {
+        let start = self.pos;
+        while self.pos < self.source.len()
+            && (self.source[self.pos].is_ascii_alphanumeric() || self.source[self.pos] == b'_')
+        {
+            self.pos += 1;
+        }
+        let name = String::from_utf8_lossy(&self.source[start..self.pos]).to_string();
+        if self.peek() == Some(b'(') {
+            let f: fn(f32) -> f32 = match name.as_str() {
+                "sin" => f32::sin,
+                "cos" => f32::cos,
+                "tan" => f32::tan,
+                "sqrt" => f32::sqrt,
+                "abs" => f32::abs,
+                _ => return Err(ExpressionError::UnknownFunction(name)),
+            };
+            self.pos += 1;
+            let node = self.expression()?;
+            self.expect(b')')?;
+            return Ok(Node::Function(f, Box::new(node)));
+        }
+        if name == "pi" {
+            return Ok(Node::Number(std::f32::consts::PI));
+        }
+        Ok(Node::Parameter(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn eval_str(s: &str) -> Result<f32, ExpressionError> {
+        Expression::parse(s)?.eval(|name| if name == "a" { Some(2.0) } else { None })
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(eval_str("1 + 2 * 3"), Ok(7.0));
+        assert_eq!(eval_str("(1 + 2) * 3"), Ok(9.0));
+        assert_eq!(eval_str("-a ^ 2"), Ok(-4.0));
+        assert_eq!(eval_str("2 ^ 3 ^ 2"), Ok(512.0));
+        assert_eq!(eval_str("1 - 2 - 3"), Ok(-4.0));
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(eval_str("sqrt(a * 8)"), Ok(4.0));
+        assert!(eval_str("sin(pi / 2)").unwrap() - 1.0 < 1e-6);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(eval_str("1 +"), Err(ExpressionError::UnexpectedEnd));
+        assert_eq!(eval_str("1 $ 2"), Err(ExpressionError::Syntax(2)));
+        assert_eq!(eval_str("(1 + 2"), Err(ExpressionError::UnexpectedEnd));
+        assert_eq!(
+            eval_str("b + 1"),
+            Err(ExpressionError::UnknownParameter("b".to_string()))
+        );
+        assert_eq!(
+            eval_str("foo(1)"),
+            Err(ExpressionError::UnknownFunction("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_constant_roundtrip() {
+        for value in [1e-5, 1e7, -0.25, 3.0] {
+            let e = Expression::constant(value);
+            let json = serde_json::to_string(&e).unwrap();
+            let e2 = serde_json::from_str::<Expression>(&json).unwrap();
+            assert_eq!(e2.eval(|_| None), Ok(value));
+        }
+    }
+}
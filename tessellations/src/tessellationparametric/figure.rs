@@ -0,0 +1,326 @@
+use crate::tessellationfigure::{Decoration, Prototile, TessellationFigure, Texture};
+use crate::tessellationline::{point_deserialize, point_serialize, Point, TessellationLine};
+use crate::tessellationparametric::{Expression, ExpressionError};
+use crate::tessellationshape::TessellationShape;
+use serde::{Deserialize, Serialize};
+
+/// Named parameter of a parametric figure with the range of its slider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Parameter {
+    pub name: String,
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Parameter {
+    pub fn new(name: &str, value: f32, min: f32, max: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            min,
+            max,
+        }
+    }
+}
+
+/// Point with coordinates that are expressions of the parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParametricPoint {
+    pub x: Expression,
+    pub y: Expression,
+}
+
+impl ParametricPoint {
+    /// Parse the expressions for `x` and `y`
+    pub fn parse(x: &str, y: &str) -> Result<Self, ExpressionError> {
+        Ok(Self {
+            x: Expression::parse(x)?,
+            y: Expression::parse(y)?,
+        })
+    }
+}
+
+impl From<Point> for ParametricPoint {
+    fn from(p: Point) -> Self {
+        Self {
+            x: Expression::constant(p.x),
+            y: Expression::constant(p.y),
+        }
+    }
+}
+
+/// Line with fixed endpoints and transform, the points in between are parametric
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParametricLine {
+    #[serde(
+        serialize_with = "point_serialize",
+        deserialize_with = "point_deserialize"
+    )]
+    pub start: Point,
+    #[serde(
+        serialize_with = "point_serialize",
+        deserialize_with = "point_deserialize"
+    )]
+    pub end: Point,
+    pub points: Vec<ParametricPoint>,
+    pub tx: f32,
+    pub ty: f32,
+    pub angle: f32,
+}
+
+impl ParametricLine {
+    pub fn new(tx: f32, ty: f32, angle: f32, start: Point, end: Point) -> Self {
+        Self {
+            start,
+            end,
+            points: Vec::<ParametricPoint>::new(),
+            tx,
+            ty,
+            angle,
+        }
+    }
+
+    /// Append `point` before the end point
+    pub fn append(&mut self, point: ParametricPoint) {
+        self.points.push(point);
+    }
+}
+
+/// Figure whose line points are expressions of named parameters.
+///
+/// Evaluating the figure with [`ParametricFigure::figure`] gives a normal
+/// [`TessellationFigure`], so a family of related tiles can be made by
+/// changing the parameter values.
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct ParametricFigure {
+    pub parameters: Vec<Parameter>,
+    pub lines: Vec<ParametricLine>,
+    pub gridincx: f32,
+    pub gridincy: f32,
+    pub shiftx: f32,
+    pub shifty: f32,
+    pub rotdiv: u32,
+    pub is_reversed: bool,
+    pub shape: TessellationShape,
+    #[serde(default)]
+    pub decorations: Vec<Decoration>,
+    #[serde(default)]
+    pub prototiles: Vec<Prototile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub textures: Vec<Texture>,
+}
+
+impl ParametricFigure {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a parametric figure without parameters from `figure`, `None`
+    /// when a line has fewer than two points.
+    pub fn from_figure(figure: &TessellationFigure) -> Option<Self> {
+        let lines = figure
+            .lines
+            .iter()
+            .map(|l| {
+                let points = l.dpoints();
+                let n = points.len();
+                if n < 2 {
+                    return None;
+                }
+                let mut line =
+                    ParametricLine::new(l.tx(), l.ty(), l.angle(), points[0], points[n - 1]);
+                for &p in &points[1..n - 1] {
+                    line.append(p.into());
+                }
+                Some(line)
+            })
+            .collect::<Option<Vec<ParametricLine>>>()?;
+        Some(Self {
+            parameters: Vec::<Parameter>::new(),
+            lines,
+            gridincx: figure.gridincx,
+            gridincy: figure.gridincy,
+            shiftx: figure.shiftx,
+            shifty: figure.shifty,
+            rotdiv: figure.rotdiv,
+            is_reversed: figure.is_reversed,
+            shape: figure.shape,
+            decorations: figure.decorations.clone(),
+            prototiles: figure.prototiles.clone(),
+            textures: figure.textures.clone(),
+        })
+    }
+
+    /// Add a parameter
+    pub fn add_parameter(&mut self, parameter: Parameter) {
+        self.parameters.push(parameter);
+    }
+
+    /// Append `line` to the figure
+    pub fn append(&mut self, line: ParametricLine) {
+        self.lines.push(line);
+    }
+
+    /// Returns the value of the parameter `name`
+    pub fn get(&self, name: &str) -> Option<f32> {
+        self.parameters
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| p.value)
+    }
+
+    /// Set the parameter `name` to `value` clamped to its range, returns false
+    /// if there is no parameter with that name.
+    pub fn set(&mut self, name: &str, value: f32) -> bool {
+        match self.parameters.iter_mut().find(|p| p.name == name) {
+            Some(p) => {
+                p.value = value.clamp(p.min, p.max);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Move the parameter at `index` by `steps` hundredths of its range like
+    /// a slider, returns false if there is no parameter at `index`.
+    pub fn step(&mut self, index: usize, steps: f32) -> bool {
+        match self.parameters.get_mut(index) {
+            Some(p) => {
+                p.value = (p.value + steps * (p.max - p.min) / 100.0).clamp(p.min, p.max);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Evaluate the points with the current parameter values
+    pub fn figure(&self) -> Result<TessellationFigure, ExpressionError> {
+        let lookup = |name: &str| self.get(name);
+        let mut f = TessellationFigure::new();
+        f.gridincx = self.gridincx;
+        f.gridincy = self.gridincy;
+        f.shiftx = self.shiftx;
+        f.shifty = self.shifty;
+        f.rotdiv = self.rotdiv;
+        f.is_reversed = self.is_reversed;
+        f.shape = self.shape;
+        f.decorations = self.decorations.clone();
+        f.prototiles = self.prototiles.clone();
+        f.textures = self.textures.clone();
+        for l in &self.lines {
+            let mut line = TessellationLine::new(l.tx, l.ty, l.angle);
+            line.append(l.start);
+            for p in &l.points {
+                line.append(Point::new(p.x.eval(lookup)?, p.y.eval(lookup)?));
+            }
+            line.append(l.end);
+            f.append(line);
+        }
+        Ok(f)
+    }
+
+    /// A square with a bulge on the left side and a neck on the top side
+    pub fn bulge() -> Self {
+        let mut f = ParametricFigure::new();
+        f.gridincx = 1.0;
+        f.gridincy = 1.0;
+        f.rotdiv = 1;
+        f.is_reversed = false;
+        f.shiftx = 0.0;
+        f.shifty = 1.0;
+        f.shape = TessellationShape::S;
+        f.add_parameter(Parameter::new("bulge", 0.2, -0.4, 0.4));
+        f.add_parameter(Parameter::new("neck_width", 0.3, 0.05, 0.45));
+
+        let mut l1 = ParametricLine::new(1.0, 0.0, 0.0, Point::new(0.0, 0.0), Point::new(0.0, 1.0));
+        l1.append(ParametricPoint::parse("bulge", "0.3").unwrap());
+        l1.append(ParametricPoint::parse("bulge", "0.7").unwrap());
+        f.append(l1);
+
+        let mut l2 =
+            ParametricLine::new(0.0, -1.0, 0.0, Point::new(0.0, 1.0), Point::new(1.0, 1.0));
+        l2.append(ParametricPoint::parse("0.5 - neck_width / 2", "1.0").unwrap());
+        l2.append(ParametricPoint::parse("0.5 - neck_width / 2", "0.8").unwrap());
+        l2.append(ParametricPoint::parse("0.5 + neck_width / 2", "0.8").unwrap());
+        l2.append(ParametricPoint::parse("0.5 + neck_width / 2", "1.0").unwrap());
+        f.append(l2);
+
+        f
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_square_roundtrip() {
+        let f = ParametricFigure::from_figure(&TessellationFigure::square()).unwrap();
+        assert_eq!(
+            f.figure().unwrap().points(),
+            TessellationFigure::square().points()
+        );
+    }
+
+    #[test]
+    fn test_bulge() {
+        let mut f = ParametricFigure::bulge();
+        assert!(f.set("bulge", 0.1));
+        assert!(!f.set("missing", 0.1));
+        let t = f.figure().unwrap();
+        assert_eq!(t.lines[0].dpoints()[1], Point::new(0.1, 0.3));
+        assert_eq!(t.lines[0].cpoints()[1], Point::new(1.1, 0.3));
+        assert_eq!(t.lines[0].dpoints()[3], Point::new(0.0, 1.0));
+    }
+
+    #[test]
+    fn test_set_clamps() {
+        let mut f = ParametricFigure::bulge();
+        f.set("bulge", 2.0);
+        assert_eq!(f.get("bulge"), Some(0.4));
+    }
+
+    #[test]
+    fn test_step() {
+        let mut f = ParametricFigure::bulge();
+        assert!(f.step(0, 10.0));
+        assert!((f.get("bulge").unwrap() - 0.28).abs() < 1e-6);
+        f.step(0, 100.0);
+        assert_eq!(f.get("bulge"), Some(0.4));
+        assert!(!f.step(2, 1.0));
+    }
+
+    #[test]
+    fn test_textures_roundtrip() {
+        let mut figure = TessellationFigure::square();
+        figure.add_texture(Texture::new(
+            vec![1, 2, 3],
+            Point::new(0.0, 0.0),
+            euclid::vec2(1.0, 1.0),
+        ));
+        let f = ParametricFigure::from_figure(&figure).unwrap();
+        assert_eq!(f.textures, figure.textures);
+        assert_eq!(f.figure().unwrap().textures, figure.textures);
+    }
+
+    #[test]
+    fn test_from_figure_short_line() {
+        let mut figure = TessellationFigure::square();
+        figure.lines[0] = TessellationLine::new(0.0, 0.0, 0.0);
+        assert!(ParametricFigure::from_figure(&figure).is_none());
+        figure.lines[0].append(Point::new(0.0, 0.0));
+        assert!(ParametricFigure::from_figure(&figure).is_none());
+    }
+
+    #[test]
+    fn test_from_figure_small_coordinates() {
+        let mut figure = TessellationFigure::square();
+        figure.lines[0].insert(1, Point::new(1e-5, 0.5));
+        let f = ParametricFigure::from_figure(&figure).unwrap();
+        let json = serde_json::to_string(&f).unwrap();
+        let f2 = serde_json::from_str::<ParametricFigure>(&json).unwrap();
+        assert_eq!(f2.figure().unwrap().points(), figure.points());
+    }
+}
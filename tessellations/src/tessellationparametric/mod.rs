@@ -0,0 +1,5 @@
+mod expression;
+mod figure;
+
+pub use expression::{Expression, ExpressionError};
+pub use figure::{Parameter, ParametricFigure, ParametricLine, ParametricPoint};
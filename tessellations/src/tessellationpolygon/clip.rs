@@ -0,0 +1,422 @@
+use crate::tessellationline::{Point, Vector};
+use crate::tessellationpolygon::clean;
+use std::f32::consts::PI;
+
+/// Distance along an edge, as a part of its length, below which an
+/// intersection is at a corner
+const CORNER_EPS: f64 = 1e-6;
+
+/// Distance the clip is moved to get away from corners on edges
+const NUDGE: f64 = 1e-5;
+
+/// Shape that cuts the plane, in world units
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipShape {
+    Circle {
+        center: Point,
+        radius: f32,
+    },
+    /// polygon with `sides` corners on a circle, the first corner at
+    /// `rotation` radians from the x axis
+    RegularPolygon {
+        center: Point,
+        radius: f32,
+        sides: usize,
+        rotation: f32,
+    },
+    /// closed path
+    Path(Vec<Point>),
+}
+
+impl ClipShape {
+    /// The outline of the shape, a circle gets enough corners to stay within
+    /// `tolerance` of the curve
+    pub fn outline(&self, tolerance: f32) -> Vec<Point> {
+        match self {
+            ClipShape::Circle { center, radius } => {
+                // the sagitta of every side is at most the tolerance
+                let step = 2.0 * (1.0 - tolerance.max(1e-6) / radius).clamp(-1.0, 1.0).acos();
+                let sides = ((2.0 * PI / step).ceil() as usize).max(8);
+                regular_polygon(*center, *radius, sides, 0.0)
+            }
+            ClipShape::RegularPolygon {
+                center,
+                radius,
+                sides,
+                rotation,
+            } => regular_polygon(*center, *radius, *sides, *rotation),
+            ClipShape::Path(points) => clean(points, 1e-6),
+        }
+    }
+}
+
+fn regular_polygon(center: Point, radius: f32, sides: usize, rotation: f32) -> Vec<Point> {
+    if sides < 3 {
+        return Vec::new();
+    }
+    (0..sides)
+        .map(|k| {
+            let a = rotation + 2.0 * PI * k as f32 / sides as f32;
+            center + Vector::new(a.cos(), a.sin()) * radius
+        })
+        .collect()
+}
+
+/// True when `p` is inside the closed polygon, by the even odd rule
+pub fn contains(polygon: &[Point], p: Point) -> bool {
+    let points: Vec<[f64; 2]> = polygon.iter().map(|p| to_f64(*p)).collect();
+    contains_f64(&points, to_f64(p))
+}
+
+fn to_f64(p: Point) -> [f64; 2] {
+    [p.x as f64, p.y as f64]
+}
+
+fn contains_f64(polygon: &[[f64; 2]], p: [f64; 2]) -> bool {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % n];
+        if (a[1] > p[1]) != (b[1] > p[1]) {
+            let x = a[0] + (p[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0]);
+            if p[0] < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Corner of a polygon or crossing of the two polygons in a ring
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    p: [f64; 2],
+    next: usize,
+    prev: usize,
+    /// the same crossing in the other ring
+    neighbor: usize,
+    crossing: bool,
+    /// the boundary enters the other polygon here
+    entry: bool,
+    visited: bool,
+}
+
+/// Point where edge `a` of one polygon crosses edge `b` of the other, at
+/// `alpha` and `beta` of their lengths
+#[derive(Debug, Clone, Copy)]
+struct Crossing {
+    a: usize,
+    alpha: f64,
+    b: usize,
+    beta: f64,
+    p: [f64; 2],
+}
+
+/// Crossings of the edges of two polygons, `None` when a corner of one
+/// lies on the other or edges overlap
+fn crossings(a: &[[f64; 2]], b: &[[f64; 2]]) -> Option<Vec<Crossing>> {
+    let mut res = Vec::new();
+    for i in 0..a.len() {
+        let p = a[i];
+        let q = a[(i + 1) % a.len()];
+        let r = [q[0] - p[0], q[1] - p[1]];
+        for j in 0..b.len() {
+            let u = b[j];
+            let v = b[(j + 1) % b.len()];
+            let s = [v[0] - u[0], v[1] - u[1]];
+            let d = r[0] * s[1] - r[1] * s[0];
+            let w = [u[0] - p[0], u[1] - p[1]];
+            let lr = r[0].hypot(r[1]);
+            let ls = s[0].hypot(s[1]);
+            if d.abs() <= 1e-12 * lr * ls {
+                // parallel edges on one line overlap or touch
+                if (w[0] * r[1] - w[1] * r[0]).abs() <= CORNER_EPS * lr {
+                    let t0 = (w[0] * r[0] + w[1] * r[1]) / (lr * lr);
+                    let t1 = t0 + (s[0] * r[0] + s[1] * r[1]) / (lr * lr);
+                    if t0.max(t1) >= -CORNER_EPS && t0.min(t1) <= 1.0 + CORNER_EPS {
+                        return None;
+                    }
+                }
+                continue;
+            }
+            let t = (w[0] * s[1] - w[1] * s[0]) / d;
+            let k = (w[0] * r[1] - w[1] * r[0]) / d;
+            let inner = |x: f64| x > CORNER_EPS && x < 1.0 - CORNER_EPS;
+            let outer = |x: f64| (-CORNER_EPS..=1.0 + CORNER_EPS).contains(&x);
+            if outer(t) && outer(k) {
+                if !inner(t) || !inner(k) {
+                    return None;
+                }
+                res.push(Crossing {
+                    a: i,
+                    alpha: t,
+                    b: j,
+                    beta: k,
+                    p: [p[0] + r[0] * t, p[1] + r[1] * t],
+                });
+            }
+        }
+    }
+    Some(res)
+}
+
+/// Ring of the corners of `points` with the crossings on edge `i` at
+/// `(alpha, crossing)` inserted in order, the vertices start at `first`
+fn ring(
+    vertices: &mut Vec<Vertex>,
+    points: &[[f64; 2]],
+    mut on_edge: Vec<Vec<(f64, usize)>>,
+    first: usize,
+) -> Vec<usize> {
+    let mut order = Vec::new();
+    for (i, p) in points.iter().enumerate() {
+        order.push(vertices.len());
+        vertices.push(Vertex {
+            p: *p,
+            next: 0,
+            prev: 0,
+            neighbor: 0,
+            crossing: false,
+            entry: false,
+            visited: false,
+        });
+        on_edge[i].sort_by(|a, b| a.0.total_cmp(&b.0));
+        for &(_, crossing) in &on_edge[i] {
+            order.push(first + crossing);
+        }
+    }
+    let n = order.len();
+    for k in 0..n {
+        vertices[order[k]].next = order[(k + 1) % n];
+        vertices[order[k]].prev = order[(k + n - 1) % n];
+    }
+    order
+}
+
+/// The parts of the closed polygon `subject` inside the closed polygon
+/// `clip`, both may be concave and in either orientation.
+///
+/// When a corner of one polygon lies on the other, the clip is moved by a
+/// tiny distance so every crossing is a proper one.
+pub fn intersection(subject: &[Point], clip: &[Point]) -> Vec<Vec<Point>> {
+    let subject = clean(subject, 1e-6);
+    let clip = clean(clip, 1e-6);
+    if subject.is_empty() || clip.is_empty() {
+        return Vec::new();
+    }
+    let a: Vec<[f64; 2]> = subject.iter().map(|p| to_f64(*p)).collect();
+    for attempt in 0..8 {
+        let angle = attempt as f64 * 2.3;
+        let nudge = if attempt == 0 {
+            0.0
+        } else {
+            NUDGE * attempt as f64
+        };
+        let b: Vec<[f64; 2]> = clip
+            .iter()
+            .map(|p| {
+                [
+                    p.x as f64 + nudge * angle.cos(),
+                    p.y as f64 + nudge * angle.sin(),
+                ]
+            })
+            .collect();
+        if let Some(found) = crossings(&a, &b) {
+            let res = if found.is_empty() {
+                if contains_f64(&b, a[0]) {
+                    vec![subject.clone()]
+                } else if contains_f64(&a, b[0]) {
+                    vec![b
+                        .iter()
+                        .map(|p| Point::new(p[0] as f32, p[1] as f32))
+                        .collect()]
+                } else {
+                    Vec::new()
+                }
+            } else {
+                trace(&a, &b, &found)
+            };
+            return res
+                .iter()
+                .map(|points| clean(points, 1e-6))
+                .filter(|points| !points.is_empty())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+/// Walk the rings of the two polygons from crossing to crossing and keep
+/// the parts of the boundaries inside the other polygon
+fn trace(a: &[[f64; 2]], b: &[[f64; 2]], found: &[Crossing]) -> Vec<Vec<Point>> {
+    let mut vertices = Vec::<Vertex>::new();
+    // the crossings seen from `a` and from `b` come first
+    for crossing in found.iter().chain(found.iter()) {
+        vertices.push(Vertex {
+            p: crossing.p,
+            next: 0,
+            prev: 0,
+            neighbor: 0,
+            crossing: true,
+            entry: false,
+            visited: false,
+        });
+    }
+    let m = found.len();
+    for k in 0..m {
+        vertices[k].neighbor = m + k;
+        vertices[m + k].neighbor = k;
+    }
+    let mut on_a = vec![Vec::new(); a.len()];
+    let mut on_b = vec![Vec::new(); b.len()];
+    for (k, crossing) in found.iter().enumerate() {
+        on_a[crossing.a].push((crossing.alpha, k));
+        on_b[crossing.b].push((crossing.beta, k));
+    }
+    let ring_a = ring(&mut vertices, a, on_a, 0);
+    let ring_b = ring(&mut vertices, b, on_b, m);
+    for (order, other) in [(&ring_a, b), (&ring_b, a)] {
+        let mut inside = contains_f64(other, vertices[order[0]].p);
+        for &v in order.iter() {
+            if vertices[v].crossing {
+                vertices[v].entry = !inside;
+                inside = !inside;
+            }
+        }
+    }
+
+    let mut res = Vec::new();
+    while let Some(start) = (0..m).find(|&k| !vertices[k].visited) {
+        let mut points = vec![vertices[start].p];
+        let mut current = start;
+        loop {
+            vertices[current].visited = true;
+            let neighbor = vertices[current].neighbor;
+            vertices[neighbor].visited = true;
+            let forward = vertices[current].entry;
+            loop {
+                current = if forward {
+                    vertices[current].next
+                } else {
+                    vertices[current].prev
+                };
+                points.push(vertices[current].p);
+                if vertices[current].crossing {
+                    break;
+                }
+            }
+            if current == start || vertices[current].neighbor == start {
+                points.pop();
+                break;
+            }
+            current = vertices[current].neighbor;
+            if points.len() > 4 * vertices.len() {
+                break;
+            }
+        }
+        res.push(
+            points
+                .iter()
+                .map(|p| Point::new(p[0] as f32, p[1] as f32))
+                .collect(),
+        );
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::tessellationpolygon::signed_area;
+
+    fn square(x: f32, y: f32, size: f32) -> Vec<Point> {
+        vec![
+            Point::new(x, y),
+            Point::new(x, y + size),
+            Point::new(x + size, y + size),
+            Point::new(x + size, y),
+        ]
+    }
+
+    fn area(parts: &[Vec<Point>]) -> f32 {
+        parts.iter().map(|p| signed_area(p).abs()).sum()
+    }
+
+    #[test]
+    fn test_contains() {
+        let s = square(0.0, 0.0, 1.0);
+        assert!(contains(&s, Point::new(0.5, 0.5)));
+        assert!(!contains(&s, Point::new(1.5, 0.5)));
+    }
+
+    #[test]
+    fn test_overlapping_squares() {
+        let parts = intersection(&square(0.0, 0.0, 1.0), &square(0.5, 0.5, 1.0));
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].len(), 4);
+        assert!((area(&parts) - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_inside_and_outside() {
+        let small = square(0.25, 0.25, 0.5);
+        let large = square(0.0, 0.0, 1.0);
+        assert!((area(&intersection(&small, &large)) - 0.25).abs() < 1e-6);
+        assert!((area(&intersection(&large, &small)) - 0.25).abs() < 1e-6);
+        assert!(intersection(&small, &square(2.0, 0.0, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn test_concave_gives_two_parts() {
+        // U shape cut by a bar across both arms
+        let u = vec![
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+            Point::new(3.0, 3.0),
+            Point::new(2.0, 3.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 3.0),
+            Point::new(0.0, 3.0),
+        ];
+        let bar = vec![
+            Point::new(-1.0, 2.0),
+            Point::new(4.0, 2.0),
+            Point::new(4.0, 2.5),
+            Point::new(-1.0, 2.5),
+        ];
+        let parts = intersection(&u, &bar);
+        assert_eq!(parts.len(), 2);
+        assert!((area(&parts) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_shared_edges() {
+        // corners on edges and overlapping edges are moved apart
+        let s = square(0.0, 0.0, 1.0);
+        assert!((area(&intersection(&s, &s)) - 1.0).abs() < 1e-3);
+        let parts = intersection(&s, &square(0.0, 0.5, 1.0));
+        assert!((area(&parts) - 0.5).abs() < 1e-3);
+        assert!(area(&intersection(&s, &square(1.0, 0.0, 1.0))) < 1e-3);
+    }
+
+    #[test]
+    fn test_circle() {
+        let circle = ClipShape::Circle {
+            center: Point::new(0.0, 0.0),
+            radius: 1.0,
+        };
+        let outline = circle.outline(0.001);
+        assert!(outline.len() > 32);
+        assert!((signed_area(&outline).abs() - PI).abs() < 0.01);
+        let hexagon = ClipShape::RegularPolygon {
+            center: Point::new(0.0, 0.0),
+            radius: 1.0,
+            sides: 6,
+            rotation: 0.0,
+        };
+        assert_eq!(hexagon.outline(0.001).len(), 6);
+    }
+}
@@ -0,0 +1,7 @@
+mod clip;
+mod offset;
+mod strokes;
+
+pub use clip::{contains, intersection, ClipShape};
+pub use offset::{clean, offset, offset_loops, signed_area};
+pub use strokes::{order_strokes, strokes, travel};
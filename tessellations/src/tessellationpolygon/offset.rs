@@ -0,0 +1,491 @@
+use crate::tessellationline::{Point, Vector};
+use crate::tessellationpolygon::contains;
+use crate::tessellationstyle::LineJoin;
+use std::f32::consts::PI;
+
+/// Longest miter as a multiple of the offset distance, longer miters are
+/// beveled
+const MITER_LIMIT: f32 = 4.0;
+
+/// Largest angle in radians between the points of a round join
+const ROUND_STEP: f32 = PI / 18.0;
+
+/// Signed area of the closed polygon, positive when the points
+/// turn from the x axis to the y axis
+pub fn signed_area(points: &[Point]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| points[i].to_vector().cross(points[(i + 1) % n].to_vector()))
+        .sum::<f32>()
+        / 2.0
+}
+
+/// The closed polygon without repeated points and without points on a
+/// straight line between their neighbours, `eps` is the distance below
+/// which points are the same
+pub fn clean(points: &[Point], eps: f32) -> Vec<Point> {
+    let mut res = points.to_vec();
+    res.dedup_by(|p, q| (*p - *q).length() <= eps);
+    while res.len() > 1 && (res[0] - res[res.len() - 1]).length() <= eps {
+        res.pop();
+    }
+    // drop straight and folded back corners until none is left
+    let mut i = 0;
+    while res.len() >= 3 && i < res.len() {
+        let n = res.len();
+        let prev = res[(i + n - 1) % n];
+        let next = res[(i + 1) % n];
+        let a = res[i] - prev;
+        let b = next - res[i];
+        if a.cross(b).abs() <= eps * (a.length() + b.length()) {
+            res.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+    if res.len() < 3 {
+        res.clear();
+    }
+    res
+}
+
+/// Edge of a polygon moved along its normal
+#[derive(Debug, Clone, Copy)]
+struct OffsetEdge {
+    /// corner of the polygon where the edge starts
+    corner: Point,
+    /// start of the moved edge
+    from: Point,
+    /// unit direction
+    dir: Vector,
+    /// unit normal pointing out of the polygon
+    normal: Vector,
+}
+
+/// Point where the lines through two edges meet, `None` when parallel
+fn intersect(a: &OffsetEdge, b: &OffsetEdge) -> Option<Point> {
+    let d = a.dir.cross(b.dir);
+    if d.abs() < 1e-6 {
+        return None;
+    }
+    let t = (b.from - a.from).cross(b.dir) / d;
+    Some(a.from + a.dir * t)
+}
+
+/// The closed polygon moved `distance` outwards, or inwards when negative.
+///
+/// Edges that vanish while moving are removed, an empty list is returned
+/// when the whole polygon vanishes. Corners where the moved edges part are
+/// filled with `join`. Repeated points are ignored and where moved edges
+/// cross, like in a narrow notch, only the largest part is kept, so
+/// shrinking a tile with a narrow waist keeps its larger half and growing a
+/// tile loses the holes it closes off. [`offset_loops`] keeps every part and
+/// hole.
+pub fn offset(points: &[Point], distance: f32, join: LineJoin) -> Vec<Point> {
+    offset_loops(points, distance, join)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+/// Every loop of the closed polygon moved `distance` outwards, or inwards
+/// when negative. The parts turn the same way as the polygon and come first,
+/// largest first, followed by the holes turning the other way. Shrinking a
+/// tile with a narrow waist gives a loop for every part and growing a tile
+/// with a narrow slot into a chamber gives a hole for the chamber
+pub fn offset_loops(points: &[Point], distance: f32, join: LineJoin) -> Vec<Vec<Point>> {
+    let points = clean(points, 1e-5);
+    if points.is_empty() {
+        return Vec::new();
+    }
+    if distance == 0.0 {
+        return vec![points];
+    }
+    let orientation = signed_area(&points).signum();
+    let n = points.len();
+    let mut edges: Vec<OffsetEdge> = (0..n)
+        .map(|i| {
+            let dir = (points[(i + 1) % n] - points[i]).normalize();
+            let normal = Vector::new(dir.y, -dir.x) * orientation;
+            OffsetEdge {
+                corner: points[i],
+                from: points[i] + normal * distance,
+                dir,
+                normal,
+            }
+        })
+        .collect();
+
+    // remove the edges that turn around after moving, shortest first
+    loop {
+        if edges.len() < 3 {
+            return Vec::new();
+        }
+        let n = edges.len();
+        let corners: Vec<Option<Point>> = (0..n)
+            .map(|i| intersect(&edges[(i + n - 1) % n], &edges[i]))
+            .collect();
+        let reversed = (0..n)
+            .filter_map(|i| match (corners[i], corners[(i + 1) % n]) {
+                (Some(a), Some(b)) => {
+                    let length = (b - a).dot(edges[i].dir);
+                    if length < 0.0 {
+                        Some((i, length))
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        match reversed {
+            Some((i, _)) => {
+                edges.remove(i);
+            }
+            None => break,
+        }
+    }
+
+    let n = edges.len();
+    let mut res = Vec::<Point>::new();
+    for i in 0..n {
+        let prev = &edges[(i + n - 1) % n];
+        let edge = &edges[i];
+        let start = edge.corner + edge.normal * distance;
+        let end = prev.corner
+            + prev.dir * (edge.corner - prev.corner).dot(prev.dir)
+            + prev.normal * distance;
+        // the moved edges part on the outside of a convex corner when
+        // growing and of a concave corner when shrinking
+        let convex = prev.dir.cross(edge.dir) * orientation > 0.0;
+        let parting = convex == (distance > 0.0);
+        match intersect(prev, edge) {
+            Some(p) if !parting => res.push(p),
+            Some(p)
+                if join == LineJoin::Miter
+                    && (p - edge.corner).length() <= MITER_LIMIT * distance.abs() =>
+            {
+                res.push(p)
+            }
+            _ if join == LineJoin::Round && parting => {
+                // around the corner on the side the edges moved to
+                let (from, to) = (prev.normal * distance, edge.normal * distance);
+                let a0 = from.y.atan2(from.x);
+                let mut sweep = to.y.atan2(to.x) - a0;
+                while sweep > PI {
+                    sweep -= 2.0 * PI;
+                }
+                while sweep < -PI {
+                    sweep += 2.0 * PI;
+                }
+                let steps = (sweep.abs() / ROUND_STEP).ceil().max(1.0) as usize;
+                for k in 0..=steps {
+                    let a = a0 + sweep * k as f32 / steps as f32;
+                    res.push(edge.corner + Vector::new(a.cos(), a.sin()) * distance.abs());
+                }
+            }
+            _ => {
+                res.push(end);
+                res.push(start);
+            }
+        }
+    }
+    // edges far apart along the outline can still cross, like the sides of
+    // a narrow notch when growing. The loops left over beyond the crossing
+    // have the moved polygon on both sides of every edge and are dropped
+    let mut loops = Vec::new();
+    untangle(clean(&res, 1e-5), &mut loops);
+    let covered = |p: Point| {
+        let near = distance_to(&points, p) <= distance.abs();
+        if distance > 0.0 {
+            near || contains(&points, p)
+        } else {
+            !near && contains(&points, p)
+        }
+    };
+    let step = distance.abs() * 1e-2;
+    let mut loops: Vec<Vec<Point>> = loops
+        .into_iter()
+        .map(|points| clean(&points, 1e-5))
+        .filter(|moved| {
+            let n = moved.len();
+            (0..n).any(|i| {
+                let (a, b) = (moved[i], moved[(i + 1) % n]);
+                let side = Vector::new(b.y - a.y, a.x - b.x).normalize() * step;
+                let middle = a.lerp(b, 0.5);
+                covered(middle + side) != covered(middle - side)
+            })
+        })
+        .collect();
+    let hole = |points: &[Point]| signed_area(points) * orientation < 0.0;
+    loops.sort_by(|a, b| {
+        hole(a)
+            .cmp(&hole(b))
+            .then(signed_area(b).abs().total_cmp(&signed_area(a).abs()))
+    });
+    loops
+}
+
+/// Distance from `p` to the nearest edge of the closed polygon
+fn distance_to(points: &[Point], p: Point) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            let t = ((p - a).dot(b - a) / (b - a).square_length()).clamp(0.0, 1.0);
+            (a.lerp(b, t) - p).length()
+        })
+        .fold(f32::MAX, f32::min)
+}
+
+/// Point where `a` `b` and `c` `d` cross or where the end of one lies on
+/// the other, `None` when they are parallel
+fn crossing(a: Point, b: Point, c: Point, d: Point) -> Option<Point> {
+    let r = b - a;
+    let s = d - c;
+    let den = r.cross(s);
+    if den.abs() < 1e-12 {
+        return None;
+    }
+    let t = (c - a).cross(s) / den;
+    let u = (c - a).cross(r) / den;
+    // the start of a segment is the end of the one before
+    let on = |t: f32| t > 1e-6 && t <= 1.0 + 1e-6;
+    if on(t) && on(u) {
+        Some(a + r * t)
+    } else {
+        None
+    }
+}
+
+/// Split the closed polygon at the points where its edges cross into loops
+/// that do not cross themselves
+fn untangle(points: Vec<Point>, loops: &mut Vec<Vec<Point>>) {
+    let n = points.len();
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            if let Some(x) = crossing(points[i], points[i + 1], points[j], points[(j + 1) % n]) {
+                let mut outer = points[..=i].to_vec();
+                outer.push(x);
+                outer.extend_from_slice(&points[j + 1..]);
+                let mut inner = vec![x];
+                inner.extend_from_slice(&points[i + 1..=j]);
+                untangle(outer, loops);
+                untangle(inner, loops);
+                return;
+            }
+        }
+    }
+    if n >= 3 {
+        loops.push(points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn square() -> Vec<Point> {
+        vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(signed_area(&square()), -1.0);
+    }
+
+    #[test]
+    fn test_clean() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 0.5),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+        assert_eq!(clean(&points, 1e-5), square());
+    }
+
+    #[test]
+    fn test_shrink_square() {
+        let res = offset(&square(), -0.25, LineJoin::Miter);
+        assert_eq!(res.len(), 4);
+        assert!((signed_area(&res) + 0.25).abs() < 1e-5);
+        assert!(res.contains(&Point::new(0.25, 0.25)));
+    }
+
+    #[test]
+    fn test_grow_square() {
+        let miter = offset(&square(), 0.5, LineJoin::Miter);
+        assert!((signed_area(&miter) + 4.0).abs() < 1e-5);
+        let bevel = offset(&square(), 0.5, LineJoin::Bevel);
+        assert_eq!(bevel.len(), 8);
+        let round = offset(&square(), 0.5, LineJoin::Round);
+        assert!(round.len() > 8);
+        assert!(round.iter().all(|p| {
+            let c = Point::new(p.x.clamp(0.0, 1.0), p.y.clamp(0.0, 1.0));
+            ((*p - c).length() - 0.5).abs() < 1e-4
+        }));
+    }
+
+    #[test]
+    fn test_vanish() {
+        assert!(offset(&square(), -0.5, LineJoin::Miter).is_empty());
+        assert!(offset(&square(), -0.75, LineJoin::Miter).is_empty());
+    }
+
+    #[test]
+    fn test_concave_corner() {
+        // L shape, the inner corner is beveled when shrinking
+        let l = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let res = offset(&l, -0.1, LineJoin::Miter);
+        assert_eq!(res.len(), 6);
+        assert!(res.contains(&Point::new(0.9, 0.9)));
+        let res = offset(&l, -0.1, LineJoin::Bevel);
+        assert_eq!(res.len(), 7);
+        let res = offset(&l, -0.1, LineJoin::Round);
+        assert!(res.len() > 6);
+    }
+
+    #[test]
+    fn test_thin_rectangle_loses_short_edges() {
+        let r = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 1.0),
+            Point::new(2.0, 1.2),
+            Point::new(0.0, 1.0),
+        ];
+        // the sides vanish and a triangle under the roof is left
+        let res = offset(&r, -0.55, LineJoin::Miter);
+        assert_eq!(res.len(), 3);
+        assert!(signed_area(&res) > 0.0);
+    }
+
+    #[test]
+    fn test_narrow_notch_is_filled() {
+        // a slot narrower than the offset into the top of a square
+        let notched = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 4.0),
+            Point::new(1.95, 4.0),
+            Point::new(1.95, 2.0),
+            Point::new(2.05, 2.0),
+            Point::new(2.05, 4.0),
+            Point::new(4.0, 4.0),
+            Point::new(4.0, 0.0),
+        ];
+        for join in [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel] {
+            let res = offset(&notched, 0.2, join);
+            let mut loops = Vec::new();
+            untangle(res.clone(), &mut loops);
+            assert_eq!(loops.len(), 1);
+            assert!(signed_area(&res) < -4.4 * 4.4 + 0.1, "{join:?}");
+            assert!(res.iter().all(|p| p.y <= 4.2 + 1e-4));
+        }
+    }
+
+    #[test]
+    fn test_sharp_spike() {
+        // a thin spike into the tile with repeated points at its tip
+        let spike = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.99, 2.0),
+            Point::new(1.0, 0.5),
+            Point::new(1.0, 0.5),
+            Point::new(1.0000001, 0.5),
+            Point::new(1.01, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+        for distance in [0.05, -0.05] {
+            let res = offset(&spike, distance, LineJoin::Round);
+            let mut loops = Vec::new();
+            untangle(res.clone(), &mut loops);
+            assert_eq!(loops.len(), 1);
+            assert!(signed_area(&res) < 0.0);
+        }
+    }
+
+    #[test]
+    fn test_keyhole_closes() {
+        // a narrow slot into a chamber, the sides of the slot cross but no
+        // edge turns around
+        let keyhole = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 4.0),
+            Point::new(1.95, 4.0),
+            Point::new(1.95, 2.0),
+            Point::new(1.5, 2.0),
+            Point::new(1.5, 1.0),
+            Point::new(2.5, 1.0),
+            Point::new(2.5, 2.0),
+            Point::new(2.05, 2.0),
+            Point::new(2.05, 4.0),
+            Point::new(4.0, 4.0),
+            Point::new(4.0, 0.0),
+        ];
+        let res = offset(&keyhole, 0.2, LineJoin::Miter);
+        let mut loops = Vec::new();
+        untangle(res.clone(), &mut loops);
+        assert_eq!(loops.len(), 1);
+        assert_eq!(res.len(), 4);
+        assert!((signed_area(&res) + 4.4 * 4.4).abs() < 1e-3);
+        // the chamber is kept as a hole turning the other way
+        let loops = offset_loops(&keyhole, 0.2, LineJoin::Miter);
+        assert_eq!(loops.len(), 2);
+        assert_eq!(loops[0], res);
+        assert!((signed_area(&loops[1]) - 0.6 * 0.6).abs() < 1e-3);
+        assert!(loops[1]
+            .iter()
+            .all(|p| (1.7 - 1e-4..=2.3 + 1e-4).contains(&p.x)
+                && (1.2 - 1e-4..=1.8 + 1e-4).contains(&p.y)));
+    }
+
+    #[test]
+    fn test_narrow_waist() {
+        // two squares joined by a waist narrower than the inset
+        let dumbbell = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 0.9),
+            Point::new(3.0, 0.9),
+            Point::new(3.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(6.0, 2.0),
+            Point::new(3.0, 2.0),
+            Point::new(3.0, 1.1),
+            Point::new(2.0, 1.1),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        let loops = offset_loops(&dumbbell, -0.2, LineJoin::Miter);
+        let areas: Vec<f32> = loops.iter().map(|l| signed_area(l)).collect();
+        assert_eq!(areas.len(), 2);
+        assert!((areas[0] - 2.6 * 1.6).abs() < 1e-4, "{areas:?}");
+        assert!((areas[1] - 1.6 * 1.6).abs() < 1e-4, "{areas:?}");
+        // only the larger part is left
+        assert_eq!(offset(&dumbbell, -0.2, LineJoin::Miter), loops[0]);
+    }
+}
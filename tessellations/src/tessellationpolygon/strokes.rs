@@ -0,0 +1,238 @@
+use crate::tessellationline::Point;
+use std::collections::{HashMap, HashSet};
+
+/// The edges of the closed polygons as strokes of a pen, points closer than
+/// `tolerance` are the same.
+///
+/// The edges are split where they pass through a corner of another polygon
+/// so overlapping edges are drawn once, then chained into strokes as long
+/// as possible. A closed stroke ends with its first point.
+pub fn strokes(polygons: &[Vec<Point>], tolerance: f32) -> Vec<Vec<Point>> {
+    let mut vertices = Vec::<Point>::new();
+    // a cell can hold several vertices further apart than `tolerance`
+    let mut index = HashMap::<(i64, i64), Vec<usize>>::new();
+    let mut vertex = |p: Point| -> usize {
+        let cell = (
+            (p.x / tolerance).round() as i64,
+            (p.y / tolerance).round() as i64,
+        );
+        // a point close to the border of a cell can be in its neighbour
+        let nearest = (-1..=1)
+            .flat_map(|dx| (-1..=1).map(move |dy| (cell.0 + dx, cell.1 + dy)))
+            .filter_map(|c| index.get(&c))
+            .flatten()
+            .map(|&k| (k, (vertices[k] - p).length()))
+            .filter(|&(_, distance)| distance <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((k, _)) = nearest {
+            return k;
+        }
+        vertices.push(p);
+        index.entry(cell).or_default().push(vertices.len() - 1);
+        vertices.len() - 1
+    };
+    let polygons: Vec<Vec<usize>> = polygons
+        .iter()
+        .map(|points| points.iter().map(|&p| vertex(p)).collect())
+        .collect();
+    // an edge can pass through a corner of its neighbours, the vertices
+    // sorted by x find the corners on an edge
+    let mut order: Vec<usize> = (0..vertices.len()).collect();
+    order.sort_by(|&a, &b| vertices[a].x.total_cmp(&vertices[b].x));
+    let xs: Vec<f32> = order.iter().map(|&k| vertices[k].x).collect();
+    let mut edges = Vec::<(usize, usize)>::new();
+    let mut seen = HashSet::<(usize, usize)>::new();
+    for ids in &polygons {
+        for k in 0..ids.len() {
+            let (a, b) = (ids[k], ids[(k + 1) % ids.len()]);
+            if a == b {
+                continue;
+            }
+            let (p, q) = (vertices[a], vertices[b]);
+            let length = (q - p).length();
+            let lo = xs.partition_point(|&x| x < p.x.min(q.x) - tolerance);
+            let hi = xs.partition_point(|&x| x <= p.x.max(q.x) + tolerance);
+            let mut between: Vec<(f32, usize)> = order[lo..hi]
+                .iter()
+                .filter(|&&v| v != a && v != b)
+                .filter_map(|&v| {
+                    let t = (vertices[v] - p).dot(q - p) / (length * length);
+                    let distance = (p.lerp(q, t) - vertices[v]).length();
+                    (t > 0.0 && t < 1.0 && distance <= tolerance).then_some((t, v))
+                })
+                .collect();
+            between.sort_by(|x, y| x.0.total_cmp(&y.0));
+            let mut last = a;
+            for v in between.into_iter().map(|(_, v)| v).chain([b]) {
+                if seen.insert((last.min(v), last.max(v))) {
+                    edges.push((last, v));
+                }
+                last = v;
+            }
+        }
+    }
+    let mut neighbours = vec![Vec::<usize>::new(); vertices.len()];
+    for (e, &(a, b)) in edges.iter().enumerate() {
+        neighbours[a].push(e);
+        neighbours[b].push(e);
+    }
+    let mut used = vec![false; edges.len()];
+    let unused = |used: &[bool], v: usize| neighbours[v].iter().filter(|&&e| !used[e]).count();
+    let mut res = Vec::new();
+    for e in 0..edges.len() {
+        if used[e] {
+            continue;
+        }
+        // start at an end of the chain when there is one
+        let (a, b) = edges[e];
+        let start = if unused(&used, a) % 2 == 0 && unused(&used, b) % 2 == 1 {
+            b
+        } else {
+            a
+        };
+        let mut chain = vec![start];
+        let mut current = start;
+        while let Some(&next) = neighbours[current].iter().find(|&&e| !used[e]) {
+            used[next] = true;
+            let (a, b) = edges[next];
+            current = if a == current { b } else { a };
+            chain.push(current);
+        }
+        res.push(chain.iter().map(|&k| vertices[k]).collect());
+    }
+    res
+}
+
+/// The strokes in the order a pen starting at `start` draws them, always
+/// the stroke that starts or ends nearest to the pen next. Strokes are
+/// reversed and closed strokes start at their nearest corner to shorten the
+/// way with the pen up.
+pub fn order_strokes(strokes: Vec<Vec<Point>>, start: Point) -> Vec<Vec<Point>> {
+    let mut left: Vec<Vec<Point>> = strokes.into_iter().filter(|s| !s.is_empty()).collect();
+    let mut res = Vec::with_capacity(left.len());
+    let mut pen = start;
+    while !left.is_empty() {
+        // index of the stroke and of the point to start at
+        let (k, from, _) = left
+            .iter()
+            .enumerate()
+            .flat_map(|(k, stroke)| {
+                let last = stroke.len() - 1;
+                let candidates = if stroke.len() > 2 && stroke[0] == stroke[last] {
+                    (0..last).collect()
+                } else {
+                    vec![0, last]
+                };
+                candidates
+                    .into_iter()
+                    .map(move |i| (k, i, (stroke[i] - pen).square_length()))
+            })
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+            .unwrap();
+        let mut stroke = left.swap_remove(k);
+        let last = stroke.len() - 1;
+        if stroke.len() > 2 && stroke[0] == stroke[last] {
+            stroke.pop();
+            stroke.rotate_left(from);
+            stroke.push(stroke[0]);
+        } else if from == last {
+            stroke.reverse();
+        }
+        pen = stroke[stroke.len() - 1];
+        res.push(stroke);
+    }
+    res
+}
+
+/// Distance the pen travels up between the strokes when it starts at `start`
+pub fn travel(strokes: &[Vec<Point>], start: Point) -> f32 {
+    let mut pen = start;
+    let mut res = 0.0;
+    for stroke in strokes.iter().filter(|s| !s.is_empty()) {
+        res += (stroke[0] - pen).length();
+        pen = stroke[stroke.len() - 1];
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_shared_edges() {
+        let square = |x: f32| {
+            vec![
+                Point::new(x, 0.0),
+                Point::new(x, 1.0),
+                Point::new(x + 1.0, 1.0),
+                Point::new(x + 1.0, 0.0),
+            ]
+        };
+        let count = |polygons: &[Vec<Point>]| -> usize {
+            strokes(polygons, 1e-5)
+                .iter()
+                .map(|points| points.len() - 1)
+                .sum()
+        };
+        assert_eq!(count(&[square(0.0), square(1.0)]), 7);
+        // a corner in the middle of the edge of a neighbour
+        let long = vec![
+            Point::new(0.0, 1.0),
+            Point::new(0.0, 2.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 1.0),
+        ];
+        assert_eq!(count(&[square(0.0), square(1.0), long]), 10);
+    }
+
+    #[test]
+    fn test_vertices_in_one_cell() {
+        // both corners round to the same cell but are further apart than
+        // the tolerance
+        let triangle = |p: Point, s: f32| vec![p, Point::new(s, 0.0), Point::new(s, s)];
+        let a = triangle(Point::new(0.04, 0.04), 1.0);
+        let b = triangle(Point::new(-0.04, -0.04), -1.0);
+        let count: usize = strokes(&[a.clone(), b, a], 0.1)
+            .iter()
+            .map(|points| points.len() - 1)
+            .sum();
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn test_order() {
+        let line = |x: f32| vec![Point::new(x, 0.0), Point::new(x, 1.0)];
+        let strokes = vec![line(3.0), line(1.0), line(2.0), line(0.0)];
+        let start = Point::origin();
+        let ordered = order_strokes(strokes.clone(), start);
+        // up and down the lines from left to right
+        assert_eq!(
+            ordered,
+            vec![
+                line(0.0),
+                vec![Point::new(1.0, 1.0), Point::new(1.0, 0.0)],
+                line(2.0),
+                vec![Point::new(3.0, 1.0), Point::new(3.0, 0.0)],
+            ]
+        );
+        assert_eq!(travel(&ordered, start), 3.0);
+        assert!(travel(&strokes, start) > 3.0);
+    }
+
+    #[test]
+    fn test_closed_stroke_starts_near_the_pen() {
+        let square = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+        let ordered = order_strokes(vec![square], Point::new(2.0, 2.0));
+        assert_eq!(ordered[0][0], Point::new(1.0, 1.0));
+        assert_eq!(ordered[0][4], Point::new(1.0, 1.0));
+        assert_eq!(ordered[0].len(), 5);
+    }
+}
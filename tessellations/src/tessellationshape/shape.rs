@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TessellationShape {
     S,
     U,
@@ -8,6 +8,7 @@ pub enum TessellationShape {
     J,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for TessellationShape {
     fn default() -> Self {
         TessellationShape::S
@@ -0,0 +1,58 @@
+use palette::Srgb;
+use serde::{Deserialize, Serialize};
+
+/// Color with 8 bit red, green, blue and alpha channels, not premultiplied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0x0, 0x0, 0x0);
+    pub const WHITE: Color = Color::rgb(0xff, 0xff, 0xff);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 0xff }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Returns the color as `#rrggbb` for svg attributes
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Returns the alpha channel between 0.0 and 1.0
+    pub fn opacity(&self) -> f32 {
+        self.a as f32 / 255.0
+    }
+}
+
+impl From<Srgb<f32>> for Color {
+    fn from(c: Srgb<f32>) -> Self {
+        let c: Srgb<u8> = c.into_format();
+        Color::rgb(c.red, c.green, c.blue)
+    }
+}
+
+impl From<Color> for Srgb<f32> {
+    fn from(c: Color) -> Self {
+        Srgb::new(c.r, c.g, c.b).into_format()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_to_hex() {
+        assert_eq!(Color::rgb(0xf6, 0x88, 0x0b).to_hex(), "#f6880b");
+    }
+}
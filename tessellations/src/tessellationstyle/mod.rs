@@ -0,0 +1,7 @@
+mod color;
+mod palette;
+mod style;
+
+pub use color::Color;
+pub use palette::generate_palette;
+pub use style::{LineCap, LineJoin, LineUnit, Stroke, Style, DEFAULT_PALETTE};
@@ -0,0 +1,37 @@
+use crate::tessellationstyle::Color;
+use palette::{FromColor, Hsl, Srgb};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Three analogous colors and a complementary color around a random base
+/// hue, the same seed gives the same palette
+pub fn generate_palette(seed: u64) -> Vec<Color> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // Generate a random base hue
+    let base_hue = rng.gen_range(0..360) as f32;
+    let hue_shift = 30.0;
+    // Calculate three analogous hues and a complementary hue
+    let hues = [
+        (base_hue + hue_shift) % 360.0,
+        (base_hue + 2.0 * hue_shift) % 360.0,
+        (base_hue + 3.0 * hue_shift) % 360.0,
+        (base_hue + 180.0) % 360.0,
+    ];
+    hues.iter()
+        .map(|&hue| Srgb::from_color(Hsl::new(hue, 0.7, 0.6)).into())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_seeded_palette() {
+        assert_eq!(generate_palette(7), generate_palette(7));
+        assert_ne!(generate_palette(7), generate_palette(8));
+        assert_eq!(generate_palette(7).len(), 4);
+    }
+}
@@ -0,0 +1,179 @@
+use crate::tessellationline::Transform;
+use crate::tessellationstyle::Color;
+use serde::{Deserialize, Serialize};
+
+/// Fill colors of the tiles, a tile uses its color index modulo the length
+pub const DEFAULT_PALETTE: [Color; 4] = [
+    Color::rgb(0xf6, 0x88, 0xbb),
+    Color::rgb(0xe8, 0xf9, 0xe9),
+    Color::rgb(0xba, 0xfa, 0xa1),
+    Color::rgb(0x9d, 0xe3, 0xd0),
+];
+
+/// Shape of the corners of a line or an offset polygon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+/// Shape of the ends of an open line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+/// Unit of the width of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineUnit {
+    /// pixels of the image, the line keeps its width when zooming
+    #[default]
+    Pixel,
+    /// tile units, the line scales with the tiles
+    World,
+}
+
+/// Color, width, corners and ends of a line
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stroke {
+    pub color: Color,
+    pub width: f32,
+    pub unit: LineUnit,
+    pub join: LineJoin,
+    pub cap: LineCap,
+}
+
+impl Stroke {
+    /// Thin black line between tiles
+    pub const HAIRLINE: Stroke = Stroke::new(Color::BLACK, 1.0);
+
+    /// Line `width` pixels wide with mitered corners
+    pub const fn new(color: Color, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            unit: LineUnit::Pixel,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+        }
+    }
+
+    /// Line `width` tile units wide with mitered corners
+    pub const fn world(color: Color, width: f32) -> Self {
+        Self {
+            color,
+            width,
+            unit: LineUnit::World,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+        }
+    }
+
+    /// Returns the width in pixels for a line drawn with `m` from tile units
+    /// to pixels
+    pub fn pixel_width(&self, m: &Transform) -> f32 {
+        match self.unit {
+            LineUnit::Pixel => self.width,
+            LineUnit::World => self.width * m.determinant().abs().sqrt(),
+        }
+    }
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self::HAIRLINE
+    }
+}
+
+/// Colors and lines used by every backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Style {
+    /// fill colors of the tiles
+    pub palette: Vec<Color>,
+    /// outline of every tile
+    pub stroke: Option<Stroke>,
+    pub background: Color,
+    /// outline of the figure drawn on top of the tiles
+    pub overlay: Stroke,
+    /// width in tile units of the gap between the tiles, every tile is
+    /// shrunk by half the width so the background shows
+    pub grout: f32,
+}
+
+impl Style {
+    /// Returns the fill color for a color index
+    pub fn color(&self, index: usize) -> Color {
+        if self.palette.is_empty() {
+            return Color::WHITE;
+        }
+        self.palette[index % self.palette.len()]
+    }
+
+    /// The style with the widths in pixels of the lines multiplied by `scale`
+    pub fn scaled(&self, scale: f32) -> Self {
+        let mut style = self.clone();
+        for stroke in style.stroke.iter_mut().chain([&mut style.overlay]) {
+            if stroke.unit == LineUnit::Pixel {
+                stroke.width *= scale;
+            }
+        }
+        style
+    }
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            palette: DEFAULT_PALETTE.to_vec(),
+            stroke: None,
+            background: Color::WHITE,
+            overlay: Stroke::new(Color::rgb(0xff, 0xff, 0x0), 3.0),
+            grout: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let style: Style = serde_json::from_str(
+            r#"{"palette":[{"r":1,"g":2,"b":3,"a":255}],"stroke":{"color":{"r":0,"g":0,"b":0,"a":255},"width":2.0}}"#,
+        )
+        .unwrap();
+        assert_eq!(style.color(5), Color::rgb(1, 2, 3));
+        assert_eq!(style.stroke, Some(Stroke::new(Color::BLACK, 2.0)));
+        assert_eq!(style.background, Color::WHITE);
+        assert_eq!(style.overlay, Style::default().overlay);
+    }
+
+    #[test]
+    fn test_world_width() {
+        let stroke = Stroke::world(Color::BLACK, 0.1);
+        assert_eq!(stroke.pixel_width(&Transform::scale(50.0, 50.0)), 5.0);
+        let style = Style {
+            stroke: Some(stroke),
+            ..Style::default()
+        }
+        .scaled(2.0);
+        assert_eq!(style.stroke.unwrap().width, 0.1);
+        assert_eq!(style.overlay.width, 6.0);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let style = Style::default();
+        let json = serde_json::to_string(&style).unwrap();
+        assert_eq!(serde_json::from_str::<Style>(&json).unwrap(), style);
+    }
+}
@@ -0,0 +1,6 @@
+mod penrose;
+mod rule;
+mod tiling;
+
+pub use rule::{triangle_transform, PlacedTile, SubstitutionRule};
+pub use tiling::{AperiodicPrototile, MatchingEdge, SubstitutionTiling};
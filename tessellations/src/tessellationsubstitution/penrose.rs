@@ -0,0 +1,140 @@
+use crate::tessellationline::Point;
+use crate::tessellationsubstitution::{
+    triangle_transform, AperiodicPrototile, MatchingEdge, PlacedTile, SubstitutionRule,
+    SubstitutionTiling,
+};
+use euclid::Angle;
+
+const PHI: f32 = 1.618_034;
+
+/// Point at distance 1 from the origin in the direction `degrees`
+fn dir(degrees: f32) -> Point {
+    let a = Angle::degrees(degrees);
+    Point::new(a.radians.cos(), a.radians.sin())
+}
+
+fn child(prototile: usize, from: [Point; 3], to: [Point; 3]) -> PlacedTile {
+    PlacedTile::new(prototile, triangle_transform(from, to).unwrap())
+}
+
+impl SubstitutionTiling {
+    /// Penrose kites and darts (P2) made from half kites and half darts,
+    /// starting with a sun of five kites.
+    ///
+    /// Edge type 0 is the long edge and 1 the short edge.
+    pub fn penrose_kite_dart() -> Self {
+        // side, apex and tail of the half kite, the kite is split along the
+        // axis from apex to tail
+        let half_kite = [dir(-36.0), Point::new(0.0, 0.0), Point::new(1.0, 0.0)];
+        // side, reflex vertex and apex of the half dart
+        let half_dart = [dir(-36.0), Point::new(1.0 / PHI, 0.0), Point::new(0.0, 0.0)];
+
+        let mut rule = SubstitutionRule::new(PHI, 2);
+        let [a, b, c] = half_kite;
+        let q = a + (b - a) / PHI;
+        let r = b + (c - b) / PHI;
+        rule.add_child(0, child(1, half_dart, [r, q, b]));
+        rule.add_child(0, child(0, half_kite, [q, a, r]));
+        rule.add_child(0, child(0, half_kite, [c, a, r]));
+        let [a, b, c] = half_dart;
+        let p = c + (a - c) / PHI;
+        rule.add_child(1, child(1, half_dart, [b, p, a]));
+        rule.add_child(1, child(0, half_kite, [p, c, b]));
+
+        // the arrows point from the apex to the sides and from the sides to
+        // the tail of the kite, for the dart they point to the apex and away
+        // from the reflex vertex
+        let kite = AperiodicPrototile::new(
+            vec![half_kite[1], half_kite[0], half_kite[2], dir(36.0)],
+            vec![
+                MatchingEdge::new(0, false),
+                MatchingEdge::new(1, false),
+                MatchingEdge::new(1, true),
+                MatchingEdge::new(0, true),
+            ],
+        );
+        let dart = AperiodicPrototile::new(
+            vec![half_dart[2], half_dart[0], half_dart[1], dir(36.0)],
+            vec![
+                MatchingEdge::new(0, true),
+                MatchingEdge::new(1, true),
+                MatchingEdge::new(1, false),
+                MatchingEdge::new(0, false),
+            ],
+        );
+
+        let mut tiling = SubstitutionTiling::new(rule, vec![kite, dart]);
+        for k in 0..5 {
+            let angle = 72.0 * k as f32;
+            for side in [angle + 36.0, angle - 36.0] {
+                tiling.add_seed(child(
+                    0,
+                    half_kite,
+                    [dir(side), Point::new(0.0, 0.0), dir(angle)],
+                ));
+            }
+        }
+        tiling
+    }
+
+    /// Penrose thin and thick rhombs (P3) made from half rhombs, starting
+    /// with a star of five thick rhombs.
+    ///
+    /// Edge type 0 and 1 are the edges with a single and a double arrow.
+    pub fn penrose_rhombs() -> Self {
+        // the half rhombs are split along the diagonal from b to c
+        let half_thin = [Point::new(0.0, 0.0), Point::new(1.0, 0.0), dir(36.0)];
+        let half_thick = [Point::new(0.0, 0.0), Point::new(1.0, 0.0), dir(108.0)];
+
+        let mut rule = SubstitutionRule::new(PHI, 2);
+        let [a, b, c] = half_thin;
+        let p = a + (b - a) / PHI;
+        rule.add_child(0, child(0, half_thin, [c, p, b]));
+        rule.add_child(0, child(1, half_thick, [p, c, a]));
+        let [a, b, c] = half_thick;
+        let q = b + (a - b) / PHI;
+        let r = b + (c - b) / PHI;
+        rule.add_child(1, child(1, half_thick, [r, c, a]));
+        rule.add_child(1, child(1, half_thick, [q, r, b]));
+        rule.add_child(1, child(0, half_thin, [r, q, a]));
+
+        let thin = AperiodicPrototile::new(
+            vec![
+                half_thin[0],
+                half_thin[1],
+                half_thin[1] + half_thin[2].to_vector(),
+                half_thin[2],
+            ],
+            vec![
+                MatchingEdge::new(1, true),
+                MatchingEdge::new(1, false),
+                MatchingEdge::new(0, true),
+                MatchingEdge::new(0, false),
+            ],
+        );
+        let thick = AperiodicPrototile::new(
+            vec![
+                half_thick[0],
+                half_thick[1],
+                half_thick[1] + half_thick[2].to_vector(),
+                half_thick[2],
+            ],
+            vec![
+                MatchingEdge::new(1, false),
+                MatchingEdge::new(1, true),
+                MatchingEdge::new(0, true),
+                MatchingEdge::new(0, false),
+            ],
+        );
+
+        let mut tiling = SubstitutionTiling::new(rule, vec![thin, thick]);
+        for k in 0..5 {
+            let angle = 72.0 * k as f32;
+            let c = (dir(angle).to_vector() * PHI).to_point();
+            for side in [angle + 36.0, angle - 36.0] {
+                tiling.add_seed(child(1, half_thick, [dir(side), Point::new(0.0, 0.0), c]));
+            }
+        }
+        tiling
+    }
+}
@@ -0,0 +1,122 @@
+use crate::tessellationline::{Point, Transform};
+
+/// Copy of a prototile placed with a transform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlacedTile {
+    pub prototile: usize,
+    pub transform: Transform,
+}
+
+impl PlacedTile {
+    pub fn new(prototile: usize, transform: Transform) -> Self {
+        Self {
+            prototile,
+            transform,
+        }
+    }
+}
+
+/// Inflation rule of a substitution tiling.
+///
+/// Every prototile is replaced by its children, the children are placed in
+/// the coordinates of the prototile and are `factor` times smaller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubstitutionRule {
+    pub factor: f32,
+    pub children: Vec<Vec<PlacedTile>>,
+}
+
+impl SubstitutionRule {
+    /// A rule without children for `count` prototiles
+    pub fn new(factor: f32, count: usize) -> Self {
+        Self {
+            factor,
+            children: vec![Vec::<PlacedTile>::new(); count],
+        }
+    }
+
+    /// Add a child to the prototile `parent`
+    pub fn add_child(&mut self, parent: usize, child: PlacedTile) {
+        self.children[parent].push(child);
+    }
+
+    /// Replace every tile by its children
+    pub fn substitute(&self, tiles: &[PlacedTile]) -> Vec<PlacedTile> {
+        tiles
+            .iter()
+            .flat_map(|tile| {
+                self.children[tile.prototile].iter().map(|child| {
+                    PlacedTile::new(child.prototile, child.transform.then(&tile.transform))
+                })
+            })
+            .collect()
+    }
+
+    /// Substitute the `seed` tiles `depth` times, the result is scaled up so
+    /// the tiles have the size of the prototiles and the patch grows.
+    pub fn generate(&self, seed: &[PlacedTile], depth: usize) -> Vec<PlacedTile> {
+        let mut tiles = seed.to_vec();
+        for _ in 0..depth {
+            tiles = self.substitute(&tiles);
+        }
+        let s = self.factor.powi(depth as i32);
+        tiles
+            .into_iter()
+            .map(|t| PlacedTile::new(t.prototile, t.transform.then_scale(s, s)))
+            .collect()
+    }
+}
+
+/// Returns the affine transform that maps the triangle `from` on the
+/// triangle `to`, none if `from` has no area.
+pub fn triangle_transform(from: [Point; 3], to: [Point; 3]) -> Option<Transform> {
+    let frame = |t: [Point; 3]| {
+        let e1 = t[1] - t[0];
+        let e2 = t[2] - t[0];
+        Transform::new(e1.x, e1.y, e2.x, e2.y, t[0].x, t[0].y)
+    };
+    Some(frame(from).inverse()?.then(&frame(to)))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_triangle_transform() {
+        let from = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let to = [
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 3.0),
+            Point::new(-1.0, 1.0),
+        ];
+        let m = triangle_transform(from, to).unwrap();
+        for i in 0..3 {
+            assert!((m.transform_point(from[i]) - to[i]).length() < 1e-6);
+        }
+        assert!(triangle_transform([from[0], from[1], from[1]], to).is_none());
+    }
+
+    #[test]
+    fn test_generate_keeps_size() {
+        // a unit square split in four squares
+        let mut rule = SubstitutionRule::new(2.0, 1);
+        for (x, y) in [(0.0, 0.0), (0.5, 0.0), (0.0, 0.5), (0.5, 0.5)] {
+            rule.add_child(
+                0,
+                PlacedTile::new(
+                    0,
+                    Transform::scale(0.5, 0.5).then_translate(euclid::vec2(x, y)),
+                ),
+            );
+        }
+        let tiles = rule.generate(&[PlacedTile::new(0, Transform::identity())], 2);
+        assert_eq!(tiles.len(), 16);
+        assert_eq!(tiles[15].transform, Transform::translation(3.0, 3.0));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::tessellationline::{Point, Transform};
+use crate::tessellationsubstitution::{PlacedTile, SubstitutionRule};
+use euclid::vec2;
+use std::collections::HashMap;
+
+/// Edge of an aperiodic prototile from a vertex to the next vertex.
+///
+/// The matching rule gives every edge a type and an arrow, `reversed` is set
+/// when the arrow points from the next vertex back to the vertex. Tiles only
+/// meet along edges of the same type with the arrows in the same direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchingEdge {
+    pub edge_type: usize,
+    pub reversed: bool,
+}
+
+impl MatchingEdge {
+    pub fn new(edge_type: usize, reversed: bool) -> Self {
+        Self {
+            edge_type,
+            reversed,
+        }
+    }
+}
+
+/// Polygon with a matching edge for every side
+#[derive(Debug, Clone, PartialEq)]
+pub struct AperiodicPrototile {
+    pub vertices: Vec<Point>,
+    pub edges: Vec<MatchingEdge>,
+}
+
+impl AperiodicPrototile {
+    pub fn new(vertices: Vec<Point>, edges: Vec<MatchingEdge>) -> Self {
+        Self { vertices, edges }
+    }
+}
+
+/// Aperiodic tiling made by substitution instead of a lattice.
+///
+/// The rule works on its own prototiles, like the half tiles of the Penrose
+/// tilings. The composition places every rule prototile in a tile of the
+/// output, tiles found from more than one rule prototile are only returned
+/// once.
+///
+/// The edges of the output are deformed with the shape of their edge type,
+/// the shape is the list of points between (0, 0) and (1, 0) in the direction
+/// of the arrow. Because tiles meet with matching edges the deformed tiles
+/// still fit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubstitutionTiling {
+    pub rule: SubstitutionRule,
+    pub seed: Vec<PlacedTile>,
+    pub composition: Vec<PlacedTile>,
+    pub prototiles: Vec<AperiodicPrototile>,
+    pub edges: Vec<Vec<Point>>,
+}
+
+impl SubstitutionTiling {
+    /// A tiling where the rule prototiles are the output tiles
+    pub fn new(rule: SubstitutionRule, prototiles: Vec<AperiodicPrototile>) -> Self {
+        let composition = (0..prototiles.len())
+            .map(|k| PlacedTile::new(k, Transform::identity()))
+            .collect();
+        let edge_count = prototiles
+            .iter()
+            .flat_map(|t| t.edges.iter().map(|e| e.edge_type + 1))
+            .max()
+            .unwrap_or(0);
+        Self {
+            rule,
+            seed: Vec::<PlacedTile>::new(),
+            composition,
+            prototiles,
+            edges: vec![Vec::<Point>::new(); edge_count],
+        }
+    }
+
+    /// Add a tile to start the substitution with
+    pub fn add_seed(&mut self, tile: PlacedTile) {
+        self.seed.push(tile);
+    }
+
+    /// Set the points of the edge type `edge_type` between (0, 0) and (1, 0)
+    pub fn set_edge(&mut self, edge_type: usize, points: Vec<Point>) {
+        self.edges[edge_type] = points;
+    }
+
+    /// Returns the tiles after substituting the seed `depth` times
+    pub fn tiles(&self, depth: usize) -> Vec<PlacedTile> {
+        // the centers of the tiles found so far in cells of half a unit
+        let mut found = HashMap::<(i32, i32), Vec<(usize, Point)>>::new();
+        let cell = |p: Point| ((p.x * 2.0).floor() as i32, (p.y * 2.0).floor() as i32);
+        let mut res = Vec::<PlacedTile>::new();
+        for t in self.rule.generate(&self.seed, depth) {
+            let c = self.composition[t.prototile];
+            let tile = PlacedTile::new(c.prototile, c.transform.then(&t.transform));
+            let center = self.center(&tile);
+            let (cx, cy) = cell(center);
+            let duplicate = (cx - 1..=cx + 1)
+                .flat_map(|x| (cy - 1..=cy + 1).map(move |y| (x, y)))
+                .filter_map(|k| found.get(&k))
+                .flatten()
+                .any(|&(k, p)| k == tile.prototile && (p - center).length() < 1e-3);
+            if !duplicate {
+                found
+                    .entry((cx, cy))
+                    .or_default()
+                    .push((tile.prototile, center));
+                res.push(tile);
+            }
+        }
+        res
+    }
+
+    /// Returns the vertices of the tile
+    pub fn vertices(&self, tile: &PlacedTile) -> Vec<Point> {
+        self.prototiles[tile.prototile]
+            .vertices
+            .iter()
+            .map(|&p| tile.transform.transform_point(p))
+            .collect()
+    }
+
+    /// Returns the outline of the tile with the deformed edges, every edge
+    /// starts with its first vertex and ends with the next vertex.
+    pub fn outline(&self, tile: &PlacedTile) -> Vec<Point> {
+        let vertices = self.vertices(tile);
+        let n = vertices.len();
+        let mut res = Vec::<Point>::new();
+        for (i, edge) in self.prototiles[tile.prototile].edges.iter().enumerate() {
+            let (mut start, mut end) = (vertices[i], vertices[(i + 1) % n]);
+            if edge.reversed {
+                std::mem::swap(&mut start, &mut end);
+            }
+            // the shape is placed in world coordinates so mirrored tiles
+            // get the same edge as their neighbours
+            let d = end - start;
+            let normal = vec2(-d.y, d.x);
+            let mut points = vec![start];
+            points.extend(
+                self.edges[edge.edge_type]
+                    .iter()
+                    .map(|p| start + d * p.x + normal * p.y),
+            );
+            points.push(end);
+            if edge.reversed {
+                points.reverse();
+            }
+            res.extend(points);
+        }
+        res
+    }
+
+    /// Returns the average of the vertices of the tile
+    pub fn center(&self, tile: &PlacedTile) -> Point {
+        let vertices = self.vertices(tile);
+        let sum = vertices
+            .iter()
+            .fold(Point::zero(), |acc, p| acc + p.to_vector());
+        sum / vertices.len().max(1) as f32
+    }
+}
@@ -0,0 +1,3 @@
+mod warp;
+
+pub use warp::Warp;
@@ -0,0 +1,172 @@
+use crate::tessellationhyperbolic::Mobius;
+use crate::tessellationline::{Point, Vector};
+use std::f32::consts::PI;
+
+/// Maximum number of times an edge is halved by [`Warp::warp_polygon`]
+const MAX_DEPTH: u32 = 10;
+
+/// Conformal map of the plane that bends the straight edges of a tiling.
+///
+/// The maps are not affine so the outlines of the tiles are subdivided and
+/// every point is mapped, see [`Warp::warp_polygon`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Warp {
+    /// `exp(c z)` with `c` chosen so `period` becomes one turn and a zoom by
+    /// `scale`, a scale of 1 gives rings around the origin
+    Droste { period: Vector, scale: f32 },
+    /// the complex power `z^n`, with a cut along the negative x axis when
+    /// `n` is not an integer
+    Power(f32),
+    /// a Möbius transform
+    Mobius(Mobius),
+}
+
+impl Warp {
+    /// Log-polar spiral where the lattice vector `period` maps to one turn
+    pub fn droste(period: Vector, scale: f32) -> Self {
+        Warp::Droste { period, scale }
+    }
+
+    /// Map a point
+    pub fn transform_point(&self, p: Point) -> Point {
+        match self {
+            Warp::Droste { period, scale } => {
+                // c = (ln(scale) + 2 pi i) / period
+                let (a, b) = (scale.ln(), 2.0 * PI);
+                let d = period.square_length();
+                let cr = (a * period.x + b * period.y) / d;
+                let ci = (b * period.x - a * period.y) / d;
+                let re = cr * p.x - ci * p.y;
+                let im = cr * p.y + ci * p.x;
+                let r = re.exp();
+                Point::new(r * im.cos(), r * im.sin())
+            }
+            Warp::Power(n) => {
+                let r = p.to_vector().length().powf(*n);
+                let angle = p.y.atan2(p.x) * n;
+                Point::new(r * angle.cos(), r * angle.sin())
+            }
+            Warp::Mobius(m) => m.transform_point(p),
+        }
+    }
+
+    /// Map the closed polygon `points`, every edge is halved until the
+    /// mapped midpoint is within `tolerance` of the mapped chord. Returns an
+    /// empty list if a point maps to infinity.
+    pub fn warp_polygon(&self, points: &[Point], tolerance: f32) -> Vec<Point> {
+        let mut res = Vec::<Point>::new();
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            let wf = self.transform_point(from);
+            res.push(wf);
+            self.subdivide(
+                from,
+                to,
+                wf,
+                self.transform_point(to),
+                tolerance,
+                0,
+                &mut res,
+            );
+        }
+        if res.iter().any(|p| !p.x.is_finite() || !p.y.is_finite()) {
+            res.clear();
+        }
+        res
+    }
+
+    /// Push the mapped points between `from` and `to`, without the end points
+    #[allow(clippy::too_many_arguments)]
+    fn subdivide(
+        &self,
+        from: Point,
+        to: Point,
+        wf: Point,
+        wt: Point,
+        tolerance: f32,
+        depth: u32,
+        res: &mut Vec<Point>,
+    ) {
+        let mid = from.lerp(to, 0.5);
+        let wm = self.transform_point(mid);
+        if depth >= MAX_DEPTH || (wm - wf.lerp(wt, 0.5)).length() <= tolerance {
+            return;
+        }
+        self.subdivide(from, mid, wf, wm, tolerance, depth + 1, res);
+        res.push(wm);
+        self.subdivide(mid, to, wm, wt, tolerance, depth + 1, res);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn close(a: Point, b: Point) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn test_droste_period() {
+        let w = Warp::droste(Vector::new(0.0, 4.0), 2.0);
+        let p = Point::new(0.3, 0.7);
+        let q = w.transform_point(p + Vector::new(0.0, 4.0));
+        // one period further is the same point zoomed by the scale
+        assert!(close(
+            q,
+            (w.transform_point(p).to_vector() * 2.0).to_point()
+        ));
+    }
+
+    #[test]
+    fn test_log_polar_rings() {
+        let w = Warp::droste(Vector::new(0.0, 1.0), 1.0);
+        // x = 0 is the unit circle
+        for y in [0.0, 0.25, 0.6] {
+            let p = w.transform_point(Point::new(0.0, y));
+            assert!((p.to_vector().length() - 1.0).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_power() {
+        let w = Warp::Power(2.0);
+        assert!(close(
+            w.transform_point(Point::new(0.0, 1.0)),
+            Point::new(-1.0, 0.0)
+        ));
+        assert!(close(
+            w.transform_point(Point::new(2.0, 0.0)),
+            Point::new(4.0, 0.0)
+        ));
+    }
+
+    #[test]
+    fn test_subdivision() {
+        let square = [
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+        ];
+        // straight edges stay straight
+        let identity = Warp::Mobius(Mobius::identity());
+        assert_eq!(identity.warp_polygon(&square, 0.01).len(), 4);
+        let bent = Warp::Power(2.0).warp_polygon(&square, 0.01);
+        assert!(bent.len() > 4);
+        for p in &bent {
+            assert!(p.x.is_finite());
+        }
+    }
+
+    #[test]
+    fn test_pole() {
+        let square = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+        ];
+        assert!(Warp::Power(-1.0).warp_polygon(&square, 0.01).is_empty());
+    }
+}
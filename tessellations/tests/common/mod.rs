@@ -0,0 +1,45 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use tessellations::tessellationfigure::TessellationFigure;
+use tessellations::tessellationline::Point;
+
+/// The preset figures
+pub fn figures() -> Vec<TessellationFigure> {
+    vec![
+        TessellationFigure::square(),
+        TessellationFigure::brick(),
+        TessellationFigure::hexagon(),
+        TessellationFigure::triangle(),
+        TessellationFigure::diamond(),
+        TessellationFigure::two_triangles(),
+    ]
+}
+
+/// A square with a notch into it and a tab out of the other side, the end
+/// of the notch leaves a waist 0.1 wide
+pub fn notched_square() -> TessellationFigure {
+    let mut notched = TessellationFigure::square();
+    notched.lines[0].insert(1, Point::new(0.0, 0.45));
+    notched.lines[0].insert(2, Point::new(0.9, 0.45));
+    notched.lines[0].insert(3, Point::new(0.9, 0.55));
+    notched.lines[0].insert(4, Point::new(0.0, 0.55));
+    notched
+}
+
+/// A square with a narrow spike into it and out of the other side
+pub fn spiky_square() -> TessellationFigure {
+    let mut spiky = TessellationFigure::square();
+    spiky.lines[0].insert(1, Point::new(0.0, 0.48));
+    spiky.lines[0].insert(2, Point::new(0.9, 0.5));
+    spiky.lines[0].insert(3, Point::new(0.0, 0.52));
+    spiky
+}
+
+/// Length of the outline of the closed polygon
+pub fn perimeter(points: &[Point]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| (points[(i + 1) % n] - points[i]).length())
+        .sum()
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use euclid::Angle;
+    use std::collections::HashMap;
+    use tessellations::tessellationanalysis::{analyze, AnalysisError};
+    use tessellations::tessellationfigure::TessellationFigure;
+    use tessellations::tessellationline::{Point, Transform};
+
+    fn round(p: Point) -> (i32, i32) {
+        ((p.x * 1000.0).round() as i32, (p.y * 1000.0).round() as i32)
+    }
+
+    /// The corners of the outline rounded, without repeated points
+    fn outline(f: &TessellationFigure, m: &Transform) -> Vec<(i32, i32)> {
+        let mut res = Vec::<(i32, i32)>::new();
+        for p in f.points() {
+            let p = round(m.transform_point(p));
+            if res.last() != Some(&p) {
+                res.push(p);
+            }
+        }
+        while res.len() > 1 && res.first() == res.last() {
+            res.pop();
+        }
+        res
+    }
+
+    /// Places the copies of the tile around the origin like the plane does
+    /// and checks that every edge of the tile is used once in both directions
+    fn assert_tiles(f: &TessellationFigure) {
+        let tile = outline(f, &Transform::identity());
+        let mut edges = HashMap::<((i32, i32), (i32, i32)), usize>::new();
+        for i in -3..=3 {
+            for j in -3..=3 {
+                let x = i as f32 * f.gridincx + j as f32 * f.shiftx;
+                let y = j as f32 * f.gridincy;
+                for k in 0..f.rotdiv {
+                    let m = Transform::rotation(Angle::degrees(360.0 * k as f32 / f.rotdiv as f32))
+                        .then_translate(euclid::vec2(x, y));
+                    let points = outline(f, &m);
+                    for (n, &p) in points.iter().enumerate() {
+                        *edges
+                            .entry((p, points[(n + 1) % points.len()]))
+                            .or_default() += 1;
+                    }
+                }
+            }
+        }
+        for (n, &p) in tile.iter().enumerate() {
+            let q = tile[(n + 1) % tile.len()];
+            assert_eq!(edges.get(&(p, q)), Some(&1), "edge {:?} {:?}", p, q);
+            assert_eq!(
+                edges.get(&(q, p)),
+                Some(&1),
+                "no neighbour at {:?} {:?}",
+                p,
+                q
+            );
+        }
+    }
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Point> {
+        points.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_square() {
+        let a = analyze(&polygon(&[(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)])).unwrap();
+        assert_eq!(a.heesch_type, "TTTT");
+        assert_eq!(a.figure.rotdiv, 1);
+        assert!((a.figure.gridincx - 2.0).abs() < 1e-4);
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_hexagon() {
+        let a = analyze(&TessellationFigure::hexagon().points()).unwrap();
+        assert_eq!(a.heesch_type, "TTTTTT");
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_any_triangle() {
+        let a = analyze(&polygon(&[(0.0, 0.0), (3.0, 0.2), (1.0, 2.0)])).unwrap();
+        assert_eq!(a.heesch_type, "TCCTCC");
+        assert_eq!(a.figure.rotdiv, 2);
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_any_quadrilateral() {
+        let a = analyze(&polygon(&[(0.0, 0.0), (3.0, 0.2), (2.5, 2.0), (0.4, 1.5)])).unwrap();
+        assert_eq!(a.figure.rotdiv, 2);
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_deformed_square90() {
+        let mut f = TessellationFigure::square90();
+        f.lines[0].insert(1, Point::new(0.3, 0.4));
+        f.lines[1].insert(1, Point::new(0.4, 1.2));
+        let a = analyze(&f.points()).unwrap();
+        assert_eq!(a.heesch_type, "C4C4C4C4");
+        assert_eq!(a.figure.rotdiv, 4);
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_deformed_triangle() {
+        let mut f = TessellationFigure::triangle();
+        f.lines[0].insert(1, Point::new(-0.1, 0.4));
+        let a = analyze(&f.points()).unwrap();
+        assert_eq!(a.heesch_type, "CC6C6");
+        assert_eq!(a.figure.rotdiv, 6);
+        assert_tiles(&a.figure);
+    }
+
+    #[test]
+    fn test_pentagon_does_not_tile() {
+        let p = (0..5)
+            .map(|i| {
+                let a = i as f32 * std::f32::consts::TAU / 5.0;
+                Point::new(a.cos(), a.sin())
+            })
+            .collect::<Vec<Point>>();
+        assert_eq!(analyze(&p).unwrap_err(), AnalysisError::NoHeeschType);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        assert_eq!(
+            analyze(&polygon(&[(0.0, 0.0), (1.0, 0.0), (0.0, 0.0)])).unwrap_err(),
+            AnalysisError::TooFewPoints
+        );
+        assert_eq!(
+            analyze(&polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)])).unwrap_err(),
+            AnalysisError::ZeroArea
+        );
+    }
+}
@@ -0,0 +1,118 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::{signed_area, ClipShape};
+    use tessellations::tessellationstyle::Color;
+
+    fn shapes() -> Vec<ClipShape> {
+        let center = Point::new(2.8, 2.9);
+        let star = (0..10)
+            .map(|k| {
+                let a = std::f32::consts::PI * k as f32 / 5.0;
+                let r = if k % 2 == 0 { 2.5 } else { 1.2 };
+                center + euclid::vec2(a.cos(), a.sin()) * r
+            })
+            .collect();
+        vec![
+            ClipShape::Circle {
+                center,
+                radius: 2.5,
+            },
+            ClipShape::RegularPolygon {
+                center,
+                radius: 2.5,
+                sides: 6,
+                rotation: 0.3,
+            },
+            ClipShape::Path(star),
+        ]
+    }
+
+    #[test]
+    fn test_parts_cover_the_shape() {
+        let options = RenderOptions::default();
+        let tolerance = 0.25 / options.view.determinant().abs().sqrt();
+        for f in common::figures() {
+            for shape in shapes() {
+                let scene = Scene::clipped_plane(&TessellationPlane {}, &f, &shape, &options);
+                // the tiles do not overlap so the parts add up to the shape
+                let area: f32 = scene
+                    .tiles
+                    .iter()
+                    .map(|t| signed_area(&scene.outlines[t.outline]).abs())
+                    .sum();
+                let expected = signed_area(&shape.outline(tolerance)).abs();
+                assert!(
+                    (area - expected).abs() < 1e-3 * expected,
+                    "{:?} {} {}",
+                    shape,
+                    area,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_circle() {
+        let mut options = RenderOptions::default();
+        options.style.background = Color::rgb(0xff, 0x0, 0x0);
+        let shape = ClipShape::Circle {
+            center: Point::new(2.8, 2.9),
+            radius: 2.5,
+        };
+        let image = Backend
+            .render_clipped_plane(
+                &TessellationPlane {},
+                &TessellationFigure::hexagon(),
+                &shape,
+                &options,
+            )
+            .unwrap();
+        let data = image.get_data();
+        let pixel = |x: f32, y: f32| data[(y * 70.0) as usize * 400 + (x * 70.0) as usize];
+        assert_eq!(pixel(0.1, 0.1), 0xffff0000);
+        assert_eq!(pixel(5.5, 5.5), 0xffff0000);
+        for (x, y) in [(2.8, 2.9), (0.5, 2.9), (2.8, 5.2), (4.3, 1.3)] {
+            assert_ne!(pixel(x, y), 0xffff0000);
+        }
+    }
+
+    #[test]
+    fn test_compose_polygon() {
+        let options = RenderOptions::default();
+        let shape = &shapes()[1];
+        let f = TessellationFigure::square();
+        let scene = Scene::clipped_plane(&TessellationPlane {}, &f, shape, &options);
+        let svg = SVGBackend
+            .compose_clipped_plane(&TessellationPlane {}, &f, shape, &options)
+            .unwrap()
+            .get_data();
+        assert_eq!(svg.matches("<use").count(), scene.tiles.len());
+        // one path for the square and one for every part of a cut tile
+        assert!(scene.outlines.len() > 1);
+        assert_eq!(svg.matches("<path").count(), scene.outlines.len());
+        // the tiles far from the shape are gone
+        let plane = Scene::plane_with_options(&TessellationPlane {}, &f, &options);
+        assert!(scene.tiles.len() < plane.tiles.len());
+    }
+
+    #[test]
+    fn test_lyon_parts() {
+        let options = RenderOptions::default();
+        let shape = &shapes()[0];
+        let scene = Scene::clipped_plane(
+            &TessellationPlane {},
+            &TessellationFigure::brick(),
+            shape,
+            &options,
+        );
+        let paths = LyonBackend.build_scene(&scene);
+        assert_eq!(paths.len(), scene.tiles.len());
+    }
+}
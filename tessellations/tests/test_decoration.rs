@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::tessellationfigure::{Decoration, DecorationIndexPath, TessellationFigure};
+    use tessellations::tessellationline::Point;
+
+    #[test]
+    fn test_hitdecoration_update() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::eye(f.center(), 0.1));
+        let h = f.hitdecoration(Point::new(0.6, 0.5), 0.05);
+        assert_eq!(
+            h,
+            Some(DecorationIndexPath {
+                decoration_index: 0,
+                point_index: 2
+            })
+        );
+        f.update_decoration(h.unwrap(), Point::new(0.7, 0.5));
+        assert_eq!(f.decorations[0].points[2], Point::new(0.7, 0.5));
+    }
+
+    #[test]
+    fn test_load_json_without_decorations() {
+        let j = "{\"lines\":[],\"gridincx\":1.0,\"gridincy\":1.0,\"shiftx\":0.0,\"shifty\":1.0,\"rotdiv\":1,\"is_reversed\":false,\"shape\":\"S\"}";
+        let f = serde_json::from_str::<TessellationFigure>(j).expect("parse error");
+        assert!(f.decorations.is_empty());
+    }
+
+    #[test]
+    fn test_decoration_save_load_json() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::eye(Point::new(0.5, 0.5), 0.1));
+        let j = serde_json::to_string(&f).expect("json error");
+        let f2 = serde_json::from_str::<TessellationFigure>(&j).expect("parse error");
+        assert_eq!(f2.decorations, f.decorations);
+    }
+}
@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{Decoration, TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::ClipShape;
+
+    /// Kind, layer and points of every entity of a drawing
+    fn entities(dxf: &DXF) -> Vec<(String, String, Vec<Point>, bool)> {
+        let data = dxf.get_data();
+        let lines: Vec<&str> = data.lines().collect();
+        let start = lines.iter().position(|l| *l == "ENTITIES").unwrap() + 1;
+        let mut res = Vec::<(String, String, Vec<Point>, bool)>::new();
+        for pair in lines[start..].chunks(2) {
+            match (pair[0], pair[1]) {
+                ("0", "ENDSEC") => break,
+                ("0", kind) => res.push((kind.to_string(), String::new(), Vec::new(), false)),
+                ("8", layer) => res.last_mut().unwrap().1 = layer.to_string(),
+                ("70", flags) if res.last().unwrap().0 == "LWPOLYLINE" => {
+                    res.last_mut().unwrap().3 = flags == "1"
+                }
+                ("10", x) => res
+                    .last_mut()
+                    .unwrap()
+                    .2
+                    .push(Point::new(x.parse().unwrap(), 0.0)),
+                ("20", y) => res.last_mut().unwrap().2.last_mut().unwrap().y = y.parse().unwrap(),
+                _ => {}
+            }
+        }
+        res
+    }
+
+    fn length(points: &[Point], closed: bool) -> f32 {
+        let mut length: f32 = points.windows(2).map(|l| (l[1] - l[0]).length()).sum();
+        if closed {
+            length += (points[0] - points[points.len() - 1]).length();
+        }
+        length
+    }
+
+    /// Length of the lines on the cut layer
+    fn cut_length(dxf: &DXF) -> f32 {
+        entities(dxf)
+            .iter()
+            .filter(|e| e.1 == CUT_LAYER)
+            .map(|e| length(&e.2, e.3))
+            .sum()
+    }
+
+    #[test]
+    fn test_figure_dxf() {
+        let f = TessellationFigure::square();
+        let dxf = DXFBackend.draft_figure(&f, &DXFOptions::new(20.0));
+        let data = dxf.get_data();
+        assert!(data.starts_with("0\nSECTION\n2\nHEADER\n"));
+        assert!(data.contains("$INSUNITS\n70\n4\n"));
+        assert!(data.ends_with("0\nEOF\n"));
+        let entities = entities(&dxf);
+        assert_eq!(entities.len(), 1);
+        assert_eq!(entities[0].0, "LWPOLYLINE");
+        assert_eq!(entities[0].1, CUT_LAYER);
+        assert!(entities[0].3);
+        assert_eq!(
+            entities[0].2,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, -20.0),
+                Point::new(20.0, -20.0),
+                Point::new(20.0, 0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decoration_layers() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::eye(f.center(), 0.1));
+        let mut options = DXFOptions::new(10.0);
+        let dxf = DXFBackend.draft_figure(&f, &options);
+        let engraved: Vec<_> = entities(&dxf)
+            .into_iter()
+            .filter(|e| e.1 == ENGRAVE_LAYER)
+            .collect();
+        assert!(!engraved.is_empty());
+        assert!(engraved.iter().any(|e| e.0 == "SPLINE"));
+        options.splines = false;
+        options.layers = false;
+        let entities = entities(&DXFBackend.draft_figure(&f, &options));
+        assert!(entities.iter().all(|e| e.0 == "LWPOLYLINE" && e.1 == "0"));
+        assert!(entities.len() > 1);
+    }
+
+    #[test]
+    fn test_shared_edges_are_cut_once() {
+        let plane = TessellationPlane {};
+        let center = Point::new(2.8, 2.9);
+        let shape = ClipShape::Circle {
+            center,
+            radius: 2.5,
+        };
+        let mut options = DXFOptions::new(10.0);
+        options.tolerance = 0.01;
+        let boundary = length(&shape.outline(options.tolerance / options.scale), true) * 10.0;
+        for f in common::figures() {
+            options.merge_edges = false;
+            let unmerged =
+                cut_length(&DXFBackend.draft_clipped_plane(&plane, &f, &shape, &options));
+            options.merge_edges = true;
+            let merged = cut_length(&DXFBackend.draft_clipped_plane(&plane, &f, &shape, &options));
+            // every edge inside the circle belongs to two tiles
+            let expected = (unmerged + boundary) / 2.0;
+            assert!(
+                (merged - expected).abs() < 1e-3 * expected,
+                "{merged} {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_clip_shape_without_area() {
+        let plane = TessellationPlane {};
+        let f = TessellationFigure::square();
+        let options = DXFOptions::default();
+        for shape in [
+            ClipShape::Path(vec![]),
+            ClipShape::RegularPolygon {
+                center: Point::new(0.0, 0.0),
+                radius: 1.0,
+                sides: 2,
+                rotation: 0.0,
+            },
+        ] {
+            let dxf = DXFBackend.draft_clipped_plane(&plane, &f, &shape, &options);
+            assert!(dxf.get_data().ends_with("0\nEOF\n"));
+            assert!(entities(&dxf).is_empty());
+        }
+    }
+}
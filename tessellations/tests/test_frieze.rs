@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationStrip};
+    use tessellations::tessellationfrieze::FriezeGroup;
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationstyle::Style;
+
+    fn centers(group: FriezeGroup) -> Vec<(i32, i32)> {
+        let f = TessellationFigure::square();
+        let mut c = TessellationStrip::new(group)
+            .strip(&f, 4.0)
+            .iter()
+            .map(|t| t.transform_point(Point::new(0.5, 0.5)))
+            .map(|p| ((p.x * 10.0).round() as i32, (p.y * 10.0).round() as i32))
+            .collect::<Vec<(i32, i32)>>();
+        c.sort();
+        c
+    }
+
+    #[test]
+    fn test_strip_has_no_overlaps() {
+        for group in FriezeGroup::ALL {
+            let c = centers(group);
+            let mut d = c.clone();
+            d.dedup();
+            assert_eq!(c, d, "{}", group.name());
+        }
+    }
+
+    #[test]
+    fn test_strip_fills_band() {
+        // every unit cell of the band over the length has a tile
+        for group in FriezeGroup::ALL {
+            let c = centers(group);
+            let rows: &[i32] = if group.has_horizontal_mirror() {
+                &[-5, 5]
+            } else {
+                &[5]
+            };
+            for x in 0..4 {
+                for &y in rows {
+                    assert!(c.contains(&(x * 10 + 5, y)), "{}", group.name());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_strip() {
+        let f = TessellationFigure::square();
+        let strip = TessellationStrip::new(FriezeGroup::SpinningSidle);
+        let image = Backend
+            .render_strip_to_image(&strip, &f, 300.0, 50.0, &Style::default())
+            .unwrap();
+        assert_eq!(image.get_data().len(), 300 * 50);
+        // the strip is filled up to the edges
+        assert!(image.get_data().iter().all(|&p| p != 0xffffffff));
+    }
+}
@@ -1,7 +1,11 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common;
     use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
     use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::signed_area;
 
     #[test]
     fn test_grid_square() {
@@ -10,4 +14,124 @@ mod tests {
         let expected = "[[(-1.5, -2.5), (-0.5, -2.5), (0.5, -2.5), (1.5, -2.5), (2.5, -2.5)], [(-1.5, -1.5), (-0.5, -1.5), (0.5, -1.5), (1.5, -1.5), (2.5, -1.5)], [(-1.5, -0.5), (-0.5, -0.5), (0.5, -0.5), (1.5, -0.5), (2.5, -0.5)], [(-1.5, 0.5), (-0.5, 0.5), (0.5, 0.5), (1.5, 0.5), (2.5, 0.5)], [(-1.5, 1.5), (-0.5, 1.5), (0.5, 1.5), (1.5, 1.5), (2.5, 1.5)]]";
         assert_eq!(format!("{:?}", p.grid(&f, 1.0, 1.0)), expected);
     }
+
+    #[test]
+    fn test_cover_brick() {
+        let f = TessellationFigure::brick();
+        let p = TessellationPlane {};
+        let points = p.cover(&f, Point::new(0.0, 0.0), Point::new(1.0, 1.0), 0.0);
+        assert!(points.iter().all(|g| g.point.x >= -f.gridincx
+            && g.point.x <= 1.0 + f.gridincx
+            && g.point.y >= -f.gridincy
+            && g.point.y <= 1.0 + f.gridincy));
+        // the rows are shifted by half a brick
+        let origin = points.iter().find(|g| g.row == 0 && g.column == 0).unwrap();
+        assert_eq!(origin.point, Point::new(0.0, 0.0));
+        let shifted = points.iter().find(|g| g.row == 1 && g.column == 0).unwrap();
+        assert_eq!(shifted.point, Point::new(f.shiftx, f.gridincy));
+    }
+
+    #[test]
+    fn test_period() {
+        let p = TessellationPlane {};
+        // the colors of the square repeat after four columns and two rows
+        let period = p.period(&TessellationFigure::square(), 4).unwrap();
+        assert_eq!((period.x, period.y), (4.0, 2.0));
+        // the brick rows shift half a brick so eight rows bring the colors back
+        let period = p.period(&TessellationFigure::brick(), 4).unwrap();
+        assert_eq!((period.x, period.y), (4.0, 8.0));
+        // oblique lattices need a supercell of several rows
+        let f = TessellationFigure::hexagon();
+        let period = p.period(&f, 4).unwrap();
+        assert_eq!((period.x, period.y), (f.gridincx, 6.0 * f.gridincy));
+        let f = TessellationFigure::triangle();
+        let period = p.period(&f, 4).unwrap();
+        assert_eq!((period.x, period.y), (f.gridincx, 2.0 * f.gridincy));
+    }
+
+    #[test]
+    fn test_colors_are_in_the_palette() {
+        let p = TessellationPlane {};
+        for f in common::figures() {
+            for colors in 1..4 {
+                let placements =
+                    p.placements(&f, Point::new(-2.0, -2.0), Point::new(2.0, 2.0), colors);
+                assert!(placements.iter().all(|t| t.color < colors));
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_period() {
+        let mut f = TessellationFigure::brick();
+        f.shiftx = std::f32::consts::FRAC_1_SQRT_2;
+        assert!(TessellationPlane {}.period(&f, 4).is_none());
+    }
+
+    #[test]
+    fn test_tiles_fill_the_region() {
+        let p = TessellationPlane {};
+        let (min, max) = (Point::new(-1.3, 0.4), Point::new(4.1, 3.7));
+        let region = (max.x - min.x) * (max.y - min.y);
+        for f in common::figures() {
+            let tiles = p.tiles(&f, min, max, 4);
+            let area: f32 = tiles
+                .iter()
+                .flat_map(|t| t.polygons.iter())
+                .map(|points| signed_area(points).abs())
+                .sum();
+            assert!((area - region).abs() < 1e-3 * region, "{area} {region}");
+            assert!(tiles
+                .iter()
+                .flat_map(|t| t.polygons.iter().flatten())
+                .all(|q| q.x >= min.x - 1e-4
+                    && q.x <= max.x + 1e-4
+                    && q.y >= min.y - 1e-4
+                    && q.y <= max.y + 1e-4));
+        }
+    }
+
+    #[test]
+    fn test_tiles_on_the_border() {
+        // the border of the region lies on the edges of the tiles
+        let p = TessellationPlane {};
+        let tiles = p.tiles(
+            &TessellationFigure::square(),
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            4,
+        );
+        assert_eq!(tiles.len(), 4);
+        for tile in &tiles {
+            assert_eq!(tile.polygons.len(), 1);
+            assert!((signed_area(&tile.polygons[0]).abs() - 1.0).abs() < 1e-4);
+        }
+        let tiles = p.tiles(
+            &TessellationFigure::two_triangles(),
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            4,
+        );
+        assert_eq!(tiles.len(), 8);
+        assert!(tiles
+            .iter()
+            .all(|t| (signed_area(&t.polygons[0]).abs() - 0.5).abs() < 1e-4));
+    }
+
+    #[test]
+    fn test_tiles_keep_their_class() {
+        let p = TessellationPlane {};
+        let f = TessellationFigure::diamond();
+        let tiles = p.tiles(&f, Point::new(0.0, 0.0), Point::new(4.0, 4.0), 4);
+        // a diamond is colored by its rotation class
+        assert!(tiles.iter().all(|t| t.color == t.rotation && t.tile == 0));
+        for rotation in 0..3 {
+            assert!(tiles.iter().any(|t| t.rotation == rotation));
+        }
+        let f = TessellationFigure::two_triangles();
+        let tiles = p.tiles(&f, Point::new(0.0, 0.0), Point::new(2.0, 2.0), 4);
+        assert!(tiles.iter().all(|t| t.color == t.tile));
+        assert!(tiles.iter().any(|t| t.tile == 0));
+        assert!(tiles.iter().any(|t| t.tile == 1));
+    }
 }
@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::tessellationhyperbolic::{HyperbolicFigure, Mobius};
+    use tessellations::tessellationline::Point;
+
+    fn close(p1: Point, p2: Point) -> bool {
+        (p1 - p2).length() < 1e-4
+    }
+
+    #[test]
+    fn test_cells_layers() {
+        let f = HyperbolicFigure::new(7, 3).unwrap();
+        let cells = f.cells(2);
+        assert_eq!(cells.iter().filter(|c| c.layer == 1).count(), 7);
+        // the second ring of the heptagonal tiling has 21 cells
+        assert_eq!(cells.iter().filter(|c| c.layer == 2).count(), 21);
+        for c in &cells {
+            assert!(
+                c.transform
+                    .transform_point(Point::zero())
+                    .to_vector()
+                    .length()
+                    < 1.0
+            );
+        }
+    }
+
+    #[test]
+    fn test_q_cells_around_vertex() {
+        let f = HyperbolicFigure::new(5, 4).unwrap();
+        let v = f.vertices()[0];
+        let around = f
+            .cells(2)
+            .iter()
+            .filter(|c| {
+                f.vertices()
+                    .iter()
+                    .any(|&p| close(c.transform.transform_point(p), v))
+            })
+            .count();
+        assert_eq!(around, 4);
+    }
+
+    #[test]
+    fn test_deformed_edge_matches_neighbour() {
+        let mut f = HyperbolicFigure::new(6, 4).unwrap();
+        f.points.push(Point::new(0.3, 0.1));
+        let outline = f.outline();
+        let neighbour = Mobius::half_turn(f.midpoint());
+        // the first edge of the neighbour is the first edge walked backwards
+        let n = outline.len() / 6;
+        for i in 1..n {
+            assert!(close(neighbour.transform_point(outline[i]), outline[n - i]));
+        }
+    }
+}
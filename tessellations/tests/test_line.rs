@@ -127,4 +127,27 @@ mod tests {
         let l2 = serde_json::from_str::<TessellationLine>(j).expect("parse error");
         assert_eq!(l1, l2);
     }
+
+    #[test]
+    fn test_hitpolygon() {
+        let mut l1: TessellationLine = TessellationLine::new(1.0, 0.0, 0.0);
+        l1.append(Point::new(0.0, 0.0));
+        l1.append(Point::new(0.0, 0.5));
+        l1.append(Point::new(0.0, 1.0));
+        let polygon = [
+            Point::new(0.5, 0.0),
+            Point::new(1.5, 0.0),
+            Point::new(1.5, 1.0),
+            Point::new(0.5, 1.0),
+        ];
+
+        assert_eq!(
+            l1.hitpolygon(&polygon),
+            vec![PointIndexPath {
+                line_index: 0,
+                point_index: 1,
+                corrp: true
+            }]
+        );
+    }
 }
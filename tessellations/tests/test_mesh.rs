@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{Decoration, TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::signed_area;
+    use tessellations::tessellationstyle::Style;
+
+    fn area(mesh: &Mesh) -> f32 {
+        mesh.indices
+            .chunks(3)
+            .map(|t| {
+                let [a, b, c] = [t[0], t[1], t[2]].map(|i| mesh.vertices[i as usize].position);
+                ((b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])).abs() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_fill_covers_the_tiles() {
+        for f in [
+            TessellationFigure::square(),
+            TessellationFigure::hexagon(),
+            TessellationFigure::diamond(),
+            TessellationFigure::two_triangles(),
+        ] {
+            let paths = LyonBackend.build_prototiles(&f);
+            for (path, points) in paths.iter().zip(f.prototile_points()) {
+                let mesh = LyonBackend.fill_mesh(path, &MeshOptions::default());
+                assert_eq!(mesh.indices.len() % 3, 0);
+                assert!((area(&mesh) - signed_area(&points).abs()).abs() < 1e-4);
+                assert!(mesh.vertices.iter().all(|v| v.normal == [0.0, 0.0]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_stroke_normals() {
+        let path = LyonBackend.build(&TessellationFigure::square()).unwrap();
+        let mesh = LyonBackend.stroke_mesh(&path, &MeshOptions::default());
+        assert!(!mesh.indices.is_empty());
+        assert!(mesh
+            .indices
+            .iter()
+            .all(|&i| (i as usize) < mesh.vertices.len()));
+        for v in &mesh.vertices {
+            // on the outline of the unit square
+            let [x, y] = v.position;
+            assert!(
+                x.abs() < 1e-6
+                    || y.abs() < 1e-6
+                    || (x - 1.0).abs() < 1e-6
+                    || (y - 1.0).abs() < 1e-6
+            );
+            let length = (v.normal[0] * v.normal[0] + v.normal[1] * v.normal[1]).sqrt();
+            assert!(length >= 1.0 - 1e-4);
+        }
+        // a stroke of width 0.1 covers a band of 0.1 along the perimeter of 4
+        let wide = Mesh {
+            vertices: mesh
+                .vertices
+                .iter()
+                .map(|v| MeshVertex {
+                    position: [
+                        v.position[0] + v.normal[0] * 0.05,
+                        v.position[1] + v.normal[1] * 0.05,
+                    ],
+                    normal: [0.0, 0.0],
+                })
+                .collect(),
+            indices: mesh.indices.clone(),
+        };
+        assert!((area(&wide) - 0.4).abs() < 0.02, "{}", area(&wide));
+    }
+
+    #[test]
+    fn test_tolerance() {
+        let mut f = TessellationFigure::square();
+        f.add_decoration(Decoration::eye(Point::new(0.5, 0.5), 0.4));
+        let path = &LyonBackend.build_decorations(&f)[0];
+        let coarse = LyonBackend.fill_mesh(path, &MeshOptions::new(0.05));
+        let fine = LyonBackend.fill_mesh(path, &MeshOptions::new(0.001));
+        assert!(fine.vertices.len() > coarse.vertices.len());
+        let exact = area(&LyonBackend.fill_mesh(path, &MeshOptions::new(1e-5)));
+        assert!((area(&fine) - exact).abs() < (area(&coarse) - exact).abs());
+    }
+
+    #[test]
+    fn test_plane_instances() {
+        let f = TessellationFigure::two_triangles();
+        let style = Style::default();
+        let (min, max) = (Point::new(0.0, 0.0), Point::new(4.0, 3.0));
+        let mesh = LyonBackend.tessellate_plane(
+            &TessellationPlane {},
+            &f,
+            min,
+            max,
+            &style,
+            &MeshOptions::default(),
+        );
+        let placements = TessellationPlane {}.placements(&f, min, max, style.palette.len());
+        assert_eq!(mesh.tiles.len(), 2);
+        assert_eq!(mesh.instances.len(), placements.len());
+        // the ranges follow each other and cover every instance
+        assert_eq!(mesh.tiles[0].instances.start, 0);
+        assert_eq!(mesh.tiles[0].instances.end, mesh.tiles[1].instances.start);
+        assert_eq!(mesh.tiles[1].instances.end as usize, mesh.instances.len());
+        for (k, tile) in mesh.tiles.iter().enumerate() {
+            let expected = placements.iter().filter(|p| p.tile == k);
+            for (instance, placement) in mesh.instances
+                [tile.instances.start as usize..tile.instances.end as usize]
+                .iter()
+                .zip(expected)
+            {
+                let [m11, m12, m21, m22, m31, m32] = instance.transform;
+                let p = Point::new(0.25, 0.75);
+                let q = placement.transform.transform_point(p);
+                assert!((p.x * m11 + p.y * m21 + m31 - q.x).abs() < 1e-5);
+                assert!((p.x * m12 + p.y * m22 + m32 - q.y).abs() < 1e-5);
+                let color = style.color(placement.color);
+                assert_eq!(instance.color[0], color.r as f32 / 255.0);
+                assert_eq!(instance.color[3], color.opacity());
+            }
+        }
+    }
+}
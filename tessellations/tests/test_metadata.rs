@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{Decoration, TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::{Point, Transform};
+    use tessellations::tessellationstyle::{Color, Stroke, Style};
+    use tessellations::tessellationsubstitution::SubstitutionTiling;
+
+    fn figure() -> TessellationFigure {
+        let mut f = TessellationFigure::brick();
+        f.add_decoration(Decoration::polyline(vec![
+            Point::new(0.25, 0.5),
+            Point::new(0.75, 0.5),
+        ]));
+        f
+    }
+
+    fn style() -> Style {
+        Style {
+            stroke: Some(Stroke::new(Color::rgb(0x10, 0x20, 0x30), 2.0)),
+            grout: 0.05,
+            ..Style::default()
+        }
+    }
+
+    fn assert_same(metadata: ImageMetadata) {
+        let f = figure();
+        assert_eq!(metadata.figure.lines, f.lines);
+        assert_eq!(metadata.figure.shiftx, f.shiftx);
+        assert_eq!(metadata.figure.decorations, f.decorations);
+        assert_eq!(metadata.style, style());
+    }
+
+    fn temp(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("tessellations-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_svg_roundtrip() {
+        let m = Transform::scale(50.0, 50.0);
+        let svg = SVGBackend
+            .compose_plane(&TessellationPlane {}, &figure(), &m, &style())
+            .unwrap();
+        assert_same(ImageMetadata::from_svg(&svg.get_data()).unwrap());
+        let path = temp("plane.svg");
+        assert!(svg.save_svg(&path));
+        assert_same(ImageMetadata::load(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_png_roundtrip() {
+        let mut options = RenderOptions::new(100.0, 80.0, Transform::scale(30.0, 30.0));
+        options.dpr = 2.0;
+        options.style = style();
+        let image = Backend
+            .render_plane_with_options(&TessellationPlane {}, &figure(), &options)
+            .unwrap();
+        let path = temp("plane.png");
+        assert!(image.save_png(&path));
+        // the style is the one asked for, not the one scaled to the device
+        assert_same(ImageMetadata::load(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_exports_of_a_period_keep_the_figure() {
+        let plane = TessellationPlane {};
+        let image = Backend
+            .render_period_to_image(&plane, &figure(), 20.0, &style())
+            .unwrap();
+        let path = temp("period.png");
+        assert!(image.save_png(&path));
+        assert_same(ImageMetadata::load(&path).unwrap());
+        std::fs::remove_file(path).unwrap();
+        let options = RenderOptions {
+            style: style(),
+            ..RenderOptions::default()
+        };
+        let svg = SVGBackend
+            .compose_plane_pattern(&plane, &figure(), &options)
+            .unwrap();
+        assert_same(ImageMetadata::from_svg(&svg.get_data()).unwrap());
+    }
+
+    #[test]
+    fn test_without_figure() {
+        let t = SubstitutionTiling::penrose_kite_dart();
+        let svg = SVGBackend
+            .compose_substitution(&t, 1, &Transform::scale(10.0, 10.0), &Style::default())
+            .unwrap();
+        assert!(ImageMetadata::from_svg(&svg.get_data()).is_none());
+        assert!(ImageMetadata::load(std::path::Path::new("no-such-file.png")).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{self, perimeter};
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::TessellationFigure;
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::{clean, signed_area};
+    use tessellations::tessellationstyle::LineJoin;
+
+    /// The presets and a square with a narrow spike
+    fn figures() -> Vec<TessellationFigure> {
+        let mut res = common::figures();
+        res.push(common::spiky_square());
+        res
+    }
+
+    /// No two edges that are not neighbours cross or touch
+    fn is_simple(points: &[Point]) -> bool {
+        let n = points.len();
+        let edge = |i: usize| (points[i], points[(i + 1) % n]);
+        (0..n).all(|i| {
+            (i + 2..n).all(|j| {
+                if i == 0 && j == n - 1 {
+                    return true;
+                }
+                let ((a, b), (c, d)) = (edge(i), edge(j));
+                let side = |p: Point, q: Point, r: Point| (q - p).cross(r - p);
+                !(side(a, b, c) * side(a, b, d) < 0.0 && side(c, d, a) * side(c, d, b) < 0.0)
+            })
+        })
+    }
+
+    #[test]
+    fn test_offset_outlines_stay_simple() {
+        for f in figures() {
+            let outlines = f.prototile_points();
+            for join in [LineJoin::Miter, LineJoin::Round, LineJoin::Bevel] {
+                for distance in [0.02, -0.02] {
+                    let moved = f.offset_prototile_points(distance, join);
+                    for (points, moved) in outlines.iter().zip(&moved) {
+                        // nothing splits or closes off a hole
+                        assert_eq!(moved.len(), 1, "{join:?} {distance}");
+                        let moved = &moved[0];
+                        assert!(is_simple(moved), "{join:?} {distance}");
+                        let area = signed_area(points);
+                        let change = signed_area(moved).abs() - area.abs();
+                        // at most the strip along the edges and the corners
+                        let strip = perimeter(&clean(points, 1e-5)) * distance;
+                        assert!(area * signed_area(moved) > 0.0);
+                        assert!(change * distance > 0.0);
+                        assert!(change.abs() <= strip.abs() * 1.5, "{change} {strip}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_spike_is_filled_by_the_kerf() {
+        let f = common::spiky_square();
+        let grown = &f.offset_prototile_points(0.05, LineJoin::Round)[0];
+        assert_eq!(grown.len(), 1);
+        let grown = &grown[0];
+        assert!(is_simple(grown));
+        // the notch is narrower than the kerf and closes up
+        assert!(grown
+            .iter()
+            .all(|p| p.x <= 0.0 || p.x >= 1.0 || p.y <= 0.0 || p.y >= 1.0));
+    }
+
+    #[test]
+    fn test_waist_splits_when_shrinking() {
+        let f = common::notched_square();
+        let parts = &f.offset_prototile_points(-0.06, LineJoin::Miter)[0];
+        assert_eq!(parts.len(), 2);
+        for part in parts {
+            assert!(is_simple(part));
+            assert!(signed_area(part).abs() > 0.2);
+        }
+    }
+
+    #[test]
+    fn test_kerf_dxf() {
+        let f = TessellationFigure::square();
+        let mut options = DXFOptions::new(10.0);
+        options.kerf = 0.2;
+        let data = DXFBackend.draft_figure(&f, &options).get_data();
+        let lines: Vec<&str> = data.lines().collect();
+        let values = |code: &str| -> Vec<f32> {
+            lines
+                .chunks(2)
+                .filter(|pair| pair[0] == code)
+                .map(|pair| pair[1].parse().unwrap())
+                .collect()
+        };
+        // the square of 10 millimetres grows by half the kerf on every side
+        let (xs, ys) = (values("10"), values("20"));
+        assert_eq!(xs.len(), 4);
+        let min = |v: &[f32]| v.iter().cloned().fold(f32::MAX, f32::min);
+        let max = |v: &[f32]| v.iter().cloned().fold(f32::MIN, f32::max);
+        assert!((min(&xs) + 0.1).abs() < 1e-4 && (max(&xs) - 10.1).abs() < 1e-4);
+        assert!((min(&ys) + 10.1).abs() < 1e-4 && (max(&ys) - 0.1).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::tessellationparametric::{Parameter, ParametricFigure};
+
+    #[test]
+    fn test_parametric_save_load_json() {
+        let mut f = ParametricFigure::bulge();
+        f.set("neck_width", 0.2);
+        let j = serde_json::to_string(&f).expect("json error");
+        let f2 = serde_json::from_str::<ParametricFigure>(&j).expect("parse error");
+
+        assert_eq!(f2.parameters, f.parameters);
+        assert_eq!(f2.lines, f.lines);
+        assert_eq!(f2.figure().unwrap().points(), f.figure().unwrap().points());
+    }
+
+    #[test]
+    fn test_parametric_load_json() {
+        let j = "{\"parameters\":[{\"name\":\"a\",\"value\":0.25,\"min\":0.0,\"max\":1.0}],\"lines\":[{\"start\":{\"x\":0.0,\"y\":0.0},\"end\":{\"x\":0.0,\"y\":1.0},\"points\":[{\"x\":\"a\",\"y\":\"2 * a\"}],\"tx\":1.0,\"ty\":0.0,\"angle\":0.0}],\"gridincx\":1.0,\"gridincy\":1.0,\"shiftx\":0.0,\"shifty\":1.0,\"rotdiv\":1,\"is_reversed\":false,\"shape\":\"S\"}";
+        let f = serde_json::from_str::<ParametricFigure>(j).expect("parse error");
+
+        assert_eq!(f.parameters, vec![Parameter::new("a", 0.25, 0.0, 1.0)]);
+        assert_eq!(
+            format!("{:?}", f.figure().unwrap().lines[0].dpoints()),
+            "[(0.0, 0.0), (0.25, 0.5), (0.0, 1.0)]"
+        );
+    }
+
+    #[test]
+    fn test_parametric_load_bad_expression() {
+        let j = "{\"parameters\":[],\"lines\":[{\"start\":{\"x\":0.0,\"y\":0.0},\"end\":{\"x\":0.0,\"y\":1.0},\"points\":[{\"x\":\"a +\",\"y\":\"0\"}],\"tx\":1.0,\"ty\":0.0,\"angle\":0.0}],\"gridincx\":1.0,\"gridincy\":1.0,\"shiftx\":0.0,\"shifty\":1.0,\"rotdiv\":1,\"is_reversed\":false,\"shape\":\"S\"}";
+        assert!(serde_json::from_str::<ParametricFigure>(j).is_err());
+    }
+}
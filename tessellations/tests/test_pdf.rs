@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{Decoration, TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Transform;
+    use tessellations::tessellationstyle::Color;
+
+    /// The document with a character for every byte
+    fn text(pdf: &PDF) -> String {
+        pdf.get_data()
+            .iter()
+            .map(|&b| if b < 128 { b as char } else { '?' })
+            .collect()
+    }
+
+    fn number(x: f32) -> String {
+        let s = format!("{:.3}", x);
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+
+    /// The cross reference table points at the objects and the length of
+    /// every stream is right
+    fn assert_well_formed(pdf: &PDF) {
+        let data = pdf.get_data();
+        let text = text(pdf);
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        let start = text.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let xref: usize = text[start..].lines().next().unwrap().parse().unwrap();
+        assert!(data[xref..].starts_with(b"xref\n"));
+        let table: Vec<&str> = text[xref..].lines().collect();
+        let count: usize = table[1].split(' ').nth(1).unwrap().parse().unwrap();
+        for (i, entry) in table[3..3 + count - 1].iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(data[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+        }
+        let mut rest = text.as_str();
+        while let Some(k) = rest.find("/Length ") {
+            rest = &rest[k + "/Length ".len()..];
+            let length: usize = rest[..rest.find(' ').unwrap()].parse().unwrap();
+            let stream = rest.find("stream\n").unwrap() + "stream\n".len();
+            assert!(rest[stream + length..].starts_with("endstream"));
+        }
+    }
+
+    fn scene(width: f32, height: f32) -> Scene {
+        let mut f = TessellationFigure::hexagon();
+        f.add_decoration(Decoration::eye(f.center(), 0.1));
+        let options = RenderOptions::new(width, height, Transform::scale(60.0, 60.0));
+        Scene::plane_with_options(&TessellationPlane {}, &f, &options)
+    }
+
+    #[test]
+    fn test_single_page() {
+        let mut options = PDFOptions::a4(1.0);
+        options.poster = false;
+        let pdf = PDFBackend
+            .print_scene(&scene(800.0, 600.0), &options)
+            .unwrap();
+        assert_well_formed(&pdf);
+        assert_eq!(pdf.pages(), 1);
+        let text = text(&pdf);
+        assert!(text.contains("/MediaBox [0 0 595.276 841.89]"));
+        assert!(!text.contains(" Tj "));
+        // the decorations are curves
+        assert!(text.contains(" c\n"));
+    }
+
+    #[test]
+    fn test_poster_pages() {
+        // 400 by 300 millimetres on sheets with 190 by 277 millimetres
+        // to draw on that overlap 10 millimetres
+        let pdf = PDFBackend
+            .print_scene(&scene(800.0, 600.0), &PDFOptions::a4(0.5))
+            .unwrap();
+        assert_well_formed(&pdf);
+        assert_eq!((pdf.rows, pdf.columns), (2, 3));
+        let text = text(&pdf);
+        assert_eq!(text.matches("/Type /Page ").count(), 6);
+        assert!(text.contains("(A1  page 1 of 6) Tj"));
+        assert!(text.contains("(B3  page 6 of 6) Tj"));
+        // eight crop marks on every page
+        assert_eq!(text.matches(" l S\n").count(), 6 * 8);
+        // a page only draws the tiles on it
+        let single = PDFBackend
+            .print_scene(&scene(800.0, 600.0), &PDFOptions::new(1000.0, 1000.0, 0.5))
+            .unwrap();
+        assert_eq!(single.pages(), 1);
+        let fills = |text: &str| text.matches("\nf\n").count();
+        assert!(fills(&text) < 6 * fills(&self::text(&single)));
+    }
+
+    #[test]
+    fn test_page_offsets() {
+        let pdf = PDFBackend
+            .print_scene(&scene(800.0, 600.0), &PDFOptions::a4(0.5))
+            .unwrap();
+        let text = text(&pdf);
+        // the second column starts 180 millimetres into the image, one
+        // pixel is half a millimetre
+        let k = 72.0 / 25.4;
+        assert!(text.contains(&format!(
+            "{} 0 0 {} {} ",
+            number(0.5 * k),
+            number(-0.5 * k),
+            number((10.0 - 180.0) * k)
+        )));
+    }
+
+    #[test]
+    fn test_transparent_colors() {
+        let mut scene = scene(200.0, 200.0);
+        scene.style.palette[0] = Color::rgba(255, 0, 0, 128);
+        let pdf = PDFBackend
+            .print_scene(&scene, &PDFOptions::a4(0.5))
+            .unwrap();
+        assert_well_formed(&pdf);
+        let text = text(&pdf);
+        assert!(text.contains("/A128 << /ca 0.502 /CA 0.502 >>"));
+        assert!(text.contains("/A128 gs"));
+        assert!(text.contains("/Opaque gs"));
+    }
+}
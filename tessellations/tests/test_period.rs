@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Transform;
+    use tessellations::tessellationstyle::{Color, Stroke, Style};
+
+    /// Largest difference of a color channel of two pixels
+    fn difference(a: u32, b: u32) -> u32 {
+        (0..4)
+            .map(|k| ((a >> (8 * k)) & 0xff).abs_diff((b >> (8 * k)) & 0xff))
+            .max()
+            .unwrap()
+    }
+
+    /// The period moved by half its size must look like the plane rendered
+    /// with the view moved as much, the borders of the period are then in
+    /// the middle of the image
+    fn assert_seamless(figure: &TessellationFigure, style: &Style) {
+        let plane = TessellationPlane {};
+        let scene = Scene::period(&plane, figure, 23.0, style).unwrap();
+        let (w, h) = (scene.width as usize, scene.height as usize);
+        let period = Backend
+            .render_period_to_image(&plane, figure, 23.0, style)
+            .unwrap();
+        // with a margin as the pixels at the border collect the shapes beyond
+        let (dx, dy) = (w / 2, h / 2);
+        let view = scene
+            .view
+            .then_translate(euclid::vec2(2.0 - dx as f32, 2.0 - dy as f32));
+        let mut options = RenderOptions::new(scene.width + 4.0, scene.height + 4.0, view);
+        options.style = style.clone();
+        let mut moved = Scene::canvas(&options);
+        moved.add_visible_plane(&plane, figure);
+        let moved = Backend.render_scene(&moved).unwrap();
+        let (period, moved) = (period.get_data(), moved.get_data());
+        let mut worst = 0;
+        for y in 0..h {
+            for x in 0..w {
+                let a = period[((y + dy) % h) * w + (x + dx) % w];
+                worst = worst.max(difference(a, moved[(y + 2) * (w + 4) + x + 2]));
+            }
+        }
+        // rounding moves the antialiased edges a little, a seam would differ
+        // by a whole step between two colors of the palette
+        assert!(worst <= 12, "differs by {worst}");
+    }
+
+    #[test]
+    fn test_square_period_is_seamless() {
+        assert_seamless(&TessellationFigure::square(), &Style::default());
+    }
+
+    #[test]
+    fn test_brick_period_is_seamless() {
+        let style = Style {
+            stroke: Some(Stroke::new(Color::BLACK, 3.0)),
+            ..Style::default()
+        };
+        assert_seamless(&TessellationFigure::brick(), &style);
+    }
+
+    #[test]
+    fn test_oblique_periods_are_seamless() {
+        assert_seamless(&TessellationFigure::hexagon(), &Style::default());
+        assert_seamless(&TessellationFigure::triangle(), &Style::default());
+        assert_seamless(&TessellationFigure::diamond(), &Style::default());
+    }
+
+    #[test]
+    fn test_svg_period() {
+        let f = TessellationFigure::hexagon();
+        let style = Style::default();
+        let scene = Scene::period(&TessellationPlane {}, &f, 20.0, &style).unwrap();
+        let svg = SVGBackend
+            .compose_period(&TessellationPlane {}, &f, 20.0, &style)
+            .unwrap()
+            .get_data();
+        assert!(svg.contains(&format!("viewBox=\"0 0 {} {}\"", scene.width, scene.height)));
+    }
+
+    #[test]
+    fn test_svg_pattern() {
+        let plane = TessellationPlane {};
+        let f = TessellationFigure::brick();
+        let mut options = RenderOptions::new(
+            300.0,
+            200.0,
+            Transform::rotation(euclid::Angle::degrees(30.0)).then_scale(40.0, 40.0),
+        );
+        options.dpr = 2.0;
+        let svg = SVGBackend
+            .compose_plane_pattern(&plane, &f, &options)
+            .unwrap()
+            .get_data();
+        let cell = Scene::fundamental(&plane, &f, &options.style).unwrap();
+        assert!(svg.contains("viewBox=\"0 0 600 400\""));
+        assert!(svg.contains(&format!(
+            "height=\"{}\" id=\"plane\" patternTransform=\"matrix(",
+            cell.height
+        )));
+        assert!(svg.contains("fill=\"url(#plane)\" height=\"400\" width=\"600\""));
+        // only the tiles of one period are written
+        assert_eq!(svg.matches("<use").count(), cell.tiles.len());
+        let plane_svg = SVGBackend
+            .compose_plane_with_options(&plane, &f, &options)
+            .unwrap()
+            .get_data();
+        assert!(svg.len() < plane_svg.len());
+    }
+}
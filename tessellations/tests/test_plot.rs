@@ -0,0 +1,99 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::{self, perimeter};
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationpolygon::{strokes, travel};
+
+    #[test]
+    fn test_square_edges_are_drawn_once() {
+        let plot = PlotterBackend.plot_plane(
+            &TessellationPlane {},
+            &TessellationFigure::square(),
+            Point::new(0.5, 0.5),
+            Point::new(3.5, 2.5),
+            &PlotOptions::new(10.0),
+        );
+        // the border of the sheet and three and two lines across
+        assert!((plot.length() - 220.0).abs() < 1e-2, "{}", plot.length());
+        assert_eq!((plot.width, plot.height), (30.0, 20.0));
+    }
+
+    #[test]
+    fn test_every_edge_once() {
+        let plane = TessellationPlane {};
+        let (min, max) = (Point::new(-0.3, 0.2), Point::new(4.1, 3.3));
+        let options = PlotOptions::new(10.0);
+        for f in common::figures() {
+            let polygons: Vec<Vec<Point>> = plane
+                .tiles(&f, min, max, 1)
+                .into_iter()
+                .flat_map(|t| t.polygons)
+                .collect();
+            let drawn: f32 = polygons.iter().map(|p| perimeter(p)).sum();
+            let border = 2.0 * (max.x - min.x + max.y - min.y);
+            let plot = PlotterBackend.plot_plane(&plane, &f, min, max, &options);
+            // every edge inside the sheet belongs to two tiles
+            let expected = (drawn + border) / 2.0 * options.scale;
+            assert!(
+                (plot.length() - expected).abs() < 1e-3 * expected,
+                "{} {expected}",
+                plot.length()
+            );
+            // ordering the strokes shortens the way with the pen up
+            let scaled: Vec<Vec<Point>> = polygons
+                .iter()
+                .map(|p| p.iter().map(|&q| ((q - min) * 10.0).to_point()).collect())
+                .collect();
+            let unordered = travel(&strokes(&scaled, options.tolerance), Point::origin());
+            assert!(plot.travel() < unordered / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_plot_formats() {
+        let plot = PlotterBackend.plot_plane(
+            &TessellationPlane {},
+            &TessellationFigure::hexagon(),
+            Point::new(0.0, 0.0),
+            Point::new(5.0, 4.0),
+            &PlotOptions::new(10.0),
+        );
+        let svg = plot.svg().to_string();
+        assert!(svg.contains("viewBox=\"0 0 50 40\""));
+        assert!(svg.contains("width=\"50mm\""));
+        assert_eq!(svg.matches("<path").count(), plot.strokes.len());
+        let hpgl = plot.hpgl();
+        assert!(hpgl.starts_with("IN;SP1;PU"));
+        assert!(hpgl.ends_with("PU;SP0;\n"));
+        assert_eq!(hpgl.matches("PD").count(), plot.strokes.len());
+        // 40 plotter units per millimetre with the y axis up
+        let first = plot.strokes[0][0];
+        assert!(hpgl.starts_with(&format!(
+            "IN;SP1;PU{},{};",
+            (first.x * 40.0).round(),
+            ((40.0 - first.y) * 40.0).round()
+        )));
+    }
+
+    #[test]
+    fn test_plot_scene() {
+        let mut options = RenderOptions::new(200.0, 100.0, euclid::Transform2D::scale(50.0, 50.0));
+        options.style.grout = 0.0;
+        let mut scene = Scene::canvas(&options);
+        scene.add_visible_plane(&TessellationPlane {}, &TessellationFigure::square());
+        let plot = PlotterBackend
+            .plot_scene(&scene, &PlotOptions::new(1.0))
+            .unwrap();
+        // the whole squares reaching into the image
+        assert!(plot.width >= 4.0 && plot.height >= 2.0);
+        let cells = (plot.width.round() * plot.height.round()) as usize;
+        assert_eq!(scene.tiles.len(), cells);
+        assert!(PlotterBackend
+            .plot_scene(&Scene::default(), &PlotOptions::default())
+            .is_none());
+    }
+}
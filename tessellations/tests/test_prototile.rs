@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+    use tessellations::tessellationline::{Point, PointIndexPath};
+
+    #[test]
+    fn test_shared_edge_moves_both_prototiles() {
+        let mut f = TessellationFigure::two_triangles();
+        f.lines[0].insert(1, Point::new(0.6, 0.4));
+        let tiles = f.prototile_points();
+        assert!(tiles[0].contains(&Point::new(0.6, 0.4)));
+        assert!(tiles[1].contains(&Point::new(0.6, 0.4)));
+    }
+
+    #[test]
+    fn test_linked_edge_moves_corresponding_point() {
+        let mut f = TessellationFigure::two_triangles();
+        f.lines[1].insert(1, Point::new(0.5, 0.1));
+        let path = PointIndexPath {
+            line_index: 1,
+            point_index: 1,
+            corrp: true,
+        };
+        assert_eq!(f.point(path), Point::new(0.5, 1.1));
+        assert!(f.prototile_points()[1].contains(&Point::new(0.5, 1.1)));
+    }
+
+    #[test]
+    fn test_single_prototile() {
+        let f = TessellationFigure::square();
+        assert_eq!(f.prototile_count(), 1);
+        assert_eq!(f.prototile_points(), vec![f.points()]);
+    }
+
+    #[test]
+    fn test_two_triangles_grid() {
+        let f = TessellationFigure::two_triangles();
+        let g = TessellationPlane {}.grid(&f, 2.0, 2.0);
+        // both prototiles are placed on the lattice of the unit square
+        assert_eq!(g[0][1] - g[0][0], euclid::vec2(1.0, 0.0));
+        assert_eq!(g[1][0].y - g[0][0].y, 1.0);
+    }
+
+    #[test]
+    fn test_prototile_save_load_json() {
+        let f = TessellationFigure::two_triangles();
+        let j = serde_json::to_string(&f).expect("json error");
+        let f2 = serde_json::from_str::<TessellationFigure>(&j).expect("parse error");
+        assert_eq!(f2.prototiles, f.prototiles);
+        assert_eq!(f2.prototile_points(), f.prototile_points());
+    }
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+    use tessellations::tessellationhyperbolic::HyperbolicFigure;
+    use tessellations::tessellationline::{Point, Transform};
+    use tessellations::tessellationstyle::{Color, LineJoin, Stroke, Style};
+
+    /// The tiles of the plane on the default image without the overlays
+    fn plane(f: &TessellationFigure, style: &Style) -> Scene {
+        let options = RenderOptions {
+            style: style.clone(),
+            ..RenderOptions::default()
+        };
+        let mut scene = Scene::plane_with_options(&TessellationPlane {}, f, &options);
+        scene.overlays.clear();
+        scene
+    }
+
+    /// Pixel of the image as 0xaarrggbb
+    fn pixel(image: &dyn OutputImage, scene: &Scene, p: Point) -> u32 {
+        image.get_data()[p.y as usize * scene.width as usize + p.x as usize]
+    }
+
+    #[test]
+    fn test_png_and_svg_use_the_scene_colors() {
+        let f = TessellationFigure::square90();
+        let scene = plane(&f, &Style::default());
+        let image = Backend.render_scene(&scene).unwrap();
+        let svg = SVGBackend.compose_scene(&scene).unwrap().get_data();
+        for tile in scene.tiles.iter().take(40) {
+            let center = tile
+                .transform
+                .then(&scene.view)
+                .transform_point(Point::new(0.5, 0.5));
+            if center.x < 1.0
+                || center.y < 1.0
+                || center.x >= scene.width - 1.0
+                || center.y >= scene.height - 1.0
+            {
+                continue;
+            }
+            let color = scene.color(tile);
+            let expected =
+                0xff000000 | (color.r as u32) << 16 | (color.g as u32) << 8 | color.b as u32;
+            assert_eq!(pixel(image.as_ref(), &scene, center), expected);
+            assert!(svg.contains(&color.to_hex()));
+        }
+        assert_eq!(svg.matches("<use").count(), scene.tiles.len());
+    }
+
+    #[test]
+    fn test_lyon_builds_every_tile() {
+        let f = TessellationFigure::two_triangles();
+        let scene = Scene::region(
+            &TessellationPlane {},
+            &f,
+            Point::new(-2.0, -2.0),
+            Point::new(2.0, 2.0),
+            &Style::default(),
+        );
+        let paths = LyonBackend.build_scene(&scene);
+        assert_eq!(paths.len(), scene.tiles.len());
+        assert!(paths
+            .iter()
+            .zip(scene.tiles.iter())
+            .all(|(p, t)| p.color == t.color));
+    }
+
+    #[test]
+    fn test_plane_covers_the_image() {
+        let mut options = RenderOptions::new(
+            160.0,
+            90.0,
+            Transform::rotation(euclid::Angle::degrees(30.0))
+                .then_scale(25.0, 25.0)
+                .then_translate(euclid::vec2(-300.0, 500.0)),
+        );
+        options.dpr = 2.0;
+        options.style.background = Color::rgb(0xff, 0x0, 0x0);
+        for f in [
+            TessellationFigure::square(),
+            TessellationFigure::hexagon(),
+            TessellationFigure::square90(),
+        ] {
+            let mut scene = Scene::plane_with_options(&TessellationPlane {}, &f, &options);
+            scene.overlays.clear();
+            assert_eq!((scene.width, scene.height), (320.0, 180.0));
+            let image = Backend.render_scene(&scene).unwrap();
+            assert_eq!(image.get_data().len(), 320 * 180);
+            assert!(image.get_data().iter().all(|&p| p != 0xffff0000));
+        }
+    }
+
+    #[test]
+    fn test_style_is_shared() {
+        let style = Style {
+            palette: vec![Color::rgb(0x12, 0x34, 0x56)],
+            stroke: Some(Stroke::new(Color::rgb(0xab, 0xcd, 0xef), 2.0)),
+            ..Style::default()
+        };
+        let f = TessellationFigure::square();
+        let p = TessellationPlane {};
+        let m = Transform::scale(70.0, 70.0);
+        let svg = SVGBackend
+            .compose_plane(&p, &f, &m, &style)
+            .unwrap()
+            .get_data();
+        assert!(svg.contains("fill=\"#123456\""));
+        assert!(svg.contains("stroke=\"#abcdef\" stroke-width=\"2px\""));
+        let image = Backend.render_plane_to_image(&p, &f, &m, &style).unwrap();
+        // the middle of a tile
+        assert_eq!(image.get_data()[35 * 400 + 35], 0xff123456);
+    }
+
+    #[test]
+    fn test_grout_shows_the_background() {
+        let style = Style {
+            palette: vec![Color::rgb(0x12, 0x34, 0x56)],
+            background: Color::WHITE,
+            grout: 0.2,
+            ..Style::default()
+        };
+        let f = TessellationFigure::square();
+        let mut scene = plane(&f, &style);
+        let image = Backend.render_scene(&scene).unwrap();
+        // the tiles meet at multiples of 70 pixels
+        assert_eq!(image.get_data()[35 * 400 + 70], 0xffffffff);
+        assert_eq!(image.get_data()[35 * 400 + 35], 0xff123456);
+        let svg = SVGBackend.compose_scene(&scene).unwrap().get_data();
+        assert!(svg.contains("href=\"#figure-grout\""));
+        assert!(svg.contains("L0.1,0.9 L0.9,0.9"));
+        // the overlay keeps the outline of the figure
+        scene.add_overlays(&Transform::identity());
+        let svg = SVGBackend.compose_scene(&scene).unwrap().get_data();
+        assert!(svg.contains("fill=\"none\" href=\"#figure\""));
+    }
+
+    #[test]
+    fn test_world_stroke() {
+        let mut stroke = Stroke::world(Color::BLACK, 0.1);
+        stroke.join = LineJoin::Round;
+        let style = Style {
+            stroke: Some(stroke),
+            ..Style::default()
+        };
+        let f = TessellationFigure::square();
+        let scene = plane(&f, &style);
+        let svg = SVGBackend.compose_scene(&scene).unwrap().get_data();
+        assert!(svg.contains("stroke=\"#000000\" stroke-linejoin=\"round\" stroke-width=\"7px\""));
+        let image = Backend.render_scene(&scene).unwrap();
+        // the line is 7 pixels wide around the edge at 70 pixels
+        assert_eq!(image.get_data()[35 * 400 + 68], 0xff000000);
+        assert_ne!(image.get_data()[35 * 400 + 60], 0xff000000);
+    }
+
+    #[test]
+    fn test_hyperbolic_follows_the_options() {
+        let f = HyperbolicFigure::new(5, 4).unwrap();
+        let mut options = RenderOptions::new(
+            200.0,
+            100.0,
+            Transform::scale(45.0, 45.0).then_translate(euclid::vec2(100.0, 50.0)),
+        );
+        options.dpr = 2.0;
+        options.style.palette = vec![Color::rgb(0x12, 0x34, 0x56), Color::WHITE];
+        let scene = Scene::hyperbolic(&f, 1, &options);
+        // the center cell and its five neighbours with the rim on top
+        assert_eq!(scene.tiles.len(), 6);
+        assert_eq!(scene.overlays.len(), 1);
+        let image = Backend
+            .render_hyperbolic_with_options(&f, 1, &options)
+            .unwrap();
+        assert_eq!(image.get_data().len(), 400 * 200);
+        // the center of the disk is in the center cell
+        assert_eq!(
+            pixel(image.as_ref(), &scene, Point::new(200.0, 100.0)),
+            0xff123456
+        );
+        let svg = SVGBackend
+            .compose_hyperbolic_with_options(&f, 1, &options)
+            .unwrap()
+            .get_data();
+        assert!(svg.contains("viewBox=\"0 0 400 200\""));
+    }
+}
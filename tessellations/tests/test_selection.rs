@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use euclid::Angle;
+    use tessellations::tessellationfigure::{PointSelection, TessellationFigure};
+    use tessellations::tessellationline::{Point, PointIndexPath, Vector};
+
+    fn square_with_bumps() -> TessellationFigure {
+        let mut f = TessellationFigure::square();
+        f.lines[0].insert(1, Point::new(0.0, 0.4));
+        f.lines[0].insert(2, Point::new(0.0, 0.6));
+        f.lines[1].insert(1, Point::new(0.5, 1.0));
+        f
+    }
+
+    #[test]
+    fn test_hitrect_lines_and_corrp() {
+        let f = square_with_bumps();
+        assert_eq!(
+            f.hitrect(Point::new(0.9, 0.3), Point::new(1.1, 0.5)),
+            vec![PointIndexPath {
+                line_index: 0,
+                point_index: 1,
+                corrp: true
+            }]
+        );
+        assert_eq!(
+            f.hitrect(Point::new(-0.1, 0.3), Point::new(0.6, 1.1)).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_translate_keeps_corrp() {
+        let mut f = square_with_bumps();
+        let mut s = PointSelection::new();
+        s.extend(f.hitrect(Point::new(-0.1, 0.3), Point::new(0.6, 1.1)));
+        f.translate(&s, Vector::new(0.1, 0.0));
+
+        assert_eq!(f.lines[0].dpoints()[1], Point::new(0.1, 0.4));
+        assert_eq!(f.lines[1].dpoints()[1], Point::new(0.6, 1.0));
+        assert_eq!(f.lines[0].cpoints()[1], Point::new(1.1, 0.4));
+    }
+
+    #[test]
+    fn test_rotate_around_centroid() {
+        let mut f = square_with_bumps();
+        let mut s = PointSelection::new();
+        s.extend(f.hitrect(Point::new(-0.1, 0.3), Point::new(0.1, 0.7)));
+        f.rotate(&s, Angle::degrees(90.0));
+
+        let p1 = f.lines[0].dpoints()[1];
+        let p2 = f.lines[0].dpoints()[2];
+        assert!((p1 - Point::new(0.1, 0.5)).length() < 1e-6);
+        assert!((p2 - Point::new(-0.1, 0.5)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_scale_around_centroid() {
+        let mut f = square_with_bumps();
+        let mut s = PointSelection::new();
+        s.extend(f.hitrect(Point::new(-0.1, 0.3), Point::new(0.1, 0.7)));
+        f.scale(&s, 2.0);
+
+        assert_eq!(f.centroid(&s), Some(Point::new(0.0, 0.5)));
+        assert!((f.lines[0].dpoints()[1] - Point::new(0.0, 0.3)).length() < 1e-6);
+        assert!((f.lines[0].dpoints()[2] - Point::new(0.0, 0.7)).length() < 1e-6);
+    }
+
+    #[test]
+    fn test_corrp_selected_once() {
+        let mut f = square_with_bumps();
+        let mut s = PointSelection::new();
+        s.extend(f.hitrect(Point::new(-0.1, -0.1), Point::new(1.1, 1.1)));
+        assert_eq!(s.len(), 3);
+        f.translate(&s, Vector::new(0.0, 0.1));
+        assert_eq!(f.lines[1].dpoints()[1], Point::new(0.5, 1.1));
+    }
+}
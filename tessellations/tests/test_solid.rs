@@ -0,0 +1,113 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common;
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::TessellationFigure;
+    use tessellations::tessellationpolygon::{offset_loops, signed_area};
+    use tessellations::tessellationstyle::LineJoin;
+
+    /// Area of the prototiles in square millimetres, without the holes
+    fn area(f: &TessellationFigure, scale: f32, distance: f32) -> f32 {
+        f.prototile_points()
+            .iter()
+            .map(|points| {
+                let points: Vec<_> = points.iter().map(|&p| p * scale).collect();
+                let loops = offset_loops(&points, distance, LineJoin::Miter);
+                loops.iter().map(|l| signed_area(l)).sum::<f32>().abs()
+            })
+            .sum()
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-3 * b.abs().max(1.0), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_solid_tiles() {
+        let options = ExtrudeOptions::new(20.0, 3.0);
+        for f in common::figures() {
+            let solid = LyonBackend.extrude_figure(&f, &options).unwrap();
+            assert!(solid.is_watertight());
+            assert_close(solid.volume(), area(&f, 20.0, 0.0) * 3.0);
+            assert!(solid.vertices.iter().all(|v| v[2] == 0.0 || v[2] == 3.0));
+        }
+    }
+
+    #[test]
+    fn test_cookie_cutters() {
+        let options = ExtrudeOptions::cutter(40.0, 12.0, 1.0);
+        for f in common::figures() {
+            let solid = LyonBackend.extrude_figure(&f, &options).unwrap();
+            assert!(solid.is_watertight());
+            let wall = area(&f, 40.0, 0.0) - area(&f, 40.0, -1.0);
+            let flange = area(&f, 40.0, 5.0) - area(&f, 40.0, 0.0);
+            assert_close(solid.volume(), wall * 12.0 + flange * 1.0);
+        }
+    }
+
+    #[test]
+    fn test_notch_in_line_with_the_tab() {
+        // the edges of the notch and the tab are on one line
+        let f = common::notched_square();
+        let solid = LyonBackend
+            .extrude_figure(&f, &ExtrudeOptions::new(40.0, 4.0))
+            .unwrap();
+        assert!(solid.is_watertight());
+        assert_close(solid.volume(), area(&f, 40.0, 0.0) * 4.0);
+        let options = ExtrudeOptions::cutter(40.0, 12.0, 1.0);
+        let solid = LyonBackend.extrude_figure(&f, &options).unwrap();
+        assert!(solid.is_watertight());
+    }
+
+    #[test]
+    fn test_cutter_with_a_narrow_waist() {
+        // the wall of 3 mm splits the hole at the waist of 4 mm
+        let f = common::notched_square();
+        let options = ExtrudeOptions::cutter(40.0, 12.0, 3.0);
+        let solid = LyonBackend.extrude_figure(&f, &options).unwrap();
+        assert!(solid.is_watertight());
+        let points: Vec<_> = f.prototile_points()[0].iter().map(|&p| p * 40.0).collect();
+        assert_eq!(offset_loops(&points, -3.0, LineJoin::Miter).len(), 2);
+        let wall = area(&f, 40.0, 0.0) - area(&f, 40.0, -3.0);
+        let flange = area(&f, 40.0, 5.0) - area(&f, 40.0, 0.0);
+        assert_close(solid.volume(), wall * 12.0 + flange * 1.0);
+    }
+
+    #[test]
+    fn test_prototiles_apart() {
+        let options = ExtrudeOptions::cutter(40.0, 12.0, 1.0);
+        let solid = LyonBackend
+            .extrude_figure(&TessellationFigure::two_triangles(), &options)
+            .unwrap();
+        // two triangles of 40 mm with a flange of 5 mm and a gap between
+        let max = solid.vertices.iter().fold(0.0f32, |a, v| a.max(v[0]));
+        assert!(max > 2.0 * 50.0);
+        assert!(solid.vertices.iter().all(|v| v[0] >= 0.0 && v[1] >= 0.0));
+    }
+
+    #[test]
+    fn test_wall_too_thick() {
+        let options = ExtrudeOptions::cutter(10.0, 12.0, 6.0);
+        assert!(LyonBackend
+            .extrude_figure(&TessellationFigure::square(), &options)
+            .is_none());
+    }
+
+    #[test]
+    fn test_mesh_files() {
+        let solid = LyonBackend
+            .extrude_figure(&TessellationFigure::square(), &ExtrudeOptions::default())
+            .unwrap();
+        // a box of 8 corners and 12 triangles
+        assert_eq!((solid.vertices.len(), solid.triangles.len()), (8, 12));
+        let stl = solid.stl();
+        assert_eq!(stl.len(), 84 + 12 * 50);
+        assert_eq!(u32::from_le_bytes(stl[80..84].try_into().unwrap()), 12);
+        let obj = solid.obj();
+        assert_eq!(obj.lines().filter(|l| l.starts_with("v ")).count(), 8);
+        assert_eq!(obj.lines().filter(|l| l.starts_with("f ")).count(), 12);
+        assert!(obj.contains("v 40 40 4\n"));
+    }
+}
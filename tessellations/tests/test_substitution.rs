@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use tessellations::tessellationline::Point;
+    use tessellations::tessellationsubstitution::{
+        AperiodicPrototile, MatchingEdge, PlacedTile, SubstitutionRule, SubstitutionTiling,
+    };
+
+    fn round(p: &Point) -> (i32, i32) {
+        ((p.x * 100.0).round() as i32, (p.y * 100.0).round() as i32)
+    }
+
+    /// Checks that no edge is used by more than two tiles and that the
+    /// deformed edges of neighbours are the same
+    fn assert_edges_match(tiling: &SubstitutionTiling, depth: usize) {
+        let mut edges = HashMap::<((i32, i32), (i32, i32)), Vec<Vec<(i32, i32)>>>::new();
+        for tile in tiling.tiles(depth) {
+            let outline = tiling.outline(&tile);
+            let mut start = 0;
+            for e in &tiling.prototiles[tile.prototile].edges {
+                let len = tiling.edges[e.edge_type].len() + 2;
+                let edge = &outline[start..start + len];
+                start += len;
+                let mut points = edge.iter().map(round).collect::<Vec<(i32, i32)>>();
+                if points[0] > points[len - 1] {
+                    points.reverse();
+                }
+                edges
+                    .entry((points[0], points[len - 1]))
+                    .or_default()
+                    .push(points);
+            }
+        }
+        let shared = edges.values().filter(|e| e.len() == 2).count();
+        assert!(shared > 0);
+        for e in edges.values() {
+            assert!(e.len() <= 2);
+            if e.len() == 2 {
+                assert_eq!(e[0], e[1]);
+            }
+        }
+    }
+
+    fn count(tiles: &[PlacedTile], prototile: usize) -> usize {
+        tiles.iter().filter(|t| t.prototile == prototile).count()
+    }
+
+    #[test]
+    fn test_kite_dart_deformed_edges_match() {
+        let mut t = SubstitutionTiling::penrose_kite_dart();
+        t.set_edge(0, vec![Point::new(0.3, 0.1)]);
+        t.set_edge(1, vec![Point::new(0.6, -0.07), Point::new(0.8, 0.05)]);
+        assert_edges_match(&t, 4);
+    }
+
+    #[test]
+    fn test_rhombs_deformed_edges_match() {
+        let mut t = SubstitutionTiling::penrose_rhombs();
+        t.set_edge(0, vec![Point::new(0.3, 0.1)]);
+        t.set_edge(1, vec![Point::new(0.6, -0.07)]);
+        assert_edges_match(&t, 4);
+    }
+
+    #[test]
+    fn test_kite_dart_ratio() {
+        let tiles = SubstitutionTiling::penrose_kite_dart().tiles(5);
+        assert_eq!(count(&tiles, 0), 450);
+        assert_eq!(count(&tiles, 1), 285);
+    }
+
+    #[test]
+    fn test_rhombs_sun() {
+        let t = SubstitutionTiling::penrose_rhombs();
+        let tiles = t.tiles(0);
+        assert_eq!(tiles.len(), 5);
+        // the five thick rhombs meet at the origin
+        for tile in &tiles {
+            assert!(t
+                .vertices(tile)
+                .iter()
+                .any(|p| p.to_vector().length() < 1e-6));
+        }
+    }
+
+    #[test]
+    fn test_own_rule() {
+        // a right triangle split in two halves of the same shape
+        let s = 2f32.sqrt();
+        let triangle = AperiodicPrototile::new(
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(1.0, 0.0),
+                Point::new(0.0, 1.0),
+            ],
+            vec![MatchingEdge::new(0, false); 3],
+        );
+        let mut rule = SubstitutionRule::new(s, 1);
+        let from = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let apex = Point::new(0.5, 0.5);
+        for to in [
+            [apex, Point::new(0.0, 0.0), Point::new(1.0, 0.0)],
+            [apex, Point::new(0.0, 1.0), Point::new(0.0, 0.0)],
+        ] {
+            rule.add_child(
+                0,
+                PlacedTile::new(
+                    0,
+                    tessellations::tessellationsubstitution::triangle_transform(from, to).unwrap(),
+                ),
+            );
+        }
+        let mut t = SubstitutionTiling::new(rule, vec![triangle]);
+        t.add_seed(PlacedTile::new(
+            0,
+            tessellations::tessellationline::Transform::identity(),
+        ));
+        assert_eq!(t.tiles(4).len(), 16);
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use tessellations::render::*;
+    use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane, Texture};
+    use tessellations::tessellationline::{Point, Vector};
+
+    /// A 2 by 2 texture of one color covering the tile
+    fn texture(rgba: [u8; 4], rotation: Option<usize>) -> Texture {
+        let mut t = Texture::from_rgba(
+            2,
+            2,
+            &rgba.repeat(4),
+            Point::new(-1.0, -1.0),
+            Vector::new(2.0, 2.0),
+        )
+        .unwrap();
+        t.rotation = rotation;
+        t
+    }
+
+    fn center(scene: &Scene, tile: &SceneTile) -> Point {
+        let outline = &scene.outlines[tile.outline];
+        let c = outline
+            .iter()
+            .fold(Vector::zero(), |a, p| a + p.to_vector())
+            / outline.len() as f32;
+        tile.transform
+            .then(&scene.view)
+            .transform_point(c.to_point())
+    }
+
+    #[test]
+    fn test_texture_per_rotation_class() {
+        let mut f = TessellationFigure::square90();
+        f.add_texture(texture([0xff, 0x0, 0x0, 0xff], None));
+        f.add_texture(texture([0x0, 0x0, 0xff, 0xff], Some(1)));
+        let scene = Scene::plane_with_options(&TessellationPlane {}, &f, &RenderOptions::default());
+        let image = Backend.render_scene(&scene).unwrap();
+        let mut checked = [0, 0];
+        for tile in &scene.tiles {
+            let c = center(&scene, tile);
+            if c.x < 1.0 || c.y < 1.0 || c.x >= scene.width || c.y >= scene.height {
+                continue;
+            }
+            let pixel = image.get_data()[c.y as usize * 400 + c.x as usize];
+            if tile.rotation == 1 {
+                assert_eq!(pixel, 0xff0000ff);
+                checked[1] += 1;
+            } else {
+                assert_eq!(pixel, 0xffff0000);
+                checked[0] += 1;
+            }
+        }
+        assert!(checked[0] > 0 && checked[1] > 0);
+
+        let svg = SVGBackend.compose_scene(&scene).unwrap().get_data();
+        assert!(svg.contains("id=\"texture-0\""));
+        assert!(svg.contains("id=\"texture-1\""));
+        assert!(svg.contains("href=\"data:image/png;base64,"));
+        assert!(svg.contains("fill=\"url(#texture-1)\""));
+    }
+
+    #[test]
+    fn test_textures_in_the_document() {
+        let mut f = TessellationFigure::square();
+        f.add_texture(texture([0x10, 0x20, 0x30, 0xff], Some(0)));
+        let json = serde_json::to_string(&f).unwrap();
+        let g: TessellationFigure = serde_json::from_str(&json).unwrap();
+        assert_eq!(g.textures, f.textures);
+        assert_eq!(g.texture(0), Some(&f.textures[0]));
+        assert_eq!(g.texture(1), None);
+        // documents without textures do not change
+        let json = serde_json::to_string(&TessellationFigure::square()).unwrap();
+        assert!(!json.contains("textures"));
+    }
+}
@@ -9,19 +9,76 @@ use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 
+use euclid::Angle;
 use raqote::*;
 use tessellations::render::*;
-use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+use tessellations::tessellationfigure::{
+    Decoration, DecorationIndexPath, PointSelection, TessellationFigure, TessellationPlane,
+};
 use tessellations::tessellationline::PointIndexPath;
+use tessellations::tessellationparametric::ParametricFigure;
+use tessellations::tessellationstyle::Style;
 
 pub fn draw(ctx: &web_sys::HtmlElement, _: u32, _: u32, f: &TessellationFigure) {
     let backend = Box::new(SVGBackend);
     let m: Transform = Transform::scale(100.0, 100.0).then_translate(euclid::vec2(100.0, 100.0));
     let p = TessellationPlane {};
-    let svg_document = backend.compose_plane(&p, f, &m).unwrap();
+    let svg_document = backend.compose_plane(&p, f, &m, &Style::default()).unwrap();
     ctx.set_inner_html(&svg_document.get_data());
 }
 
+/// Add a slider for every parameter of the parametric figure
+fn parameter_sliders(
+    container: &web_sys::HtmlElement,
+    context: &Rc<web_sys::HtmlElement>,
+    figure: &Rc<RefCell<TessellationFigure>>,
+    parametric: &Rc<RefCell<Option<ParametricFigure>>>,
+) -> Result<(), JsValue> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    container.set_inner_html("");
+    let parameters = match parametric.borrow().as_ref() {
+        Some(pf) => pf.parameters.clone(),
+        None => return Ok(()),
+    };
+    for parameter in parameters {
+        let label = document.create_element("label")?;
+        label.set_text_content(Some(&parameter.name));
+        let input = document
+            .create_element("input")?
+            .dyn_into::<web_sys::HtmlInputElement>()?;
+        input.set_type("range");
+        input.set_min(&parameter.min.to_string());
+        input.set_max(&parameter.max.to_string());
+        input.set_step(&((parameter.max - parameter.min) / 100.0).to_string());
+        input.set_value(&parameter.value.to_string());
+
+        let context = context.clone();
+        let figure_cloned = figure.clone();
+        let parametric_cloned = parametric.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::Event| {
+            let input = event
+                .target()
+                .unwrap()
+                .dyn_into::<web_sys::HtmlInputElement>()
+                .unwrap();
+            if let Some(pf) = parametric_cloned.borrow_mut().as_mut() {
+                pf.set(&parameter.name, input.value_as_number() as f32);
+                if let Ok(pfigure) = pf.figure() {
+                    let mut f = figure_cloned.borrow_mut();
+                    f.load(pfigure);
+                    draw(&context, 400, 400, &f);
+                }
+            }
+        }) as Box<dyn FnMut(_)>);
+        input.add_event_listener_with_callback("input", closure.as_ref().unchecked_ref())?;
+        closure.forget();
+
+        label.append_child(&input)?;
+        container.append_child(&label)?;
+    }
+    Ok(())
+}
+
 fn app(name: &str) -> Result<(), JsValue> {
     let document = web_sys::window().unwrap().document().unwrap();
     let editor = document
@@ -33,12 +90,20 @@ fn app(name: &str) -> Result<(), JsValue> {
         .unwrap()
         .dyn_into::<web_sys::HtmlElement>()?;
     let _ = editor.append_child(&context);
+    let sliders = document
+        .get_element_by_id("parameters")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlElement>()?;
 
     let m1: Transform = Transform::scale(100.0, 100.0).then_translate(euclid::vec2(100.0, 100.0));
     let mi = m1.inverse().unwrap();
     let figure: Rc<RefCell<TessellationFigure>> =
         Rc::new(RefCell::new(TessellationFigure::triangle()));
-    let selected_point_index: Rc<Cell<Option<PointIndexPath>>> = Rc::new(Cell::new(None));
+    let parametric: Rc<RefCell<Option<ParametricFigure>>> = Rc::new(RefCell::new(None));
+    let selection: Rc<RefCell<PointSelection>> = Rc::new(RefCell::new(PointSelection::new()));
+    let lasso: Rc<RefCell<Option<Vec<Point>>>> = Rc::new(RefCell::new(None));
+    let last_point: Rc<Cell<Option<Point>>> = Rc::new(Cell::new(None));
+    let selected_decoration: Rc<Cell<Option<DecorationIndexPath>>> = Rc::new(Cell::new(None));
 
     let context = Rc::new(context);
     let pressed = Rc::new(Cell::new(false));
@@ -49,28 +114,41 @@ fn app(name: &str) -> Result<(), JsValue> {
         let context = context.clone();
         let pressed = pressed.clone();
         let figure_cloned = figure.clone();
-        let selected_point_index_cloned = selected_point_index.clone();
+        let selection_cloned = selection.clone();
+        let lasso_cloned = lasso.clone();
+        let last_point_cloned = last_point.clone();
+        let selected_decoration_cloned = selected_decoration.clone();
 
         let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
             let p =
                 mi.transform_point(Point::new(event.offset_x() as f32, event.offset_y() as f32));
             let mut f = figure_cloned.borrow_mut();
-            let s = match f.hitpoints(p, 0.05) {
-                Some(h) => Some(h),
-                _ => match f.hitline(p, 0.05) {
-                    Some(h) => {
-                        f.insert(h, p);
-                        draw(&context, 400, 400, &f);
-                        Some(PointIndexPath {
-                            line_index: h.line_index,
-                            point_index: h.point_index + 1,
-                            corrp: h.corrp,
-                        })
+            let mut selection = selection_cloned.borrow_mut();
+            match f.hitpoints(p, 0.05) {
+                Some(h) => {
+                    if event.shift_key() {
+                        selection.toggle(h);
+                    } else if !selection.contains(h) {
+                        *selection = PointSelection::from(h);
                     }
-                    _ => None,
+                }
+                _ => match f.hitdecoration(p, 0.05) {
+                    Some(h) => selected_decoration_cloned.set(Some(h)),
+                    _ => match f.hitline(p, 0.05) {
+                        Some(h) => {
+                            f.insert(h, p);
+                            draw(&context, 400, 400, &f);
+                            *selection = PointSelection::from(PointIndexPath {
+                                line_index: h.line_index,
+                                point_index: h.point_index + 1,
+                                corrp: h.corrp,
+                            });
+                        }
+                        _ => *lasso_cloned.borrow_mut() = Some(vec![p]),
+                    },
                 },
             };
-            selected_point_index_cloned.set(s);
+            last_point_cloned.set(Some(p));
             pressed.set(true);
         }) as Box<dyn FnMut(_)>);
         editor.add_event_listener_with_callback("mousedown", closure.as_ref().unchecked_ref())?;
@@ -81,7 +159,10 @@ fn app(name: &str) -> Result<(), JsValue> {
         let context = context.clone();
         let pressed = pressed.clone();
         let figure_cloned = figure.clone();
-        let selected_point_index_cloned = selected_point_index;
+        let selection_cloned = selection.clone();
+        let lasso_cloned = lasso.clone();
+        let last_point_cloned = last_point.clone();
+        let selected_decoration_cloned = selected_decoration.clone();
 
         let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
             let mut f = figure_cloned.borrow_mut();
@@ -90,10 +171,16 @@ fn app(name: &str) -> Result<(), JsValue> {
                 let p = mi
                     .transform_point(Point::new(event.offset_x() as f32, event.offset_y() as f32));
 
-                if let Some(h) = selected_point_index_cloned.get() {
-                    f.update(h, p);
+                if let Some(l) = lasso_cloned.borrow_mut().as_mut() {
+                    l.push(p);
+                } else if let Some(h) = selected_decoration_cloned.get() {
+                    f.update_decoration(h, p);
+                    draw(&context, 400, 400, &f);
+                } else if let Some(last) = last_point_cloned.get() {
+                    f.translate(&selection_cloned.borrow(), p - last);
                     draw(&context, 400, 400, &f);
                 }
+                last_point_cloned.set(Some(p));
             }
         }) as Box<dyn FnMut(_)>);
         editor.add_event_listener_with_callback("mousemove", closure.as_ref().unchecked_ref())?;
@@ -101,8 +188,24 @@ fn app(name: &str) -> Result<(), JsValue> {
     }
 
     {
-        let pressed = pressed;
-        let closure = Closure::wrap(Box::new(move |_event: web_sys::MouseEvent| {
+        let figure_cloned = figure.clone();
+        let selection_cloned = selection.clone();
+        let closure = Closure::wrap(Box::new(move |event: web_sys::MouseEvent| {
+            // rubber band select, or lasso select when control is held
+            if let Some(l) = lasso.borrow_mut().take() {
+                let f = figure_cloned.borrow();
+                let mut selection = selection_cloned.borrow_mut();
+                if !event.shift_key() {
+                    selection.clear();
+                }
+                if event.ctrl_key() {
+                    selection.extend(f.hitpolygon(&l));
+                } else if let Some(&last) = l.last() {
+                    selection.extend(f.hitrect(l[0], last));
+                }
+            }
+            last_point.set(None);
+            selected_decoration.set(None);
             pressed.set(false);
         }) as Box<dyn FnMut(_)>);
         editor.add_event_listener_with_callback("mouseup", closure.as_ref().unchecked_ref())?;
@@ -114,10 +217,39 @@ fn app(name: &str) -> Result<(), JsValue> {
         let figure_cloned = figure.clone();
         let closure = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
             let mut f = figure_cloned.borrow_mut();
+            let mut selection = selection.borrow_mut();
             if event.key() == "1" {
                 f.load(TessellationFigure::brick());
+                selection.clear();
+                *parametric.borrow_mut() = None;
+                let _ = parameter_sliders(&sliders, &context, &figure_cloned, &parametric);
             } else if event.key() == "2" {
                 f.load(TessellationFigure::triangle());
+                selection.clear();
+                *parametric.borrow_mut() = None;
+                let _ = parameter_sliders(&sliders, &context, &figure_cloned, &parametric);
+            } else if event.key() == "3" {
+                let pf = ParametricFigure::bulge();
+                f.load(pf.figure().unwrap());
+                selection.clear();
+                *parametric.borrow_mut() = Some(pf);
+                let _ = parameter_sliders(&sliders, &context, &figure_cloned, &parametric);
+            } else if event.key() == "4" {
+                f.load(TessellationFigure::two_triangles());
+                selection.clear();
+                *parametric.borrow_mut() = None;
+                let _ = parameter_sliders(&sliders, &context, &figure_cloned, &parametric);
+            } else if event.key() == "d" {
+                let center = f.center();
+                f.add_decoration(Decoration::eye(center, 0.1));
+            } else if event.key() == "[" {
+                f.rotate(&selection, Angle::degrees(-15.0));
+            } else if event.key() == "]" {
+                f.rotate(&selection, Angle::degrees(15.0));
+            } else if event.key() == "-" {
+                f.scale(&selection, 0.9);
+            } else if event.key() == "=" {
+                f.scale(&selection, 1.1);
             }
             draw(&context, 400, 400, &f);
         }) as Box<dyn FnMut(_)>);
@@ -6,17 +6,17 @@ struct Globals {
 
 struct Primitive {
     color: vec4<f32>,
-    translate: vec2<f32>,
+    transform: mat3x2<f32>,
     z_index: i32,
     width: f32,
-    angle: f32,
     scale: f32,
     pad1: i32,
     pad2: i32,
+    pad3: i32,
 };
 
 struct Primitives {
-    primitives: array<Primitive, 1356>,
+    primitives: array<Primitive, 32768>,
 };
 
 @group(0) @binding(0) var<uniform> globals: Globals;
@@ -38,13 +38,9 @@ fn main(
 
     var invert_y = vec2<f32>(1.0, -1.0);
 
-    var rotation = mat2x2<f32>(
-        vec2<f32>(cos(prim.angle), -sin(prim.angle)),
-        vec2<f32>(sin(prim.angle), cos(prim.angle))
-    );
-
-    var local_pos = (a_position * prim.scale + a_normal * prim.width) * rotation;
-    var world_pos = local_pos - globals.scroll_offset + prim.translate;
+    var local_pos = a_position * prim.scale + a_normal * prim.width;
+    var linear = mat2x2<f32>(prim.transform[0], prim.transform[1]);
+    var world_pos = linear * local_pos + prim.transform[2] * prim.scale - globals.scroll_offset;
     var transformed_pos = world_pos * globals.zoom / (0.5 * globals.resolution) * invert_y;
 
     var z = f32(prim.z_index) / 4096.0;
@@ -1,14 +1,13 @@
 use std::fs;
+use std::ops::Range;
 
 use lyon::math::*;
 use lyon::tessellation;
 use lyon::tessellation::geometry_builder::*;
-use lyon::tessellation::{FillOptions, FillTessellator};
-use lyon::tessellation::{StrokeOptions, StrokeTessellator};
-use palette::{FromColor, Hsl, Srgb};
-use rand::prelude::*;
 use winit::dpi::PhysicalSize;
-use winit::event::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent};
+use winit::event::{
+    ElementState, Event, KeyboardInput, ModifiersState, MouseButton, VirtualKeyCode, WindowEvent,
+};
 use winit::event_loop::{ControlFlow, EventLoop};
 use winit::window::{Window, WindowBuilder};
 
@@ -18,12 +17,23 @@ use wgpu::util::DeviceExt;
 use futures::executor::block_on;
 
 use tessellations::render::*;
-use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+use tessellations::tessellationfigure::{
+    Decoration, DecorationIndexPath, PointSelection, TessellationFigure, TessellationPlane,
+};
 use tessellations::tessellationline::PointIndexPath;
+use tessellations::tessellationparametric::ParametricFigure;
+use tessellations::tessellationstyle::{generate_palette, LineCap, LineJoin, Style};
 
 //use log;
 
-const PRIM_BUFFER_LEN: usize = 1356;
+const PRIM_BUFFER_LEN: usize = 32768;
+
+/// Most decorations on a figure, every one takes two blocks of primitives
+const MAX_DECORATIONS: usize = 16;
+
+/// Corners in tile units of the region of the plane drawn around the tile
+const PLANE_MIN: (f32, f32) = (-8.0, -8.0);
+const PLANE_MAX: (f32, f32) = (8.0, 8.0);
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -51,25 +61,22 @@ unsafe impl bytemuck::Zeroable for GpuVertex {}
 #[derive(Copy, Clone)]
 struct Primitive {
     color: [f32; 4],
-    translate: [f32; 2],
+    /// affine map of the copy, the translation in tile units
+    transform: [f32; 6],
     z_index: i32,
     width: f32,
-    angle: f32,
     scale: f32,
-    _pad1: i32,
-    _pad2: i32,
+    _pad: [i32; 3],
 }
 
 impl Primitive {
     const DEFAULT: Self = Primitive {
         color: [0.0; 4],
-        translate: [0.0; 2],
+        transform: [1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
         z_index: 0,
         width: 0.0,
-        angle: 0.0,
         scale: 1.0,
-        _pad1: 0,
-        _pad2: 0,
+        _pad: [0; 3],
     };
 }
 
@@ -121,9 +128,15 @@ fn main() {
     println!("  PgUp/PgDown: zoom in/out");
     println!("  b: toggle drawing the background");
     println!("  a/z: increase/decrease the stroke width");
+    println!("  d: add a decoration");
+    println!("  [/]: rotate the selected points");
+    println!("  -/=: scale the selected points");
 
-    let mut palette: Vec<Srgb<f32>> = generate_palette();
-    eprintln!("{:?}", palette);
+    let mut style = Style {
+        palette: generate_palette(rand::random()),
+        ..Style::default()
+    };
+    eprintln!("{:?}", style.palette);
     // add tessellation square
     let mut f = TessellationFigure::triangle();
     let plane = TessellationPlane {};
@@ -153,7 +166,13 @@ fn main() {
 
         drag_start: None,
         mouse_position: None,
-        selected_point_index: None,
+        modifiers: ModifiersState::default(),
+        selection: PointSelection::new(),
+        lasso: None,
+        last_point: None,
+        selected_decoration: None,
+        parametric: None,
+        parameter: 0,
     };
 
     let event_loop = EventLoop::new();
@@ -200,8 +219,6 @@ fn main() {
         mapped_at_creation: false,
     });
 
-
-
     let vs_module = &device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("Geometry vs"),
         source: wgpu::ShaderSource::Wgsl(include_str!("./../shaders/geometry.vs.wgsl").into()),
@@ -228,7 +245,7 @@ fn main() {
                 binding: 1,
                 visibility: wgpu::ShaderStages::VERTEX,
                 ty: wgpu::BindingType::Buffer {
-                    ty: wgpu::BufferBindingType::Storage {read_only:true},
+                    ty: wgpu::BufferBindingType::Storage { read_only: true },
                     has_dynamic_offset: false,
                     min_binding_size: wgpu::BufferSize::new(prim_buffer_byte_size),
                 },
@@ -270,7 +287,7 @@ fn main() {
         bias: wgpu::DepthBiasState::default(),
     });
 
-    let mut render_pipeline_descriptor = wgpu::RenderPipelineDescriptor {
+    let render_pipeline_descriptor = wgpu::RenderPipelineDescriptor {
         label: None,
         layout: Some(&pipeline_layout),
         vertex: wgpu::VertexState {
@@ -327,10 +344,6 @@ fn main() {
 
     let render_pipeline = device.create_render_pipeline(&render_pipeline_descriptor);
 
-    // TODO: this isn't what we want: we'd need the equivalent of VK_POLYGON_MODE_LINE,
-    // but it doesn't seem to be exposed by wgpu?
-    render_pipeline_descriptor.primitive.topology = wgpu::PrimitiveTopology::LineList;
-
     let swapchain_capabilities = surface.get_capabilities(&adapter);
     let size = window.inner_size();
 
@@ -353,14 +366,7 @@ fn main() {
     window.request_redraw();
 
     event_loop.run(move |event, _, control_flow| {
-        if !update_inputs(
-            event,
-            &window,
-            control_flow,
-            &mut scene,
-            &mut f,
-            &mut palette,
-        ) {
+        if !update_inputs(event, &window, control_flow, &mut scene, &mut f, &mut style) {
             // keep polling inputs.
             return;
         }
@@ -423,37 +429,85 @@ fn main() {
             label: Some("Encoder"),
         });
 
-        let mut geometry: VertexBuffers<GpuVertex, u16> = VertexBuffers::new();
-
-        let mut fill_tess = FillTessellator::new();
-        let mut stroke_tess = StrokeTessellator::new();
+        let mut geometry: VertexBuffers<GpuVertex, u32> = VertexBuffers::new();
 
         let lb = Box::new(LyonBackend);
         let path = lb.build(&f).unwrap();
-        let grid = lb.build_plane(&plane, &f, &palette);
-        //println!("{}",grid.len());
-        fill_tess
-            .tessellate_path(
-                &path,
-                &FillOptions::tolerance(tolerance).with_fill_rule(tessellation::FillRule::NonZero),
-                &mut BuffersBuilder::new(&mut geometry, WithId(fill_prim_id as u32)),
-            )
-            .unwrap();
-
-        let fill_range = 0..(geometry.indices.len() as u32);
-
-        stroke_tess
-            .tessellate_path(
-                &path,
-                &StrokeOptions::tolerance(tolerance)
-                    .with_line_cap(lyon::path::LineCap::Square)
-                    .with_line_join(lyon::path::LineJoin::Bevel)
-                    .with_miter_limit(1.0),
-                &mut BuffersBuilder::new(&mut geometry, WithId(stroke_prim_id as u32)),
-            )
-            .unwrap();
-
-        let stroke_range = fill_range.end..(geometry.indices.len() as u32);
+        let plane_mesh = lb.tessellate_plane(
+            &plane,
+            &f,
+            PLANE_MIN.into(),
+            PLANE_MAX.into(),
+            &style,
+            &MeshOptions::new(tolerance),
+        );
+        // every prototile is filled with its own block of primitives, the
+        // edited tile followed by its instances on the plane
+        let tile_count = plane_mesh.tiles.len();
+        // the instances nearest the edited tile that fit the primitives, a
+        // large figure or a fine lattice leaves the rest of the plane out
+        let max_instances = (PRIM_BUFFER_LEN - fill_prim_id) / tile_count.max(1) - 1;
+        let instances: Vec<Vec<MeshInstance>> = plane_mesh
+            .tiles
+            .iter()
+            .map(|tile| {
+                let range = tile.instances.start as usize..tile.instances.end as usize;
+                let mut instances = plane_mesh.instances[range].to_vec();
+                let distance = |i: &MeshInstance| i.transform[4].hypot(i.transform[5]);
+                instances.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+                instances.truncate(max_instances);
+                instances
+            })
+            .collect();
+        let tile_len = 1 + instances.iter().map(Vec::len).max().unwrap_or(0);
+        let fill_ranges: Vec<_> = plane_mesh
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(k, tile)| {
+                push_mesh(
+                    &mut geometry,
+                    &tile.fill,
+                    (fill_prim_id + k * tile_len) as u32,
+                )
+            })
+            .collect();
+
+        let stroke_options = MeshOptions {
+            join: LineJoin::Bevel,
+            cap: LineCap::Square,
+            ..MeshOptions::new(tolerance)
+        };
+        let stroke_range = push_mesh(
+            &mut geometry,
+            &lb.stroke_mesh(&path, &stroke_options),
+            stroke_prim_id as u32,
+        );
+
+        // decorations repeat with every copy of the first prototile, each with
+        // a block of fill and a block of stroke primitives after the fills
+        let decoration_options = MeshOptions {
+            join: LineJoin::Round,
+            cap: LineCap::Round,
+            ..MeshOptions::new(tolerance)
+        };
+        let decoration_prim_id = fill_prim_id + tile_count * tile_len;
+        let decoration_count = f
+            .decorations
+            .len()
+            .min(MAX_DECORATIONS)
+            .min(PRIM_BUFFER_LEN.saturating_sub(decoration_prim_id) / (2 * tile_len));
+        let decoration_paths = lb.build_decorations(&f);
+        for (i, decoration_path) in decoration_paths.iter().take(decoration_count).enumerate() {
+            let prim_id = (decoration_prim_id + 2 * i * tile_len) as u32;
+            if f.decorations[i].fill.is_some() {
+                let mesh = lb.fill_mesh(decoration_path, &decoration_options);
+                push_mesh(&mut geometry, &mesh, prim_id);
+            }
+            let mesh = lb.stroke_mesh(decoration_path, &decoration_options);
+            push_mesh(&mut geometry, &mesh, prim_id + tile_len as u32);
+        }
+        let decoration_range = stroke_range.end..(geometry.indices.len() as u32);
 
         let mut cpu_primitives = Vec::with_capacity(PRIM_BUFFER_LEN);
         for _ in 0..PRIM_BUFFER_LEN {
@@ -461,8 +515,6 @@ fn main() {
                 color: [1.0, 0.0, 0.0, 1.0],
                 z_index: 0,
                 width: 0.0,
-                translate: [0.0, 0.0],
-                angle: 0.0,
                 ..Primitive::DEFAULT
             });
         }
@@ -476,27 +528,60 @@ fn main() {
             ..Primitive::DEFAULT
         };
 
-        // Main fill primitive
-        cpu_primitives[fill_prim_id] = Primitive {
-            color: [0.0, 1.0, 1.0, 1.0],
-            z_index: 1,
-            ..Primitive::DEFAULT
-        };
-
-        if scene.draw_background {
-            // grid stuff
-            for (i, p) in grid.iter().enumerate() {
-                cpu_primitives[fill_prim_id + i] = Primitive {
-                    color: [p.r, p.g, p.b, 1.0],
-                    translate: [p.x * 100.0 -100.0, p.y * 100.0],
+        for (k, tile_instances) in instances.iter().enumerate() {
+            // Main fill primitive of the prototile
+            let start = fill_prim_id + k * tile_len;
+            cpu_primitives[start] = Primitive {
+                color: [0.0, 1.0, 1.0, 1.0],
+                z_index: 1,
+                scale: 100.0,
+                ..Primitive::DEFAULT
+            };
+            // the instances follow the main primitive of their prototile
+            for (i, instance) in tile_instances.iter().enumerate() {
+                cpu_primitives[start + 1 + i] = Primitive {
+                    color: instance.color,
+                    transform: instance.transform,
                     z_index: 1,
-                    angle: p.angle,
                     scale: 100.0,
                     ..Primitive::DEFAULT
                 };
             }
         }
-        let figure_count = 1 + grid.len() as u32;
+
+        for (i, decoration) in f.decorations.iter().take(decoration_count).enumerate() {
+            let color = |c: tessellations::tessellationstyle::Color| {
+                [
+                    c.r as f32 / 255.0,
+                    c.g as f32 / 255.0,
+                    c.b as f32 / 255.0,
+                    c.opacity(),
+                ]
+            };
+            let fill = Primitive {
+                color: decoration.fill.map(color).unwrap_or_default(),
+                z_index: 2,
+                ..Primitive::DEFAULT
+            };
+            let stroke = Primitive {
+                color: decoration.stroke.map(color).unwrap_or_default(),
+                z_index: 3,
+                width: decoration.stroke_width * 100.0 / 2.0,
+                ..Primitive::DEFAULT
+            };
+            // placed like the fills of the first prototile
+            let start = decoration_prim_id + 2 * i * tile_len;
+            for j in 0..tile_len {
+                let copy = cpu_primitives[fill_prim_id + j];
+                let placed = |p: Primitive| Primitive {
+                    transform: copy.transform,
+                    scale: copy.scale,
+                    ..p
+                };
+                cpu_primitives[start + j] = placed(fill);
+                cpu_primitives[start + tile_len + j] = placed(stroke);
+            }
+        }
 
         let vbo = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: None,
@@ -569,11 +654,23 @@ fn main() {
 
             pass.set_pipeline(&render_pipeline);
             pass.set_bind_group(0, &bind_group, &[]);
-            pass.set_index_buffer(ibo.slice(..), wgpu::IndexFormat::Uint16);
+            pass.set_index_buffer(ibo.slice(..), wgpu::IndexFormat::Uint32);
             pass.set_vertex_buffer(0, vbo.slice(..));
 
             pass.draw_indexed(stroke_range.clone(), 0, 0..1);
-            pass.draw_indexed(fill_range.clone(), 0, 0..figure_count);
+            // the edited tile alone or with its instances on the plane
+            let copies = |tile_instances: &Vec<MeshInstance>| {
+                if scene.draw_background {
+                    1 + tile_instances.len()
+                } else {
+                    1
+                }
+            };
+            for (fill_range, tile_instances) in fill_ranges.iter().zip(&instances) {
+                pass.draw_indexed(fill_range.clone(), 0, 0..copies(tile_instances) as u32);
+            }
+            let copies = instances.first().map_or(1, copies);
+            pass.draw_indexed(decoration_range.clone(), 0, 0..copies as u32);
         }
 
         queue.submit(Some(encoder.finish()));
@@ -581,28 +678,26 @@ fn main() {
     });
 }
 
-/// This vertex constructor forwards the positions and normals provided by the
-/// tessellators and add a shape id.
-pub struct WithId(pub u32);
-
-impl FillVertexConstructor<GpuVertex> for WithId {
-    fn new_vertex(&mut self, vertex: tessellation::FillVertex) -> GpuVertex {
-        GpuVertex {
-            position: vertex.position().to_array(),
-            normal: [0.0, 0.0],
-            prim_id: self.0,
-        }
-    }
-}
-
-impl StrokeVertexConstructor<GpuVertex> for WithId {
-    fn new_vertex(&mut self, vertex: tessellation::StrokeVertex) -> GpuVertex {
-        GpuVertex {
-            position: vertex.position_on_path().to_array(),
-            normal: vertex.normal().to_array(),
-            prim_id: self.0,
-        }
-    }
+/// Appends the mesh to the geometry drawn with the primitive `prim_id` and
+/// returns the range of its indices
+fn push_mesh(
+    geometry: &mut VertexBuffers<GpuVertex, u32>,
+    mesh: &Mesh,
+    prim_id: u32,
+) -> Range<u32> {
+    let start = geometry.indices.len() as u32;
+    let base = geometry.vertices.len() as u32;
+    geometry
+        .vertices
+        .extend(mesh.vertices.iter().map(|v| GpuVertex {
+            position: v.position,
+            normal: v.normal,
+            prim_id,
+        }));
+    geometry
+        .indices
+        .extend(mesh.indices.iter().map(|&i| base + i));
+    start..geometry.indices.len() as u32
 }
 
 pub struct Custom;
@@ -629,7 +724,15 @@ struct SceneParams {
     render: bool,
     drag_start: Option<(f32, f32)>,
     mouse_position: Option<(f32, f32)>,
-    selected_point_index: Option<PointIndexPath>,
+    modifiers: ModifiersState,
+    selection: PointSelection,
+    lasso: Option<Vec<Point>>,
+    last_point: Option<Point>,
+    selected_decoration: Option<DecorationIndexPath>,
+    /// the figure is evaluated from it while set
+    parametric: Option<ParametricFigure>,
+    /// index of the parameter changed with , and .
+    parameter: usize,
 }
 
 fn update_inputs(
@@ -638,7 +741,7 @@ fn update_inputs(
     control_flow: &mut ControlFlow,
     scene: &mut SceneParams,
     figure: &mut TessellationFigure,
-    palette: &mut Vec<Srgb<f32>>,
+    style: &mut Style,
 ) -> bool {
     let mpx = (window.inner_size().width as f32) / 2.0;
     let mpy = (window.inner_size().height as f32) / 2.0;
@@ -689,21 +792,29 @@ fn update_inputs(
                 match figure.hitpoints(p, 0.05) {
                     Some(h) => {
                         //println!("hit ");
-                        scene.selected_point_index = Some(h)
-                    }
-                    _ => match figure.hitline(p, 0.05) {
-                        Some(h) => {
-                            //println!("breakline");
-                            figure.insert(h, p);
-                            scene.selected_point_index = Some(PointIndexPath {
-                                line_index: h.line_index,
-                                point_index: h.point_index + 1,
-                                corrp: h.corrp,
-                            });
+                        if scene.modifiers.shift() {
+                            scene.selection.toggle(h);
+                        } else if !scene.selection.contains(h) {
+                            scene.selection = PointSelection::from(h);
                         }
-                        _ => scene.selected_point_index = None,
+                    }
+                    _ => match figure.hitdecoration(p, 0.05) {
+                        Some(h) => scene.selected_decoration = Some(h),
+                        _ => match figure.hitline(p, 0.05) {
+                            Some(h) => {
+                                //println!("breakline");
+                                figure.insert(h, p);
+                                scene.selection = PointSelection::from(PointIndexPath {
+                                    line_index: h.line_index,
+                                    point_index: h.point_index + 1,
+                                    corrp: h.corrp,
+                                });
+                            }
+                            _ => scene.lasso = Some(vec![p]),
+                        },
                     },
                 };
+                scene.last_point = Some(p);
             }
         }
         Event::WindowEvent {
@@ -715,7 +826,26 @@ fn update_inputs(
                 },
             ..
         } => {
+            // rubber band select, or lasso select when control is held
+            if let Some(l) = scene.lasso.take() {
+                if !scene.modifiers.shift() {
+                    scene.selection.clear();
+                }
+                if scene.modifiers.ctrl() {
+                    scene.selection.extend(figure.hitpolygon(&l));
+                } else if let Some(&last) = l.last() {
+                    scene.selection.extend(figure.hitrect(l[0], last));
+                }
+            }
             scene.drag_start = None;
+            scene.last_point = None;
+            scene.selected_decoration = None;
+        }
+        Event::WindowEvent {
+            event: WindowEvent::ModifiersChanged(modifiers),
+            ..
+        } => {
+            scene.modifiers = modifiers;
         }
         Event::WindowEvent {
             event: WindowEvent::CursorMoved { position, .. },
@@ -733,10 +863,16 @@ fn update_inputs(
                 if let Some(d) = scene.drag_start {
                     //println!("dragging");
                     if d != mouse {
-                        if let Some(h) = scene.selected_point_index {
-                            figure.update(h, p);
+                        if let Some(l) = scene.lasso.as_mut() {
+                            l.push(p);
+                        } else if let Some(h) = scene.selected_decoration {
+                            figure.update_decoration(h, p);
+                            window.request_redraw();
+                        } else if let Some(last) = scene.last_point {
+                            figure.translate(&scene.selection, p - last);
                             window.request_redraw();
                         }
+                        scene.last_point = Some(p);
                     }
                 };
                 scene.mouse_position = Some(mouse);
@@ -789,8 +925,29 @@ fn update_inputs(
             VirtualKeyCode::Z => {
                 scene.target_stroke_width *= 0.8;
             }
+            // further decorations would not fit the primitives
+            VirtualKeyCode::D if figure.decorations.len() < MAX_DECORATIONS => {
+                figure.add_decoration(Decoration::eye(figure.center(), 0.1));
+                window.request_redraw();
+            }
+            VirtualKeyCode::LBracket => {
+                figure.rotate(&scene.selection, euclid::Angle::degrees(-15.0));
+                window.request_redraw();
+            }
+            VirtualKeyCode::RBracket => {
+                figure.rotate(&scene.selection, euclid::Angle::degrees(15.0));
+                window.request_redraw();
+            }
+            VirtualKeyCode::Minus => {
+                figure.scale(&scene.selection, 0.9);
+                window.request_redraw();
+            }
+            VirtualKeyCode::Equals => {
+                figure.scale(&scene.selection, 1.1);
+                window.request_redraw();
+            }
             VirtualKeyCode::R => {
-                *palette = generate_palette();
+                style.palette = generate_palette(rand::random());
                 window.request_redraw();
             }
             VirtualKeyCode::S => {
@@ -809,6 +966,8 @@ fn update_inputs(
                         .as_str(),
                 )
                 .expect("json error");
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::W => {
@@ -817,33 +976,82 @@ fn update_inputs(
                 let m: Transform =
                     Transform::scale(100.0, 100.0).then_translate(euclid::vec2(100.0, 100.0));
 
-                let svg = svgbackend.compose_plane(&p, &figure, &m).unwrap();
+                let svg = svgbackend.compose_plane(&p, figure, &m, style).unwrap();
                 svg.save_svg(std::path::Path::new("out.svg"));
             }
             VirtualKeyCode::Key1 => {
                 *figure = TessellationFigure::square();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::Key2 => {
                 *figure = TessellationFigure::triangle();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::Key3 => {
                 *figure = TessellationFigure::square90();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::Key4 => {
                 *figure = TessellationFigure::diamond();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::Key5 => {
                 *figure = TessellationFigure::brick();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
             VirtualKeyCode::Key6 => {
                 *figure = TessellationFigure::hexagon();
+                scene.selection.clear();
+                scene.parametric = None;
+                window.request_redraw();
+            }
+            VirtualKeyCode::Key7 => {
+                *figure = TessellationFigure::two_triangles();
+                scene.selection.clear();
+                scene.parametric = None;
                 window.request_redraw();
             }
+            VirtualKeyCode::Key8 => {
+                let pf = ParametricFigure::bulge();
+                *figure = pf.figure().unwrap();
+                scene.selection.clear();
+                scene.parametric = Some(pf);
+                scene.parameter = 0;
+                window.request_redraw();
+            }
+            VirtualKeyCode::Tab => {
+                if let Some(pf) = &scene.parametric {
+                    if !pf.parameters.is_empty() {
+                        scene.parameter = (scene.parameter + 1) % pf.parameters.len();
+                        println!("parameter {}", pf.parameters[scene.parameter].name);
+                    }
+                }
+            }
+            VirtualKeyCode::Comma | VirtualKeyCode::Period => {
+                let steps = if key == VirtualKeyCode::Comma {
+                    -5.0
+                } else {
+                    5.0
+                };
+                if let Some(pf) = scene.parametric.as_mut() {
+                    if pf.step(scene.parameter, steps) {
+                        if let Ok(f) = pf.figure() {
+                            *figure = f;
+                            window.request_redraw();
+                        }
+                    }
+                }
+            }
             _key => {}
         },
         _evt => {
@@ -861,30 +1069,3 @@ fn update_inputs(
 
     true
 }
-
-fn generate_palette() -> Vec<Srgb<f32>> {
-    let mut rng: ThreadRng = rand::thread_rng();
-
-    // Generate a random base hue
-    let base_hue = rng.gen_range(0..360) as f32;
-    let mut palette = Vec::<Srgb<f32>>::new();
-    // Calculate three analogous hues
-    let hue_shift = 30.0; // You can adjust this value for different analogous colors
-    let analogous_hues = vec![
-        (base_hue + hue_shift) % 360.0,
-        (base_hue + 2.0 * hue_shift) % 360.0,
-        (base_hue + 3.0 * hue_shift) % 360.0,
-    ];
-
-    // Convert analogous hues to RGB colors and add to the palette
-    for hue in &analogous_hues {
-        let color = Hsl::new(*hue, 0.7, 0.6).into_format();
-        palette.push(Srgb::from_color(color));
-    }
-
-    // Add a complementary color to the palette
-    let complementary_hue = (base_hue + 180.0) % 360.0;
-    let complementary_color = Hsl::new(complementary_hue, 0.7, 0.6).into_format();
-    palette.push(Srgb::from_color(complementary_color));
-    return palette;
-}
@@ -9,11 +9,22 @@ use winit::{
 };
 use winit_input_helper::WinitInputHelper;
 
+use euclid::Angle;
 use raqote::*;
 
 use tessellations::render::*;
-use tessellations::tessellationfigure::{TessellationFigure, TessellationPlane};
+use tessellations::tessellationfigure::{
+    Decoration, DecorationIndexPath, PointSelection, TessellationFigure, TessellationPlane,
+    TessellationStrip, Texture,
+};
+use tessellations::tessellationfrieze::FriezeGroup;
+use tessellations::tessellationhyperbolic::HyperbolicFigure;
 use tessellations::tessellationline::PointIndexPath;
+use tessellations::tessellationparametric::ParametricFigure;
+use tessellations::tessellationpolygon::ClipShape;
+use tessellations::tessellationstyle::Style;
+use tessellations::tessellationsubstitution::SubstitutionTiling;
+use tessellations::tessellationwarp::Warp;
 
 const WIDTH: u32 = 400;
 const HEIGHT: u32 = 400;
@@ -39,14 +50,24 @@ fn main() -> Result<(), Error> {
     let p = TessellationPlane {};
     let backend = Box::new(Backend);
     let svgbackend = Box::new(SVGBackend);
+    let style = Style::default();
     let mut drag: Option<(f32, f32)> = None;
     let m: Transform = Transform::scale(100.0, 100.0).then_translate(euclid::vec2(100.0, 100.0));
     let mi = m.inverse().unwrap();
-    let mut selected_point_index: Option<PointIndexPath> = None;
+    let mut selection = PointSelection::new();
+    let mut lasso: Option<Vec<Point>> = None;
+    let mut last_point: Option<Point> = None;
+    let mut selected_decoration: Option<DecorationIndexPath> = None;
+    // the parametric figure the figure is evaluated from and the parameter
+    // changed with , and .
+    let mut parametric: Option<ParametricFigure> = None;
+    let mut parameter = 0;
 
     event_loop.run(move |event, _, control_flow| {
         if let Event::RedrawRequested(_) = event {
-            let image = backend.render_plane_to_image(&p, &f, &m).unwrap();
+            let mut options = RenderOptions::new(WIDTH as f32, HEIGHT as f32, m);
+            options.style = style.clone();
+            let image = backend.render_plane_with_options(&p, &f, &options).unwrap();
             for (dst, &src) in pixels
                 .frame_mut()
                 .chunks_exact_mut(4)
@@ -78,44 +99,238 @@ fn main() -> Result<(), Error> {
                 .expect("file error");
             }
             if input.key_pressed(VirtualKeyCode::L) {
-                f = serde_json::from_str(
-                    fs::read_to_string("figure.json")
-                        .expect("file error")
-                        .as_str(),
-                )
-                .expect("json error"); //TODO set matrix
-                window.request_redraw();
+                // the exported images carry the figure as well
+                let loaded = match fs::read_to_string("figure.json") {
+                    Ok(json) => Some(serde_json::from_str(json.as_str()).expect("json error")),
+                    Err(_) => ["out.svg", "out.png"]
+                        .iter()
+                        .find_map(|path| ImageMetadata::load(std::path::Path::new(path)))
+                        .map(|metadata| metadata.figure),
+                };
+                if let Some(figure) = loaded {
+                    f = figure; //TODO set matrix
+                    selection.clear();
+                    parametric = None;
+                    window.request_redraw();
+                }
             }
             if input.key_pressed(VirtualKeyCode::E) {
-                let image = backend.render_plane_to_image(&p, &f, &m).unwrap();
+                let image = backend.render_plane_to_image(&p, &f, &m, &style).unwrap();
                 image.save_png(std::path::Path::new("out.png"));
+                // the same view at 4K
+                let mut options = RenderOptions::new(WIDTH as f32, HEIGHT as f32, m);
+                options.dpr = 3840.0 / WIDTH as f32;
+                let image = backend.render_plane_with_options(&p, &f, &options).unwrap();
+                image.save_png(std::path::Path::new("out-4k.png"));
+                // a poster of the view on A4 sheets, a millimetre per pixel
+                if let Some(pdf) = PDFBackend.print_plane_with_options(
+                    &p,
+                    &f,
+                    &RenderOptions::new(WIDTH as f32, HEIGHT as f32, m),
+                    &PDFOptions::a4(1.0),
+                ) {
+                    pdf.save_pdf(std::path::Path::new("poster.pdf"));
+                }
             }
             if input.key_pressed(VirtualKeyCode::S) {
-                let svg = svgbackend.compose_plane(&p, &f, &m).unwrap();
+                let svg = svgbackend.compose_plane(&p, &f, &m, &style).unwrap();
                 svg.save_svg(std::path::Path::new("out.svg"));
             }
+            if input.key_pressed(VirtualKeyCode::C) {
+                // a round coaster in the middle of the view
+                let mut options = RenderOptions::new(WIDTH as f32, HEIGHT as f32, m);
+                options.style = style.clone();
+                let center = m
+                    .inverse()
+                    .unwrap()
+                    .transform_point(euclid::point2(WIDTH as f32 / 2.0, HEIGHT as f32 / 2.0));
+                let shape = ClipShape::Circle {
+                    center,
+                    radius: 0.45 * WIDTH as f32 / m.m11,
+                };
+                if let Some(image) = backend.render_clipped_plane(&p, &f, &shape, &options) {
+                    image.save_png(std::path::Path::new("clipped.png"));
+                }
+                if let Some(svg) = svgbackend.compose_clipped_plane(&p, &f, &shape, &options) {
+                    svg.save_svg(std::path::Path::new("clipped.svg"));
+                }
+                // the same coaster 90 mm wide for the laser
+                let dxf = DXFBackend.draft_clipped_plane(
+                    &p,
+                    &f,
+                    &shape,
+                    &DXFOptions::new(90.0 / (2.0 * 0.45 * WIDTH as f32 / m.m11)),
+                );
+                dxf.save_dxf(std::path::Path::new("clipped.dxf"));
+            }
+            if input.key_pressed(VirtualKeyCode::X) {
+                // one period that repeats as a texture
+                if let Some(image) = backend.render_period_to_image(&p, &f, m.m11, &style) {
+                    image.save_png(std::path::Path::new("period.png"));
+                }
+                if let Some(svg) = svgbackend.compose_period(&p, &f, m.m11, &style) {
+                    svg.save_svg(std::path::Path::new("period.svg"));
+                }
+                let mut options = RenderOptions::new(WIDTH as f32, HEIGHT as f32, m);
+                options.style = style.clone();
+                if let Some(svg) = svgbackend.compose_plane_pattern(&p, &f, &options) {
+                    svg.save_svg(std::path::Path::new("pattern.svg"));
+                }
+            }
+            if input.key_pressed(VirtualKeyCode::G) {
+                // the edges of the view for a pen plotter, 20 mm per tile unit
+                let min = mi.transform_point(Point::new(0.0, 0.0));
+                let max = mi.transform_point(Point::new(WIDTH as f32, HEIGHT as f32));
+                let plot = PlotterBackend.plot_plane(&p, &f, min, max, &PlotOptions::new(20.0));
+                plot.save_svg(std::path::Path::new("plot.svg"));
+                plot.save_hpgl(std::path::Path::new("plot.hpgl"));
+            }
+            if input.key_pressed(VirtualKeyCode::M) {
+                // a tile to print and a cookie cutter, 40 mm per tile unit
+                if let Some(solid) = LyonBackend.extrude_figure(&f, &ExtrudeOptions::default()) {
+                    solid.save_stl(std::path::Path::new("tile.stl"));
+                }
+                let options = ExtrudeOptions::cutter(40.0, 12.0, 1.0);
+                if let Some(solid) = LyonBackend.extrude_figure(&f, &options) {
+                    solid.save_obj(std::path::Path::new("cutter.obj"));
+                }
+            }
+            if input.key_pressed(VirtualKeyCode::P) {
+                let t = SubstitutionTiling::penrose_kite_dart();
+                let m = Transform::scale(20.0, 20.0).then_translate(euclid::vec2(200.0, 200.0));
+                let svg = svgbackend.compose_substitution(&t, 5, &m, &style).unwrap();
+                svg.save_svg(std::path::Path::new("penrose.svg"));
+            }
+            if input.key_pressed(VirtualKeyCode::F) {
+                for group in FriezeGroup::ALL {
+                    let strip = TessellationStrip::new(group);
+                    let svg = svgbackend
+                        .compose_strip(&strip, &f, 800.0, 100.0, &style)
+                        .unwrap();
+                    svg.save_svg(std::path::Path::new(&format!(
+                        "frieze-{}.svg",
+                        group.name()
+                    )));
+                }
+            }
+            if input.key_pressed(VirtualKeyCode::W) {
+                let warp = Warp::droste(euclid::vec2(0.5, 1.0), 3.0);
+                let m = Transform::scale(0.125, 0.125);
+                let view = Transform::scale(60.0, 60.0).then_translate(euclid::vec2(200.0, 200.0));
+                let svg = svgbackend
+                    .compose_warped(&p, &f, &warp, &m, &view, &style)
+                    .unwrap();
+                svg.save_svg(std::path::Path::new("droste.svg"));
+                let image = backend
+                    .render_warped_to_image(&p, &f, &warp, &m, &view, &style)
+                    .unwrap();
+                image.save_png(std::path::Path::new("droste.png"));
+            }
+            if input.key_pressed(VirtualKeyCode::H) {
+                let h = HyperbolicFigure::new(7, 3).unwrap();
+                let m = Transform::scale(190.0, 190.0).then_translate(euclid::vec2(200.0, 200.0));
+                let svg = svgbackend.compose_hyperbolic(&h, 4, &m, &style).unwrap();
+                svg.save_svg(std::path::Path::new("hyperbolic.svg"));
+            }
+            if input.key_pressed(VirtualKeyCode::T) {
+                // paint texture.png over the bounding box of the figure
+                if let Ok(png) = fs::read("texture.png") {
+                    let points = f.points();
+                    let min = points.iter().fold(points[0], |a, p| a.min(*p));
+                    let max = points.iter().fold(points[0], |a, p| a.max(*p));
+                    f.textures.clear();
+                    f.add_texture(Texture::new(png, min, max - min));
+                    window.request_redraw();
+                }
+            }
             if input.key_pressed(VirtualKeyCode::Key1) {
                 f = TessellationFigure::square();
+                selection.clear();
+                parametric = None;
                 window.request_redraw();
             }
             if input.key_pressed(VirtualKeyCode::Key2) {
                 f = TessellationFigure::triangle();
+                selection.clear();
+                parametric = None;
                 window.request_redraw();
             }
             if input.key_pressed(VirtualKeyCode::Key3) {
                 f = TessellationFigure::square90();
+                selection.clear();
+                parametric = None;
                 window.request_redraw();
             }
             if input.key_pressed(VirtualKeyCode::Key4) {
                 f = TessellationFigure::diamond();
+                selection.clear();
+                parametric = None;
                 window.request_redraw();
             }
             if input.key_pressed(VirtualKeyCode::Key5) {
                 f = TessellationFigure::brick();
+                selection.clear();
+                parametric = None;
                 window.request_redraw();
             }
             if input.key_pressed(VirtualKeyCode::Key6) {
                 f = TessellationFigure::hexagon();
+                selection.clear();
+                parametric = None;
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::Key7) {
+                f = TessellationFigure::two_triangles();
+                selection.clear();
+                parametric = None;
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::Key8) {
+                let pf = ParametricFigure::bulge();
+                f = pf.figure().unwrap();
+                selection.clear();
+                parametric = Some(pf);
+                parameter = 0;
+                window.request_redraw();
+            }
+            if let Some(pf) = parametric.as_mut() {
+                if input.key_pressed(VirtualKeyCode::Tab) && !pf.parameters.is_empty() {
+                    parameter = (parameter + 1) % pf.parameters.len();
+                    println!("parameter {}", pf.parameters[parameter].name);
+                }
+                let steps = if input.key_pressed(VirtualKeyCode::Comma) {
+                    -5.0
+                } else if input.key_pressed(VirtualKeyCode::Period) {
+                    5.0
+                } else {
+                    0.0
+                };
+                if steps != 0.0 && pf.step(parameter, steps) {
+                    if let Ok(figure) = pf.figure() {
+                        f = figure;
+                        window.request_redraw();
+                    }
+                }
+            }
+
+            if input.key_pressed(VirtualKeyCode::D) {
+                f.add_decoration(Decoration::eye(f.center(), 0.1));
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::LBracket) {
+                f.rotate(&selection, Angle::degrees(-15.0));
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::RBracket) {
+                f.rotate(&selection, Angle::degrees(15.0));
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::Minus) {
+                f.scale(&selection, 0.9);
+                window.request_redraw();
+            }
+            if input.key_pressed(VirtualKeyCode::Equals) {
+                f.scale(&selection, 1.1);
                 window.request_redraw();
             }
 
@@ -128,32 +343,59 @@ fn main() -> Result<(), Error> {
                     match drag {
                         Some(d) => {
                             if d != mouse {
-                                if let Some(h) = selected_point_index {
-                                    f.update(h, p);
+                                if let Some(l) = lasso.as_mut() {
+                                    l.push(p);
+                                } else if let Some(h) = selected_decoration {
+                                    f.update_decoration(h, p);
+                                    window.request_redraw();
+                                } else if let Some(last) = last_point {
+                                    f.translate(&selection, p - last);
                                     window.request_redraw();
                                 }
                             }
                         }
                         _ => match f.hitpoints(p, 0.05) {
-                            Some(h) => selected_point_index = Some(h),
-                            _ => match f.hitline(p, 0.05) {
-                                Some(h) => {
-                                    f.insert(h, p);
-                                    selected_point_index = Some(PointIndexPath {
-                                        line_index: h.line_index,
-                                        point_index: h.point_index + 1,
-                                        corrp: h.corrp,
-                                    });
+                            Some(h) => {
+                                if input.held_shift() {
+                                    selection.toggle(h);
+                                } else if !selection.contains(h) {
+                                    selection = PointSelection::from(h);
                                 }
-                                _ => selected_point_index = None,
+                            }
+                            _ => match f.hitdecoration(p, 0.05) {
+                                Some(h) => selected_decoration = Some(h),
+                                _ => match f.hitline(p, 0.05) {
+                                    Some(h) => {
+                                        f.insert(h, p);
+                                        selection = PointSelection::from(PointIndexPath {
+                                            line_index: h.line_index,
+                                            point_index: h.point_index + 1,
+                                            corrp: h.corrp,
+                                        });
+                                    }
+                                    _ => lasso = Some(vec![p]),
+                                },
                             },
                         },
                     }
                     drag = Some(mouse);
+                    last_point = Some(p);
                 }
             } else if input.mouse_released(0) {
-                selected_point_index = None;
+                // rubber band select, or lasso select when control is held
+                if let Some(l) = lasso.take() {
+                    if !input.held_shift() {
+                        selection.clear();
+                    }
+                    if input.held_control() {
+                        selection.extend(f.hitpolygon(&l));
+                    } else if let Some(&last) = l.last() {
+                        selection.extend(f.hitrect(l[0], last));
+                    }
+                }
                 drag = None;
+                last_point = None;
+                selected_decoration = None;
             }
         }
     });